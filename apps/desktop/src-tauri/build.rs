@@ -1,3 +1,54 @@
+/// Verification-path dependencies and the features that would let them open
+/// network connections. `offline-only` builds fail on a manifest enabling any
+/// of them, or leaving the dependency's default features on.
+///
+/// This is a fast scan of our own manifest only; features a transitive
+/// dependency turns on are invisible to it. CI checks the resolved graph with
+/// `scripts/audit/offline-graph.sh`.
+const OFFLINE_ONLY_DEPENDENCIES: &[(&str, &[&str])] = &[
+    (
+        "alloy",
+        &[
+            "full",
+            "network",
+            "providers",
+            "provider-http",
+            "provider-ws",
+            "provider-ipc",
+            "pubsub",
+            "reqwest",
+            "rpc-client",
+            "transports",
+        ],
+    ),
+    ("revm", &["alloydb"]),
+];
+
 fn main() {
+    if std::env::var_os("CARGO_FEATURE_OFFLINE_ONLY").is_some() {
+        assert_offline_only_manifest();
+    }
     tauri_build::build()
 }
+
+fn assert_offline_only_manifest() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    let manifest = std::fs::read_to_string("Cargo.toml").expect("failed to read Cargo.toml");
+    for (dependency, network_features) in OFFLINE_ONLY_DEPENDENCIES {
+        let prefix = format!("{dependency} = ");
+        let declaration = manifest
+            .lines()
+            .find(|line| line.starts_with(&prefix))
+            .unwrap_or_else(|| panic!("offline-only: `{dependency}` is not declared"));
+        assert!(
+            declaration.contains("default-features = false"),
+            "offline-only: `{dependency}` must disable default features"
+        );
+        for feature in *network_features {
+            assert!(
+                !declaration.contains(&format!("\"{feature}\"")),
+                "offline-only: `{dependency}` enables network-capable feature `{feature}`"
+            );
+        }
+    }
+}
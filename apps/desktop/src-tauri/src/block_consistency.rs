@@ -0,0 +1,147 @@
+//! Cross-check between the simulated block and the consensus-verified block.
+//!
+//! A replay proves what the transaction does against a witness for
+//! `simulation.blockNumber`, while the consensus proof binds a state root to
+//! `verified_block_number`. Both only vouch for the package when they refer
+//! to the same block, on the same chain: sections carrying different chain
+//! ids point to a package stitched together from several sources.
+
+use serde::{Deserialize, Serialize};
+
+const ERR_SIMULATION_BLOCK_MISMATCH: &str = "simulation-block-mismatch";
+const ERR_CONSENSUS_BLOCK_UNVERIFIED: &str = "consensus-block-unverified";
+const ERR_CHAIN_ID_INCONSISTENT: &str = "chain-id-inconsistent";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockConsistencyInput {
+    /// `simulation.blockNumber` from the package.
+    pub simulation_block_number: u64,
+    /// `verified_block_number` from `verify_consensus_proof`; absent when
+    /// consensus verification did not reach an execution payload.
+    pub verified_block_number: Option<u64>,
+    /// `packageChainId` passed to `verify_consensus_proof`.
+    pub consensus_chain_id: u64,
+    /// `chainId` of the package's simulation replay input.
+    pub replay_chain_id: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockConsistencyResult {
+    pub valid: bool,
+    pub simulation_block_number: u64,
+    pub verified_block_number: Option<u64>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+pub fn verify_block_consistency(input: &BlockConsistencyInput) -> BlockConsistencyResult {
+    let failure = if input.consensus_chain_id != input.replay_chain_id {
+        Some((
+            ERR_CHAIN_ID_INCONSISTENT,
+            format!(
+                "Consensus section is for chain {} but the replay section is for chain {}.",
+                input.consensus_chain_id, input.replay_chain_id
+            ),
+        ))
+    } else {
+        block_number_failure(input)
+    };
+
+    BlockConsistencyResult {
+        valid: failure.is_none(),
+        simulation_block_number: input.simulation_block_number,
+        verified_block_number: input.verified_block_number,
+        error: failure.as_ref().map(|(_, error)| error.clone()),
+        error_code: failure.map(|(code, _)| code.to_string()),
+    }
+}
+
+fn block_number_failure(input: &BlockConsistencyInput) -> Option<(&'static str, String)> {
+    match input.verified_block_number {
+        None => Some((
+            ERR_CONSENSUS_BLOCK_UNVERIFIED,
+            "No consensus-verified block number to compare the simulation against.".to_string(),
+        )),
+        Some(verified) if verified != input.simulation_block_number => Some((
+            ERR_SIMULATION_BLOCK_MISMATCH,
+            format!(
+                "Simulation ran at block {} but consensus verified block {verified}.",
+                input.simulation_block_number
+            ),
+        )),
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_matching_block_numbers() {
+        let result = verify_block_consistency(&BlockConsistencyInput {
+            simulation_block_number: 21_000_000,
+            verified_block_number: Some(21_000_000),
+            ..Default::default()
+        });
+
+        assert!(result.valid, "{result:?}");
+        assert_eq!(result.error_code, None);
+    }
+
+    #[test]
+    fn rejects_simulation_at_a_different_block() {
+        let result = verify_block_consistency(&BlockConsistencyInput {
+            simulation_block_number: 21_000_000,
+            verified_block_number: Some(21_000_001),
+            ..Default::default()
+        });
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_SIMULATION_BLOCK_MISMATCH)
+        );
+        assert!(result
+            .error
+            .expect("error")
+            .contains("block 21000000 but consensus verified block 21000001"));
+    }
+
+    #[test]
+    fn rejects_when_consensus_did_not_verify_a_block() {
+        let result = verify_block_consistency(&BlockConsistencyInput {
+            simulation_block_number: 1,
+            verified_block_number: None,
+            ..Default::default()
+        });
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_CONSENSUS_BLOCK_UNVERIFIED)
+        );
+    }
+
+    #[test]
+    fn rejects_sections_for_different_chains() {
+        let result = verify_block_consistency(&BlockConsistencyInput {
+            simulation_block_number: 21_000_000,
+            verified_block_number: Some(21_000_000),
+            consensus_chain_id: 1,
+            replay_chain_id: 100,
+        });
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_CHAIN_ID_INCONSISTENT)
+        );
+        assert!(result
+            .error
+            .expect("error")
+            .contains("chain 1 but the replay section is for chain 100"));
+    }
+}
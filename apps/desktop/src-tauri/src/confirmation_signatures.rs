@@ -0,0 +1,569 @@
+//! Owner confirmations attached to a package, checked against the Safe tx hash.
+//!
+//! Signatures use the packed encoding `execTransaction` takes: 65-byte
+//! `r ++ s ++ v` entries, where `v = 0` marks an EIP-1271 contract signature
+//! whose `r` is the owner and whose `s` points at a length-prefixed payload
+//! after the static entries. ECDSA signers are recovered directly; contract
+//! signatures are checked by replaying `isValidSignature` against the
+//! package's witness, in the variant the Safe's version calls.
+//!
+//! As in `checkNSignatures`, signers must appear in strictly ascending
+//! address order; an entry out of order is invalid (`GS026`).
+
+use alloy::primitives::{keccak256, Address, Bytes, Signature, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::simulation_replay::{
+    replay_witness_call, ReplayTransaction, ReplayWitness, SimulationReplayInput,
+};
+
+const SIGNATURE_LENGTH: usize = 65;
+/// `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`, also the magic
+/// value a valid signature returns.
+const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+/// `bytes4(keccak256("isValidSignature(bytes,bytes)"))`, the pre-standard
+/// variant Safes before 1.5.0 call, and its magic value.
+const LEGACY_EIP1271_MAGIC_VALUE: [u8; 4] = [0x20, 0xc1, 0x3b, 0x0b];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationSignaturesInput {
+    pub chain_id: u64,
+    pub safe_address: String,
+    pub safe_tx_hash: String,
+    pub owners: Vec<String>,
+    /// Concatenated signatures, as passed to `execTransaction`.
+    pub signatures: String,
+    /// Block and accounts to replay `isValidSignature` against; required
+    /// only when a contract signature is present. Only the contract owners'
+    /// code is checked here (against `provenCodeHashes`); the rest of the
+    /// witness, including the owners' storage, is trusted as given, so pass
+    /// a witness already bound to the verified state root
+    /// (`verify_state_root_binding`).
+    pub simulation_witness: Option<ReplayWitness>,
+    /// Code hashes proven under the consensus-verified state root, keyed by
+    /// address. Every contract owner must be listed, and its witness code
+    /// must hash to the proven value.
+    #[serde(default)]
+    pub proven_code_hashes: BTreeMap<String, String>,
+    /// The Safe's `VERSION()`, e.g. `1.4.1`; selects the `isValidSignature`
+    /// variant. Required only when a contract signature is present.
+    pub safe_version: Option<String>,
+    /// `0x1901 ++ domainSeparator ++ messageHash` (see `compute_safe_tx_hash`),
+    /// the data Safes before 1.5.0 pass to `isValidSignature(bytes,bytes)`.
+    /// Must hash to `safeTxHash`; required only for contract signatures on
+    /// those versions.
+    pub safe_tx_data: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationSignaturesResult {
+    pub safe_tx_hash: String,
+    /// Owners with a valid confirmation, in signature order.
+    pub signers: Vec<String>,
+    pub signatures: Vec<ConfirmationSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationSignature {
+    /// Position among the 65-byte static entries.
+    pub index: usize,
+    pub kind: ConfirmationSignatureKind,
+    /// Recovered signer, or the owner a contract or approved-hash entry names.
+    pub signer: Option<String>,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfirmationSignatureKind {
+    /// `v` of 27/28 over the Safe tx hash.
+    Ecdsa,
+    /// `v` of 31/32 over the `eth_sign` prefixed Safe tx hash.
+    EthSign,
+    /// `v = 0`, validated through EIP-1271.
+    Contract,
+    /// `v = 1`, an on-chain `approveHash` that cannot be checked offline.
+    ApprovedHash,
+}
+
+/// Which `isValidSignature` the Safe calls on a contract owner.
+#[derive(Debug, Clone)]
+enum Eip1271Variant {
+    /// `isValidSignature(bytes,bytes)` over the encoded transaction data,
+    /// returning `0x20c13b0b` (Safe 1.4.1 and earlier).
+    Legacy(Bytes),
+    /// `isValidSignature(bytes32,bytes)` over the Safe tx hash, returning
+    /// `0x1626ba7e` (Safe 1.5.0 and later).
+    Hash,
+}
+
+pub fn verify_confirmation_signatures(
+    input: ConfirmationSignaturesInput,
+) -> Result<ConfirmationSignaturesResult, String> {
+    let safe_address = parse_address(&input.safe_address, "safeAddress")?;
+    let safe_tx_hash = B256::from_str(input.safe_tx_hash.trim())
+        .map_err(|err| format!("invalid safeTxHash ({}): {err}", input.safe_tx_hash))?;
+    let owners = input
+        .owners
+        .iter()
+        .enumerate()
+        .map(|(index, owner)| parse_address(owner, &format!("owners[{index}]")))
+        .collect::<Result<Vec<_>, _>>()?;
+    let signatures = Bytes::from_str(input.signatures.trim())
+        .map_err(|err| format!("invalid signatures: {err}"))?;
+
+    let variant = eip1271_variant(
+        input.safe_version.as_deref(),
+        input.safe_tx_data.as_deref(),
+        safe_tx_hash,
+    );
+    let proven_code_hashes = input
+        .proven_code_hashes
+        .keys()
+        .map(|address| parse_address(address, "provenCodeHashes address"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut call = input
+        .simulation_witness
+        .map(|witness| SimulationReplayInput {
+            chain_id: input.chain_id,
+            safe_address: format!("{safe_address:#x}"),
+            simulation_witness: ReplayWitness {
+                // The Safe itself calls `isValidSignature`.
+                replay_caller: None,
+                replay_calldata: None,
+                ..witness
+            },
+            proven_code_hashes: input.proven_code_hashes,
+            ..Default::default()
+        });
+
+    // Static entries end where the first contract signature payload starts.
+    let mut static_end = signatures.len();
+    let mut entries = Vec::new();
+    let mut index = 0;
+    while (index + 1) * SIGNATURE_LENGTH <= static_end {
+        let entry = &signatures[index * SIGNATURE_LENGTH..(index + 1) * SIGNATURE_LENGTH];
+        let v = entry[64];
+        let checked = match v {
+            0 => {
+                let offset = usize::try_from(U256::from_be_slice(&entry[32..64])).ok();
+                if let Some(offset) = offset {
+                    static_end = static_end.min(offset);
+                }
+                let owner = Address::from_slice(&entry[12..32]);
+                let outcome = offset
+                    .ok_or_else(|| "contract signature offset overflows".to_string())
+                    .and_then(|offset| contract_signature_payload(&signatures, offset))
+                    .and_then(|payload| {
+                        let call = call.as_mut().ok_or_else(|| {
+                            "contract signature requires simulationWitness to replay isValidSignature"
+                                .to_string()
+                        })?;
+                        if !proven_code_hashes.contains(&owner) {
+                            return Err(format!(
+                                "contract owner {owner:#x} has no entry in provenCodeHashes; its code must be proven before replaying isValidSignature"
+                            ));
+                        }
+                        let variant = variant.as_ref().map_err(Clone::clone)?;
+                        is_valid_contract_signature(call, owner, variant, safe_tx_hash, payload)
+                    });
+                (ConfirmationSignatureKind::Contract, Some(owner), outcome)
+            }
+            1 => (
+                ConfirmationSignatureKind::ApprovedHash,
+                Some(Address::from_slice(&entry[12..32])),
+                Err(
+                    "approved-hash confirmations live in Safe storage and are not checked here"
+                        .to_string(),
+                ),
+            ),
+            27 | 28 => {
+                let signer = recover_signer(entry, v == 28, safe_tx_hash);
+                (
+                    ConfirmationSignatureKind::Ecdsa,
+                    signer.as_ref().ok().copied(),
+                    signer.map(drop),
+                )
+            }
+            31 | 32 => {
+                let signer = recover_signer(entry, v == 32, eth_sign_hash(safe_tx_hash));
+                (
+                    ConfirmationSignatureKind::EthSign,
+                    signer.as_ref().ok().copied(),
+                    signer.map(drop),
+                )
+            }
+            other => {
+                return Err(format!("signature {index} has unsupported v value {other}"));
+            }
+        };
+        entries.push((index, checked));
+        index += 1;
+    }
+    if entries.is_empty() {
+        return Err("signatures holds no 65-byte signature entries".to_string());
+    }
+
+    let mut signers: Vec<String> = Vec::new();
+    let mut last_signer = Address::ZERO;
+    let signatures = entries
+        .into_iter()
+        .map(|(index, (kind, signer, outcome))| {
+            let outcome = outcome.and_then(|()| match signer {
+                Some(signer) if signer <= last_signer => Err(format!(
+                    "GS026: signer {signer:#x} does not sort above the previous signer {last_signer:#x}"
+                )),
+                Some(signer) if owners.contains(&signer) => Ok(signer),
+                Some(signer) => Err(format!("{signer:#x} is not an owner of the Safe")),
+                None => Err("no signer".to_string()),
+            });
+            // `checkNSignatures` orders by whatever each entry resolves to,
+            // valid or not.
+            if let Some(signer) = signer {
+                last_signer = signer;
+            }
+            if let Ok(signer) = outcome {
+                let signer = format!("{signer:#x}");
+                if !signers.contains(&signer) {
+                    signers.push(signer);
+                }
+            }
+            ConfirmationSignature {
+                index,
+                kind,
+                signer: signer.map(|signer| format!("{signer:#x}")),
+                valid: outcome.is_ok(),
+                error: outcome.err(),
+            }
+        })
+        .collect();
+
+    Ok(ConfirmationSignaturesResult {
+        safe_tx_hash: format!("{safe_tx_hash:#x}"),
+        signers,
+        signatures,
+    })
+}
+
+fn recover_signer(entry: &[u8], odd_y_parity: bool, hash: B256) -> Result<Address, String> {
+    Signature::from_bytes_and_parity(&entry[..64], odd_y_parity)
+        .recover_address_from_prehash(&hash)
+        .map_err(|err| format!("signature does not recover: {err}"))
+}
+
+/// `keccak256("\x19Ethereum Signed Message:\n32" ++ hash)`.
+fn eth_sign_hash(hash: B256) -> B256 {
+    let mut preimage = b"\x19Ethereum Signed Message:\n32".to_vec();
+    preimage.extend_from_slice(hash.as_slice());
+    keccak256(preimage)
+}
+
+fn contract_signature_payload(signatures: &[u8], offset: usize) -> Result<&[u8], String> {
+    let data_start = offset.saturating_add(32);
+    let length = signatures
+        .get(offset..data_start)
+        .map(U256::from_be_slice)
+        .ok_or_else(|| format!("contract signature offset {offset} is out of bounds"))?;
+    usize::try_from(length)
+        .ok()
+        .and_then(|length| signatures.get(data_start..data_start.checked_add(length)?))
+        .ok_or_else(|| format!("contract signature at offset {offset} overruns signatures"))
+}
+
+/// Safes before 1.5.0 call the legacy variant over the encoded transaction
+/// data, which must hash to `safe_tx_hash`.
+fn eip1271_variant(
+    safe_version: Option<&str>,
+    safe_tx_data: Option<&str>,
+    safe_tx_hash: B256,
+) -> Result<Eip1271Variant, String> {
+    let raw = safe_version
+        .ok_or("contract signature requires safeVersion to select the isValidSignature variant")?;
+    let mut parts = raw
+        .trim()
+        .split(['.', '+', '-'])
+        .map(|part| part.parse::<u64>().ok());
+    let (Some(Some(major)), Some(Some(minor))) = (parts.next(), parts.next()) else {
+        return Err(format!("invalid safeVersion ({raw})"));
+    };
+    if (major, minor) >= (1, 5) {
+        return Ok(Eip1271Variant::Hash);
+    }
+
+    let data = safe_tx_data.ok_or_else(|| {
+        format!("contract signature on Safe {raw} requires safeTxData for isValidSignature(bytes,bytes)")
+    })?;
+    let data = Bytes::from_str(data.trim()).map_err(|err| format!("invalid safeTxData: {err}"))?;
+    if keccak256(&data) != safe_tx_hash {
+        return Err(format!(
+            "safeTxData hashes to {:#x}, not safeTxHash {safe_tx_hash:#x}",
+            keccak256(&data)
+        ));
+    }
+    Ok(Eip1271Variant::Legacy(data))
+}
+
+/// Replays `owner.isValidSignature(..., payload)` from the Safe, over the
+/// Safe tx hash or the legacy encoded transaction data.
+fn is_valid_contract_signature(
+    call: &mut SimulationReplayInput,
+    owner: Address,
+    variant: &Eip1271Variant,
+    safe_tx_hash: B256,
+    payload: &[u8],
+) -> Result<(), String> {
+    // ABI arguments after the selector: the hash or a dynamic `bytes` for
+    // the data, then the dynamic `bytes` signature payload.
+    let (magic_value, mut arguments) = match variant {
+        Eip1271Variant::Hash => (
+            EIP1271_MAGIC_VALUE,
+            [safe_tx_hash.0, abi_word(64).0].concat(),
+        ),
+        Eip1271Variant::Legacy(data) => {
+            let payload_offset = 64 + 32 + data.len().next_multiple_of(32);
+            let mut arguments = [
+                abi_word(64).0,
+                abi_word(payload_offset).0,
+                abi_word(data.len()).0,
+            ]
+            .concat();
+            arguments.extend_from_slice(data);
+            arguments.resize(arguments.len().next_multiple_of(32), 0);
+            (LEGACY_EIP1271_MAGIC_VALUE, arguments)
+        }
+    };
+    arguments.extend_from_slice(abi_word(payload.len()).as_slice());
+    arguments.extend_from_slice(payload);
+    arguments.resize(arguments.len().next_multiple_of(32), 0);
+    let calldata = [magic_value.as_slice(), arguments.as_slice()].concat();
+    call.transaction = ReplayTransaction {
+        to: format!("{owner:#x}"),
+        value: "0".to_string(),
+        data: Some(format!("0x{}", hex::encode(calldata))),
+        ..Default::default()
+    };
+
+    let (success, return_data) = replay_witness_call(call)?;
+    if success && return_data.get(..4) == Some(magic_value.as_slice()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "isValidSignature on {owner:#x} did not return the EIP-1271 magic value"
+        ))
+    }
+}
+
+fn abi_word(value: usize) -> B256 {
+    B256::from(U256::from(value))
+}
+
+fn parse_address(raw: &str, field: &str) -> Result<Address, String> {
+    Address::from_str(raw.trim()).map_err(|err| format!("invalid {field} ({raw}): {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SAFE: &str = "0x1000000000000000000000000000000000000001";
+    /// Address of private key 1.
+    const KEY_ONE_OWNER: &str = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+    const CONTRACT_OWNER: &str = "0x000000000000000000000000000000000000c0de";
+    /// Private key 1 signing `0x1111…11` with `v = 28`.
+    const KEY_ONE_SIGNATURE: &str = "d47644539acec3da5e3ecf5fe8863c628a9c97e8b71e9ea9167a6f4f83c03c325e739d6ae8b35a0ced0b6d2f353258044ec9d0451b8c9cfb4bfd0ca42ba275f31c";
+
+    fn confirmation_input(
+        owners: &[&str],
+        signatures: String,
+        contract_code: &str,
+    ) -> ConfirmationSignaturesInput {
+        let code = hex::decode(contract_code.trim_start_matches("0x")).expect("code");
+        ConfirmationSignaturesInput {
+            chain_id: 1,
+            safe_address: SAFE.to_string(),
+            safe_tx_hash: format!("{:#x}", B256::repeat_byte(0x11)),
+            owners: owners.iter().map(|owner| owner.to_string()).collect(),
+            signatures,
+            simulation_witness: Some(
+                serde_json::from_value(json!({
+                    "replayAccounts": [{
+                        "address": CONTRACT_OWNER,
+                        "balance": "0",
+                        "nonce": 1,
+                        "code": contract_code
+                    }]
+                }))
+                .expect("witness"),
+            ),
+            proven_code_hashes: BTreeMap::from([(
+                CONTRACT_OWNER.to_string(),
+                format!("{:#x}", keccak256(code)),
+            )]),
+            safe_version: Some("1.5.0".to_string()),
+            safe_tx_data: None,
+        }
+    }
+
+    /// A `v = 0` entry for `CONTRACT_OWNER` followed by an empty payload.
+    fn contract_signature() -> String {
+        format!("0x{}{}", contract_entry(1), hex::encode(B256::ZERO))
+    }
+
+    /// `CONTRACT_OWNER`'s static entry, pointing past `entries` entries.
+    fn contract_entry(entries: usize) -> String {
+        format!(
+            "{}{}00",
+            hex::encode(
+                Address::from_str(CONTRACT_OWNER)
+                    .expect("owner")
+                    .into_word()
+            ),
+            hex::encode(B256::from(U256::from(entries * SIGNATURE_LENGTH))),
+        )
+    }
+
+    #[test]
+    fn recovers_ecdsa_confirmation_from_an_owner() {
+        let input = confirmation_input(&[KEY_ONE_OWNER], format!("0x{KEY_ONE_SIGNATURE}"), "0x");
+
+        let result = verify_confirmation_signatures(input).expect("verified");
+
+        assert_eq!(result.signers, vec![KEY_ONE_OWNER.to_string()]);
+        assert_eq!(result.signatures[0].kind, ConfirmationSignatureKind::Ecdsa);
+        assert!(result.signatures[0].valid);
+    }
+
+    #[test]
+    fn rejects_ecdsa_confirmation_from_a_non_owner() {
+        let input = confirmation_input(&[SAFE], format!("0x{KEY_ONE_SIGNATURE}"), "0x");
+
+        let result = verify_confirmation_signatures(input).expect("verified");
+
+        assert!(result.signers.is_empty());
+        assert_eq!(result.signatures[0].signer.as_deref(), Some(KEY_ONE_OWNER));
+        assert!(result.signatures[0]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("is not an owner")));
+    }
+
+    #[test]
+    fn validates_contract_confirmation_through_eip1271() {
+        // Runtime: PUSH4 0x1626ba7e PUSH1 0xe0 SHL PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        let accepting = confirmation_input(
+            &[CONTRACT_OWNER],
+            contract_signature(),
+            "0x631626ba7e60e01b5f5260205ff3",
+        );
+        let result = verify_confirmation_signatures(accepting).expect("verified");
+        assert_eq!(result.signers, vec![CONTRACT_OWNER.to_string()]);
+        assert_eq!(
+            result.signatures[0].kind,
+            ConfirmationSignatureKind::Contract
+        );
+
+        // Runtime: STOP (no magic value returned)
+        let rejecting = confirmation_input(&[CONTRACT_OWNER], contract_signature(), "0x00");
+        let result = verify_confirmation_signatures(rejecting).expect("verified");
+        assert!(result.signers.is_empty());
+        assert!(!result.signatures[0].valid);
+    }
+
+    #[test]
+    fn validates_legacy_contract_confirmation_on_safes_before_1_5() {
+        let safe_tx_data = [&[0x19, 0x01][..], &[0xaa; 64][..]].concat();
+        let legacy_input = |code: &str| ConfirmationSignaturesInput {
+            safe_tx_hash: format!("{:#x}", keccak256(&safe_tx_data)),
+            safe_version: Some("1.4.1".to_string()),
+            safe_tx_data: Some(format!("0x{}", hex::encode(&safe_tx_data))),
+            ..confirmation_input(&[CONTRACT_OWNER], contract_signature(), code)
+        };
+
+        // Runtime: PUSH4 0x20c13b0b PUSH1 0xe0 SHL PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        let result = verify_confirmation_signatures(legacy_input("0x6320c13b0b60e01b5f5260205ff3"))
+            .expect("verified");
+        assert_eq!(result.signers, vec![CONTRACT_OWNER.to_string()]);
+
+        // A 1.5.0-style magic value is not what a 1.4.1 Safe checks for.
+        let result = verify_confirmation_signatures(legacy_input("0x631626ba7e60e01b5f5260205ff3"))
+            .expect("verified");
+        assert!(result.signers.is_empty());
+    }
+
+    #[test]
+    fn rejects_legacy_contract_confirmation_without_matching_safe_tx_data() {
+        let input = ConfirmationSignaturesInput {
+            safe_version: Some("1.3.0".to_string()),
+            safe_tx_data: Some("0x1901".to_string()),
+            ..confirmation_input(
+                &[CONTRACT_OWNER],
+                contract_signature(),
+                "0x6320c13b0b60e01b5f5260205ff3",
+            )
+        };
+
+        let result = verify_confirmation_signatures(input).expect("verified");
+
+        assert!(result.signatures[0]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("not safeTxHash")));
+    }
+
+    #[test]
+    fn requires_proven_code_for_contract_owners() {
+        let input = ConfirmationSignaturesInput {
+            proven_code_hashes: BTreeMap::new(),
+            ..confirmation_input(
+                &[CONTRACT_OWNER],
+                contract_signature(),
+                "0x631626ba7e60e01b5f5260205ff3",
+            )
+        };
+
+        let result = verify_confirmation_signatures(input).expect("verified");
+
+        assert!(result.signers.is_empty());
+        assert!(result.signatures[0]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("provenCodeHashes")));
+    }
+
+    #[test]
+    fn rejects_signers_out_of_ascending_order() {
+        let accepting = "0x631626ba7e60e01b5f5260205ff3";
+        let owners = [CONTRACT_OWNER, KEY_ONE_OWNER];
+        let payload = hex::encode(B256::ZERO);
+
+        let ordered = confirmation_input(
+            &owners,
+            format!("0x{}{KEY_ONE_SIGNATURE}{payload}", contract_entry(2)),
+            accepting,
+        );
+        let result = verify_confirmation_signatures(ordered).expect("verified");
+        assert_eq!(
+            result.signers,
+            vec![CONTRACT_OWNER.to_string(), KEY_ONE_OWNER.to_string()]
+        );
+
+        let unordered = confirmation_input(
+            &owners,
+            format!("0x{KEY_ONE_SIGNATURE}{}{payload}", contract_entry(2)),
+            accepting,
+        );
+        let result = verify_confirmation_signatures(unordered).expect("verified");
+        assert_eq!(result.signers, vec![KEY_ONE_OWNER.to_string()]);
+        assert!(result.signatures[1]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.starts_with("GS026")));
+    }
+}
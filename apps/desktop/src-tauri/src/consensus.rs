@@ -4,25 +4,41 @@
 //! to authenticate the EVM state root. This is a pure computation,
 //! no network access needed. All data comes from the evidence package.
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use alloy::primitives::{b256, fixed_bytes, B256};
 use helios_consensus_core::{
     apply_bootstrap, apply_finality_update, apply_update,
     consensus_spec::{ConsensusSpec, MainnetConsensusSpec},
-    types::{Bootstrap, FinalityUpdate, Fork, Forks, LightClientStore, Update},
+    proof::is_execution_payload_proof_valid,
+    types::{
+        Bootstrap, ExecutionPayloadHeader, FinalityUpdate, Fork, Forks, LightClientStore, Update,
+    },
     verify_bootstrap, verify_finality_update, verify_update,
 };
-use serde::{Deserialize, Serialize};
+use helios_tree_hash::TreeHash;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
-use typenum::{U1, U128, U131072, U16, U2, U2048, U4096, U512, U64, U8, U8192};
+use typenum::{Unsigned, U1, U128, U131072, U16, U2, U2048, U4096, U512, U64, U8, U8192};
+
+use crate::{
+    l2_output::{verify_output_root_proof, OutputRootProof, ProvenL2Header},
+    number_format::OutputNumberFormat,
+    offline::OFFLINE_ONLY,
+};
 
 /// Input from the frontend: the consensus proof section of an evidence package.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConsensusProofInput {
     pub checkpoint: Option<String>,
+    /// Additional trusted weak-subjectivity checkpoints. The bootstrap must
+    /// match one of these or `checkpoint`.
+    pub checkpoints: Option<Vec<String>>,
     pub bootstrap: Option<String>,
     pub updates: Option<Vec<String>>,
     pub finality_update: Option<String>,
@@ -37,31 +53,243 @@ pub struct ConsensusProofInput {
     pub block_number: u64,
     pub package_chain_id: Option<u64>,
     pub package_packaged_at: Option<String>,
+    /// Beacon mode only: when false, `valid` reflects the BLS verification
+    /// alone and the verified root is returned without comparing it to
+    /// `expectedStateRoot`.
+    #[serde(default = "default_compare_state_root")]
+    pub compare_state_root: bool,
+    /// Beacon mode only: further roots that count as a match besides
+    /// `expectedStateRoot`, for packages built around a reorg.
+    /// `matched_state_root` reports which one the verified root equals.
+    pub acceptable_state_roots: Option<Vec<String>>,
+    /// Deployment policy: beacon networks this verifier may accept. Otherwise
+    /// supported networks outside the list fail with `unsupported-network`.
+    /// `None` accepts every supported network.
+    pub allowed_networks: Option<Vec<String>>,
+    /// Keep verifying later sync committee updates after one fails and
+    /// return a per-update status in `update_results`.
+    #[serde(default)]
+    pub report_update_results: bool,
+    /// Beacon mode only: stop after verifying `bootstrap` against the trusted
+    /// checkpoint(s). Updates, finality and the state root are not checked,
+    /// so a valid result says nothing about `expectedStateRoot`.
+    #[serde(default)]
+    pub bootstrap_only: bool,
+    /// Beacon mode only: verify an archived package long after the fact.
+    /// Updates are checked against the slot at `packagePackagedAt` instead of
+    /// the verifier clock, so the verdict does not depend on when (or on
+    /// which machine) the audit runs. BLS validity of every update and of
+    /// the finality update is still required.
+    #[serde(default)]
+    pub archived_verification: bool,
+    /// OP Stack mode: L1 state root from a verified beacon consensus proof.
+    /// Anchors `proofPayload.outputRootProof`, which proves the envelope
+    /// header through the rollup's legacy L2OutputOracle. Dispute-game
+    /// outputs are not supported.
+    pub l1_verified_state_root: Option<String>,
+    /// Byte-length caps on the beacon proof JSON, checked before parsing
+    /// (default to the `DEFAULT_MAX_*` constants).
+    pub max_bootstrap_bytes: Option<usize>,
+    pub max_update_bytes: Option<usize>,
+    pub max_finality_update_bytes: Option<usize>,
+    /// Upper bound on the number of sync committee `updates`
+    /// (defaults to `DEFAULT_MAX_UPDATES`).
+    pub max_updates: Option<usize>,
+    /// Encoding for numeric string fields in the result (decimal by default).
+    #[serde(default)]
+    pub output_number_format: OutputNumberFormat,
+}
+
+impl Default for ConsensusProofInput {
+    fn default() -> Self {
+        Self {
+            checkpoint: None,
+            checkpoints: None,
+            bootstrap: None,
+            updates: None,
+            finality_update: None,
+            consensus_mode: default_consensus_mode(),
+            network: String::new(),
+            proof_payload: None,
+            state_root: String::new(),
+            expected_state_root: String::new(),
+            block_number: 0,
+            package_chain_id: None,
+            package_packaged_at: None,
+            compare_state_root: default_compare_state_root(),
+            acceptable_state_roots: None,
+            allowed_networks: None,
+            report_update_results: false,
+            bootstrap_only: false,
+            archived_verification: false,
+            l1_verified_state_root: None,
+            max_bootstrap_bytes: None,
+            max_update_bytes: None,
+            max_finality_update_bytes: None,
+            max_updates: None,
+            output_number_format: OutputNumberFormat::default(),
+        }
+    }
 }
 
 fn default_consensus_mode() -> String {
     "beacon".to_string()
 }
 
+fn default_compare_state_root() -> bool {
+    true
+}
+
+/// Bumped whenever the serialized shape of `ConsensusVerificationResult`
+/// changes. Fields each version added: 2 `next_sync_committee_root`,
+/// 3 `offline`, 4 `update_results`, 5 `l2_output_root`, 6 `input_errors`,
+/// 7 `checks_passed`/`checks_total`, 8 `verified_blob_gas_used`/
+/// `verified_excess_blob_gas`, 9 `bootstrap_finalized_slot`,
+/// 10 `verified_block_header`, 11 `matched_state_root`; 12 removed
+/// `verified_block_number_quantity`.
+pub const CONSENSUS_RESULT_SCHEMA_VERSION: u32 = 12;
+
 /// Result returned to the frontend after verification.
 #[derive(Debug, Serialize)]
 pub struct ConsensusVerificationResult {
+    /// Result schema version; always serialized first.
+    pub schema_version: u32,
     /// Whether the consensus proof is valid.
     pub valid: bool,
     /// The verified EVM state root (from the finalized execution payload).
     pub verified_state_root: Option<String>,
     /// The block number from the finalized execution payload.
     pub verified_block_number: Option<u64>,
+    /// Receipts root of the finalized execution payload (beacon mode only).
+    /// Anchors `verify_log_inclusion` proofs.
+    pub verified_receipts_root: Option<String>,
+    /// Blob gas fields of the finalized execution payload, so replay can seed
+    /// its blob context from proven values. Beacon mode on Deneb+ headers
+    /// only; `None` before Cancun.
+    pub verified_blob_gas_used: Option<u64>,
+    pub verified_excess_blob_gas: Option<u64>,
+    /// Block context of the finalized execution payload, so replay can build
+    /// its `BlockEnv` from proven values instead of the witness (beacon mode
+    /// only).
+    pub verified_block_header: Option<VerifiedBlockHeader>,
     /// Whether the verified state root matches the claimed one.
     pub state_root_matches: bool,
-    /// Number of sync committee participants (out of 512).
+    /// The expected or acceptable root the verified root matched.
+    pub matched_state_root: Option<String>,
+    /// Number of sync committee participants (out of `sync_committee_size`).
     pub sync_committee_participants: u64,
+    /// Sync committee size of the verified network's spec (beacon mode only).
+    pub sync_committee_size: Option<u64>,
+    /// `sync_committee_participants / sync_committee_size` (beacon mode only).
+    pub participation_fraction: Option<f64>,
+    /// Fork active at the finalized header's epoch, e.g. `"electra"`
+    /// (beacon mode only).
+    pub finalized_fork: Option<String>,
+    /// SSZ root of the store's next sync committee after all updates, for
+    /// continuing the light client chain (beacon mode only).
+    pub next_sync_committee_root: Option<String>,
+    /// Finalized slot of the store initialized from the bootstrap; set only
+    /// under `bootstrapOnly`.
+    pub bootstrap_finalized_slot: Option<u64>,
     /// Human-readable error if verification failed.
     pub error: Option<String>,
     /// Machine-readable error code for deterministic trust-boundary handling.
     pub error_code: Option<String>,
     /// Individual check results.
     pub checks: Vec<ConsensusCheck>,
+    /// Number of `checks` that passed, for "N/M checks passed" summaries.
+    pub checks_passed: usize,
+    /// `checks.len()`.
+    pub checks_total: usize,
+    /// Per-update outcome, set only when `reportUpdateResults` is on. `valid`
+    /// still requires every update to apply.
+    pub update_results: Option<Vec<ConsensusUpdateStatus>>,
+    /// OP Stack output root that proved the envelope header against
+    /// `l1VerifiedStateRoot`, when an `outputRootProof` was supplied. Only
+    /// legacy L2OutputOracle outputs can be proven, not dispute-game ones.
+    pub l2_output_root: Option<String>,
+    /// Every malformed input field, set only with error code `invalid-input`.
+    pub input_errors: Option<Vec<String>>,
+    /// Built with the `offline-only` feature.
+    pub offline: bool,
+}
+
+/// Execution payload header fields of the verified finalized block.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VerifiedBlockHeader {
+    pub block_hash: String,
+    pub parent_hash: String,
+    pub fee_recipient: String,
+    pub state_root: String,
+    pub receipts_root: String,
+    pub prev_randao: String,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    /// Wei, encoded per `outputNumberFormat`.
+    pub base_fee_per_gas: String,
+}
+
+impl Default for ConsensusVerificationResult {
+    fn default() -> Self {
+        Self {
+            schema_version: CONSENSUS_RESULT_SCHEMA_VERSION,
+            valid: false,
+            verified_state_root: None,
+            verified_block_number: None,
+            verified_receipts_root: None,
+            verified_blob_gas_used: None,
+            verified_excess_blob_gas: None,
+            verified_block_header: None,
+            state_root_matches: false,
+            matched_state_root: None,
+            sync_committee_participants: 0,
+            sync_committee_size: None,
+            participation_fraction: None,
+            finalized_fork: None,
+            next_sync_committee_root: None,
+            bootstrap_finalized_slot: None,
+            error: None,
+            error_code: None,
+            checks: Vec::new(),
+            checks_passed: 0,
+            checks_total: 0,
+            update_results: None,
+            l2_output_root: None,
+            input_errors: None,
+            offline: OFFLINE_ONLY,
+        }
+    }
+}
+
+/// Pass/fail view of a `ConsensusVerificationResult` for dashboards that
+/// do not render the checks.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConsensusVerificationSummary {
+    pub schema_version: u32,
+    pub valid: bool,
+    pub error_code: Option<String>,
+    pub verified_state_root: Option<String>,
+    pub verified_block_number: Option<u64>,
+    pub state_root_matches: bool,
+    pub checks_passed: usize,
+    pub checks_total: usize,
+}
+
+impl From<&ConsensusVerificationResult> for ConsensusVerificationSummary {
+    fn from(result: &ConsensusVerificationResult) -> Self {
+        Self {
+            schema_version: result.schema_version,
+            valid: result.valid,
+            error_code: result.error_code.clone(),
+            verified_state_root: result.verified_state_root.clone(),
+            verified_block_number: result.verified_block_number,
+            state_root_matches: result.state_root_matches,
+            checks_passed: result.checks_passed,
+            checks_total: result.checks_total,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -72,6 +300,36 @@ pub struct ConsensusCheck {
     pub detail: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConsensusUpdateStatus {
+    pub index: usize,
+    pub applied: bool,
+    pub error: Option<String>,
+    /// Store's finalized slot around an applied update. Equal values mean
+    /// the update only rotated the sync committee.
+    pub finalized_slot_before: Option<u64>,
+    pub finalized_slot_after: Option<u64>,
+}
+
+/// Finalized slot of the store before and after applying one update.
+struct FinalizedProgress {
+    before: u64,
+    after: u64,
+}
+
+/// A sync committee update that failed to parse or verify.
+struct UpdateFailure {
+    index: usize,
+    code: &'static str,
+    error: String,
+    detail: String,
+}
+
+struct UpdateWalk {
+    statuses: Vec<ConsensusUpdateStatus>,
+    failures: Vec<UpdateFailure>,
+}
+
 /// Network configuration for beacon chain consensus.
 struct NetworkConfig {
     genesis_root: B256,
@@ -80,7 +338,7 @@ struct NetworkConfig {
     forks: Forks,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum ConsensusNetwork {
     Mainnet,
     Sepolia,
@@ -89,6 +347,25 @@ enum ConsensusNetwork {
     Gnosis,
 }
 
+/// Mainnet weak-subjectivity checkpoints, as `(epoch, block root)`, each
+/// cross-checked against independent sources (block explorers, checkpoint
+/// sync providers) before being added here. Matching one only adds an informational check;
+/// any trusted checkpoint still verifies the same way.
+const KNOWN_MAINNET_CHECKPOINTS: &[(u64, B256)] = &[
+    // Beacon chain genesis block (slot 0), finalized by definition.
+    (
+        0,
+        b256!("4d611d5b93fdab69013a7f0a2f961caca0c853f87cfe9595fe50038163079360"),
+    ),
+];
+
+fn known_checkpoints(network: ConsensusNetwork) -> &'static [(u64, B256)] {
+    match network {
+        ConsensusNetwork::Mainnet => KNOWN_MAINNET_CHECKPOINTS,
+        _ => &[],
+    }
+}
+
 fn mainnet_config() -> NetworkConfig {
     NetworkConfig {
         genesis_root: b256!("4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"),
@@ -280,20 +557,79 @@ fn hoodi_config() -> NetworkConfig {
     }
 }
 
+/// Canonical id and accepted aliases of every supported network.
+const SUPPORTED_NETWORKS: &[(ConsensusNetwork, &str, &[&str])] = &[
+    (ConsensusNetwork::Mainnet, "mainnet", &[]),
+    (ConsensusNetwork::Sepolia, "sepolia", &[]),
+    (ConsensusNetwork::Holesky, "holesky", &[]),
+    (ConsensusNetwork::Hoodi, "hoodi", &[]),
+    (ConsensusNetwork::Gnosis, "gnosis", &["xdai"]),
+];
+
 fn parse_network(network: &str) -> Result<ConsensusNetwork, String> {
-    match network {
-        "mainnet" => Ok(ConsensusNetwork::Mainnet),
-        "sepolia" => Ok(ConsensusNetwork::Sepolia),
-        "holesky" => Ok(ConsensusNetwork::Holesky),
-        "hoodi" => Ok(ConsensusNetwork::Hoodi),
-        "gnosis" | "xdai" => Ok(ConsensusNetwork::Gnosis),
-        _ => Err(format!(
-            "Unsupported network for consensus verification: {}. Only mainnet, sepolia, holesky, hoodi, and gnosis are currently supported.",
-            network
-        )),
+    SUPPORTED_NETWORKS
+        .iter()
+        .find(|(_, id, aliases)| *id == network || aliases.contains(&network))
+        .map(|(parsed, _, _)| *parsed)
+        .ok_or_else(|| {
+            format!(
+                "Unsupported network for consensus verification: {}. Only mainnet, sepolia, holesky, hoodi, and gnosis are currently supported.",
+                network
+            )
+        })
+}
+
+/// Summary of a supported network for frontends building a network picker.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SupportedNetwork {
+    pub id: String,
+    pub aliases: Vec<String>,
+    pub genesis_time: u64,
+    pub seconds_per_slot: u64,
+}
+
+/// Every network `parse_network` accepts, with its `get_network_config`
+/// timing.
+pub fn supported_networks() -> Vec<SupportedNetwork> {
+    SUPPORTED_NETWORKS
+        .iter()
+        .map(|(network, id, aliases)| {
+            let config = get_network_config(*network);
+            SupportedNetwork {
+                id: id.to_string(),
+                aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+                genesis_time: config.genesis_time,
+                seconds_per_slot: config.seconds_per_slot,
+            }
+        })
+        .collect()
+}
+
+/// Like `parse_network`, but also rejects networks missing from a
+/// deployment's `allowedNetworks` list. Aliases (e.g. `xdai`) match their
+/// canonical network.
+fn parse_allowed_network(
+    network: &str,
+    allowed_networks: Option<&[String]>,
+) -> Result<ConsensusNetwork, String> {
+    let parsed = parse_network(network)?;
+    let Some(allowed) = allowed_networks else {
+        return Ok(parsed);
+    };
+    if allowed
+        .iter()
+        .any(|entry| parse_network(entry).is_ok_and(|entry| entry == parsed))
+    {
+        return Ok(parsed);
     }
+    Err(format!(
+        "Network {} is supported but not allowed by this deployment's allowedNetworks policy ({}).",
+        network,
+        allowed.join(", ")
+    ))
 }
 
+const ERR_INVALID_INPUT: &str = "invalid-input";
 const ERR_UNSUPPORTED_NETWORK: &str = "unsupported-network";
 const ERR_ENVELOPE_NETWORK_MISMATCH: &str = "envelope-network-mismatch";
 const ERR_UNSUPPORTED_CONSENSUS_MODE: &str = "unsupported-consensus-mode";
@@ -304,15 +640,34 @@ const ERR_INVALID_UPDATE: &str = "invalid-update-json";
 const ERR_UPDATE_VERIFICATION_FAILED: &str = "update-verification-failed";
 const ERR_INVALID_FINALITY_UPDATE: &str = "invalid-finality-update-json";
 const ERR_FINALITY_VERIFICATION_FAILED: &str = "finality-verification-failed";
+const ERR_SIGNATURE_SLOT_MISMATCH: &str = "signature-slot-mismatch";
+const ERR_MALFORMED_SYNC_AGGREGATE: &str = "malformed-sync-aggregate";
+const ERR_PERIOD_MISMATCH: &str = "sync-period-mismatch";
 const ERR_MISSING_EXECUTION_PAYLOAD: &str = "missing-execution-payload";
+const ERR_EXECUTION_PAYLOAD_UNPROVEN: &str = "execution-payload-unproven";
+const ERR_FINALITY_LINEAGE_MISMATCH: &str = "finality-lineage-mismatch";
 const ERR_INVALID_EXPECTED_STATE_ROOT: &str = "invalid-expected-state-root";
-const ERR_STATE_ROOT_MISMATCH: &str = "state-root-mismatch";
+/// Verified root differs from the expected root and the verified block is not
+/// the package block (wrong block / reorg territory).
+const ERR_STATE_ROOT_BLOCK_MISMATCH: &str = "state-root-block-mismatch";
+/// Verified root differs from the expected root for the same block (genuine
+/// state divergence).
+const ERR_STATE_ROOT_VALUE_MISMATCH: &str = "state-root-value-mismatch";
 const ERR_ENVELOPE_STATE_ROOT_MISMATCH: &str = "envelope-state-root-mismatch";
 const ERR_ENVELOPE_BLOCK_NUMBER_MISMATCH: &str = "envelope-block-number-mismatch";
 const ERR_INVALID_PROOF_PAYLOAD: &str = "invalid-proof-payload";
+const ERR_L2_OUTPUT_ROOT_UNPROVEN: &str = "l2-output-root-unproven";
 const ERR_STALE_CONSENSUS_ENVELOPE: &str = "stale-consensus-envelope";
 const ERR_NON_FINALIZED_CONSENSUS_ENVELOPE: &str = "non-finalized-consensus-envelope";
+const ERR_VERIFICATION_PANIC: &str = "verification-panic";
 const NON_BEACON_MAX_BLOCK_AGE_SECS: i64 = 24 * 60 * 60;
+/// A mainnet bootstrap or update carrying a full 512-member sync committee
+/// serializes to well under 100 KiB.
+const DEFAULT_MAX_BOOTSTRAP_BYTES: usize = 256 * 1024;
+const DEFAULT_MAX_UPDATE_BYTES: usize = 256 * 1024;
+const DEFAULT_MAX_FINALITY_UPDATE_BYTES: usize = 64 * 1024;
+/// One update per ~27 hour sync committee period; 128 covers ~5 months.
+const DEFAULT_MAX_UPDATES: usize = 128;
 const NON_BEACON_MAX_FUTURE_SKEW_SECS: i64 = 60;
 
 #[derive(Clone, Copy)]
@@ -413,6 +768,39 @@ impl ConsensusSpec for GnosisConsensusSpec {
 /// 4. Extract the EVM state root from the finalized execution payload
 /// 5. Compare it against the claimed state root
 pub fn verify_consensus_proof(input: ConsensusProofInput) -> ConsensusVerificationResult {
+    let number_format = input.output_number_format;
+    let mut result = verify_consensus_proof_for_mode(input);
+    apply_output_number_format(&mut result, number_format);
+    result.checks_passed = result.checks.iter().filter(|check| check.passed).count();
+    result.checks_total = result.checks.len();
+    result
+}
+
+fn apply_output_number_format(
+    result: &mut ConsensusVerificationResult,
+    format: OutputNumberFormat,
+) {
+    if let Some(header) = result.verified_block_header.as_mut() {
+        header.base_fee_per_gas = format.reformat(&header.base_fee_per_gas);
+    }
+}
+
+fn verify_consensus_proof_for_mode(input: ConsensusProofInput) -> ConsensusVerificationResult {
+    let input_errors = validate_consensus_input(&input);
+    if !input_errors.is_empty() {
+        return ConsensusVerificationResult {
+            input_errors: Some(input_errors.clone()),
+            ..fail_result(
+                ERR_INVALID_INPUT,
+                format!(
+                    "{} malformed input field(s): {}",
+                    input_errors.len(),
+                    input_errors.join("; ")
+                ),
+            )
+        };
+    }
+
     if input.consensus_mode != "beacon" {
         let mode = match parse_execution_consensus_mode(&input.consensus_mode) {
             Ok(mode) => mode,
@@ -421,7 +809,7 @@ pub fn verify_consensus_proof(input: ConsensusProofInput) -> ConsensusVerificati
         return verify_execution_envelope(input, mode);
     }
 
-    let network = match parse_network(&input.network) {
+    let network = match parse_allowed_network(&input.network, input.allowed_networks.as_deref()) {
         Ok(network) => network,
         Err(err) => return fail_result(ERR_UNSUPPORTED_NETWORK, err),
     };
@@ -432,6 +820,23 @@ pub fn verify_consensus_proof(input: ConsensusProofInput) -> ConsensusVerificati
     verify_consensus_proof_for_spec::<MainnetConsensusSpec>(input, network)
 }
 
+/// Shape-check `network` and `l1VerifiedStateRoot` so every malformed one is
+/// reported at once. Checkpoints and the proof JSON keep their specific codes,
+/// and `stateRoot` and `packagePackagedAt` stay with the envelope path, which
+/// fails them as `invalid-proof-payload` with the envelope's root and block.
+fn validate_consensus_input(input: &ConsensusProofInput) -> Vec<String> {
+    let mut errors = Vec::new();
+    if input.network.trim().is_empty() {
+        errors.push("network is required.".to_string());
+    }
+    if let Some(root) = input.l1_verified_state_root.as_deref() {
+        if let Err(error) = parse_b256(root) {
+            errors.push(format!("Invalid l1VerifiedStateRoot: {}", error));
+        }
+    }
+    errors
+}
+
 fn verify_execution_envelope(
     input: ConsensusProofInput,
     mode: ExecutionConsensusMode,
@@ -550,6 +955,7 @@ fn verify_execution_envelope(
             error: Some("Envelope chainId does not match package chainId.".into()),
             error_code: Some(ERR_INVALID_PROOF_PAYLOAD.into()),
             checks,
+            ..Default::default()
         };
     }
     let chain_id_is_supported = mode.supports_chain_id(envelope_chain_id);
@@ -577,6 +983,7 @@ fn verify_execution_envelope(
             )),
             error_code: Some(ERR_UNSUPPORTED_NETWORK.into()),
             checks,
+            ..Default::default()
         };
     }
 
@@ -615,6 +1022,7 @@ fn verify_execution_envelope(
             )),
             error_code: Some(ERR_ENVELOPE_NETWORK_MISMATCH.into()),
             checks,
+            ..Default::default()
         };
     }
 
@@ -647,6 +1055,7 @@ fn verify_execution_envelope(
             )),
             error_code: Some(ERR_NON_FINALIZED_CONSENSUS_ENVELOPE.into()),
             checks,
+            ..Default::default()
         };
     }
 
@@ -808,6 +1217,52 @@ fn verify_execution_envelope(
         );
     }
 
+    let l2_output_root = match payload.get("outputRootProof") {
+        Some(proof) => {
+            let proven = verify_l2_output_root(
+                proof,
+                envelope_chain_id,
+                input.l1_verified_state_root.as_deref(),
+            )
+            .and_then(|proven| {
+                if format!("{:#x}", proven.block_hash) != envelope_block_hash
+                    || format!("{:#x}", proven.state_root) != envelope_state_root
+                    || proven.block_number != envelope_block_number
+                {
+                    return Err(format!(
+                        "Proven L2 header (block {}, hash {:#x}, stateRoot {:#x}) does not match the envelope block.",
+                        proven.block_number, proven.block_hash, proven.state_root
+                    ));
+                }
+                Ok(proven)
+            })
+            .map_err(|error| format!("{error} {LEGACY_OUTPUT_ORACLE_NOTE}"));
+            checks.push(ConsensusCheck {
+                id: "l2-output-root".into(),
+                label: "Envelope header is committed to by an L1-proven L2OutputOracle output"
+                    .into(),
+                passed: proven.is_ok(),
+                detail: Some(match &proven {
+                    Ok(proven) => format!("Output root: {:#x}", proven.output_root),
+                    Err(error) => error.clone(),
+                }),
+            });
+            match proven {
+                Ok(proven) => Some(format!("{:#x}", proven.output_root)),
+                Err(error) => {
+                    return fail_result_with_context(
+                        ERR_L2_OUTPUT_ROOT_UNPROVEN,
+                        error,
+                        checks,
+                        envelope_state_root,
+                        envelope_block_number,
+                    )
+                }
+            }
+        }
+        None => None,
+    };
+
     let expected_state_root = match parse_b256(&input.expected_state_root) {
         Ok(root) => format!("{:#x}", root),
         Err(error) => {
@@ -833,8 +1288,8 @@ fn verify_execution_envelope(
             Some("Envelope root matches onchainPolicyProof.stateRoot.".into())
         } else {
             Some(format!(
-                "Mismatch: envelope says {} but onchainPolicyProof.stateRoot is {}.",
-                envelope_state_root, expected_state_root
+                "Mismatch: envelope says {} at block {} but onchainPolicyProof.stateRoot is {} (package block {}).",
+                envelope_state_root, envelope_block_number, expected_state_root, input.block_number
             ))
         },
     });
@@ -846,8 +1301,11 @@ fn verify_execution_envelope(
             state_root_matches: false,
             sync_committee_participants: 0,
             error: Some("Envelope state root does not match onchainPolicyProof.stateRoot.".into()),
-            error_code: Some(ERR_STATE_ROOT_MISMATCH.into()),
+            error_code: Some(
+                state_root_mismatch_code(envelope_block_number, input.block_number).into(),
+            ),
             checks,
+            ..Default::default()
         };
     }
 
@@ -899,6 +1357,7 @@ fn verify_execution_envelope(
             ),
             error_code: Some(ERR_INVALID_PROOF_PAYLOAD.into()),
             checks,
+            ..Default::default()
         };
     }
 
@@ -925,6 +1384,7 @@ fn verify_execution_envelope(
             ),
             error_code: Some(ERR_STALE_CONSENSUS_ENVELOPE.into()),
             checks,
+            ..Default::default()
         };
     }
 
@@ -947,9 +1407,28 @@ fn verify_execution_envelope(
         error: None,
         error_code: None,
         checks,
+        l2_output_root,
+        ..Default::default()
     }
 }
 
+const LEGACY_OUTPUT_ORACLE_NOTE: &str = "Only legacy L2OutputOracle outputs are supported; \
+     blocks proposed through dispute games cannot be proven.";
+
+fn verify_l2_output_root(
+    proof: &Value,
+    chain_id: u64,
+    l1_verified_state_root: Option<&str>,
+) -> Result<ProvenL2Header, String> {
+    let l1_state_root = l1_verified_state_root
+        .ok_or("proofPayload.outputRootProof requires l1VerifiedStateRoot from a verified L1 consensus proof.")?;
+    let l1_state_root = parse_b256(l1_state_root)
+        .map_err(|error| format!("Invalid l1VerifiedStateRoot: {}", error))?;
+    let proof = OutputRootProof::deserialize(proof)
+        .map_err(|error| format!("Invalid proofPayload.outputRootProof: {}", error))?;
+    verify_output_root_proof(chain_id, l1_state_root, &proof)
+}
+
 fn parse_rfc3339_timestamp(value: &str, field_name: &str) -> Result<i64, String> {
     OffsetDateTime::parse(value, &Rfc3339)
         .map(|timestamp| timestamp.unix_timestamp())
@@ -962,24 +1441,14 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
 ) -> ConsensusVerificationResult {
     let mut checks = Vec::new();
 
-    // Parse the checkpoint
-    let checkpoint_raw = match input.checkpoint.as_deref() {
-        Some(checkpoint) => checkpoint,
-        None => {
-            return fail_result(
-                ERR_INVALID_CHECKPOINT,
-                "Missing checkpoint for beacon consensus proof.".into(),
-            );
-        }
-    };
-    let checkpoint = match parse_b256(checkpoint_raw) {
-        Ok(c) => c,
-        Err(e) => {
-            return fail_result(
-                ERR_INVALID_CHECKPOINT,
-                format!("Invalid checkpoint hash: {}", e),
-            );
-        }
+    if let Err((code, err)) = check_proof_size_limits(&input) {
+        return fail_result(code, err);
+    }
+
+    // Parse the trusted checkpoint(s)
+    let trusted_checkpoints = match parse_trusted_checkpoints(&input) {
+        Ok(checkpoints) => checkpoints,
+        Err(err) => return fail_result(ERR_INVALID_CHECKPOINT, err),
     };
 
     // Get network config
@@ -995,7 +1464,7 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
             );
         }
     };
-    let bootstrap: Bootstrap<S> = match serde_json::from_str(bootstrap_raw) {
+    let bootstrap: Bootstrap<S> = match parse_beacon_json(bootstrap_raw) {
         Ok(b) => b,
         Err(e) => {
             return fail_result(
@@ -1005,8 +1474,35 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
         }
     };
 
+    let bootstrap_root = bootstrap.header().beacon().tree_hash_root();
+    let checkpoint = match select_checkpoint(&trusted_checkpoints, bootstrap_root) {
+        Some(checkpoint) => checkpoint,
+        None => {
+            return fail_result(
+                ERR_INVALID_CHECKPOINT,
+                format!(
+                    "Bootstrap header {:#x} matches none of the {} trusted checkpoints.",
+                    bootstrap_root,
+                    trusted_checkpoints.len()
+                ),
+            );
+        }
+    };
+
+    if let Some(check) = known_checkpoint_check(known_checkpoints(network), checkpoint) {
+        checks.push(check);
+    }
+
     // Verify bootstrap
-    match verify_bootstrap::<S>(&bootstrap, checkpoint, &config.forks) {
+    let bootstrap_span = tracing::debug_span!("consensus_bootstrap").entered();
+    let verified = catch_verification_panic("Bootstrap verification", || {
+        verify_bootstrap::<S>(&bootstrap, checkpoint, &config.forks)
+    });
+    let verified = match verified {
+        Ok(verified) => verified,
+        Err(error) => return fail_result(ERR_VERIFICATION_PANIC, error),
+    };
+    match verified {
         Ok(()) => {
             checks.push(ConsensusCheck {
                 id: "bootstrap".into(),
@@ -1034,6 +1530,7 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
                 error: Some(format!("Bootstrap verification failed: {}", e)),
                 error_code: Some(ERR_BOOTSTRAP_VERIFICATION_FAILED.into()),
                 checks,
+                ..Default::default()
             };
         }
     }
@@ -1041,6 +1538,11 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
     // Apply bootstrap to initialize the light client store
     let mut store = LightClientStore::default();
     apply_bootstrap(&mut store, &bootstrap);
+    tracing::debug!(
+        slot = store.finalized_header.beacon().slot,
+        "bootstrap applied"
+    );
+    drop(bootstrap_span);
 
     checks.push(ConsensusCheck {
         id: "store-init".into(),
@@ -1052,82 +1554,116 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
         )),
     });
 
+    if input.bootstrap_only {
+        return bootstrap_only_result(checks, store.finalized_header.beacon().slot);
+    }
+
     // Compute expected current slot
-    let current_slot = expected_current_slot_for_network(
+    let current_slot = match reference_current_slot(
+        &input,
         SystemTime::now(),
         config.genesis_time,
         config.seconds_per_slot,
-    );
+    ) {
+        Ok(slot) => slot,
+        Err(error) => return fail_result(ERR_INVALID_INPUT, error),
+    };
+    if input.archived_verification {
+        checks.push(ConsensusCheck {
+            id: "archived-reference-slot".into(),
+            label: "Archived verification reference slot".into(),
+            passed: true,
+            detail: Some(format!(
+                "Updates checked against slot {current_slot} from packagePackagedAt, capped at the verifier clock."
+            )),
+        });
+    }
 
     // Parse and verify updates
-    let mut update_count = 0;
-    for (i, update_json) in input.updates.as_deref().unwrap_or(&[]).iter().enumerate() {
-        let update: Update<S> = match serde_json::from_str(update_json) {
-            Ok(u) => u,
-            Err(e) => {
-                let error = Some(format!("Failed to parse update {}: {}", i, e));
-                let error_code = Some(ERR_INVALID_UPDATE.into());
-                checks.push(ConsensusCheck {
-                    id: format!("update-{}", i),
-                    label: format!("Sync committee update #{}", i + 1),
-                    passed: false,
-                    detail: Some(format!("Parse error: {}", e)),
+    let walk = walk_updates(
+        input.updates.as_deref().unwrap_or(&[]),
+        input.report_update_results,
+        |i, update_json| {
+            let _span = tracing::debug_span!("consensus_update", index = i).entered();
+            let update: Update<S> = parse_beacon_json(update_json).map_err(|e| UpdateFailure {
+                index: i,
+                code: ERR_INVALID_UPDATE,
+                error: format!("Failed to parse update {}: {}", i, e),
+                detail: format!("Parse error: {}", e),
+            })?;
+            let aggregate_check = sync_aggregate_check(
+                helios_consensus_core::get_bits::<S>(&update.sync_aggregate().sync_committee_bits),
+                S::SyncCommitteeSize::to_u64(),
+                &raw_sync_committee_signature(update_json),
+            );
+            if !aggregate_check.passed {
+                let detail = aggregate_check.detail.unwrap_or_default();
+                return Err(UpdateFailure {
+                    index: i,
+                    code: ERR_MALFORMED_SYNC_AGGREGATE,
+                    error: format!("Update {}: {}", i, detail),
+                    detail,
                 });
-                return ConsensusVerificationResult {
-                    valid: false,
-                    verified_state_root: None,
-                    verified_block_number: None,
-                    state_root_matches: false,
-                    sync_committee_participants: 0,
-                    error,
-                    error_code,
-                    checks,
-                };
             }
-        };
+            catch_verification_panic("Update verification", || {
+                verify_update::<S>(
+                    &update,
+                    current_slot,
+                    &store,
+                    config.genesis_root,
+                    &config.forks,
+                )
+            })
+            .map_err(|error| UpdateFailure {
+                index: i,
+                code: ERR_VERIFICATION_PANIC,
+                error: format!("Update {}: {}", i, error),
+                detail: error,
+            })?
+            .map_err(|e| UpdateFailure {
+                index: i,
+                code: ERR_UPDATE_VERIFICATION_FAILED,
+                error: format!("Update {} verification failed: {}", i, e),
+                detail: format!("Verification failed: {}", e),
+            })?;
+            let before = store.finalized_header.beacon().slot;
+            apply_update(&mut store, &update);
+            let after = store.finalized_header.beacon().slot;
+            tracing::debug!(finalized_slot = after, "update applied");
+            Ok(FinalizedProgress { before, after })
+        },
+    );
+    let update_results = input.report_update_results.then(|| walk.statuses.clone());
 
-        match verify_update::<S>(
-            &update,
-            current_slot,
-            &store,
-            config.genesis_root,
-            &config.forks,
-        ) {
-            Ok(()) => {
-                apply_update(&mut store, &update);
-                update_count += 1;
-            }
-            Err(e) => {
-                let error = Some(format!("Update {} verification failed: {}", i, e));
-                let error_code = Some(ERR_UPDATE_VERIFICATION_FAILED.into());
-                checks.push(ConsensusCheck {
-                    id: format!("update-{}", i),
-                    label: format!("Sync committee update #{}", i + 1),
-                    passed: false,
-                    detail: Some(format!("Verification failed: {}", e)),
-                });
-                return ConsensusVerificationResult {
-                    valid: false,
-                    verified_state_root: None,
-                    verified_block_number: None,
-                    state_root_matches: false,
-                    sync_committee_participants: 0,
-                    error,
-                    error_code,
-                    checks,
-                };
-            }
-        }
+    for failure in &walk.failures {
+        checks.push(ConsensusCheck {
+            id: format!("update-{}", failure.index),
+            label: format!("Sync committee update #{}", failure.index + 1),
+            passed: false,
+            detail: Some(failure.detail.clone()),
+        });
+    }
+    if let Some(failure) = walk.failures.first() {
+        return ConsensusVerificationResult {
+            valid: false,
+            error: Some(failure.error.clone()),
+            error_code: Some(failure.code.into()),
+            checks,
+            update_results,
+            ..Default::default()
+        };
     }
 
+    let update_count = walk.statuses.len();
     if update_count > 0 {
         checks.push(ConsensusCheck {
             id: "updates".into(),
             label: "Sync committee updates".into(),
             passed: true,
             detail: Some(format!(
-                "{} sync committee update(s) verified and applied.",
-                update_count
+                "{} sync committee update(s) verified and applied; finalized slot {}.",
+                update_count,
+                finalized_slot_progression(&walk.statuses)
             )),
         });
     }
@@ -1142,7 +1678,7 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
             );
         }
     };
-    let finality_update: FinalityUpdate<S> = match serde_json::from_str(finality_update_raw) {
+    let finality_update: FinalityUpdate<S> = match parse_beacon_json(finality_update_raw) {
         Ok(f) => f,
         Err(e) => {
             return fail_result(
@@ -1152,25 +1688,98 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
         }
     };
 
+    let _finality_span = tracing::debug_span!(
+        "consensus_finality",
+        signature_slot = *finality_update.signature_slot()
+    )
+    .entered();
+
+    // The sync aggregate must be signed in a slot after the attested header.
+    let signature_check = signature_slot_check(
+        *finality_update.signature_slot(),
+        finality_update.attested_header().beacon().slot,
+    );
+    if !signature_check.passed {
+        let error = signature_check.detail.clone();
+        checks.push(signature_check);
+        return ConsensusVerificationResult {
+            valid: false,
+            error,
+            error_code: Some(ERR_SIGNATURE_SLOT_MISMATCH.into()),
+            checks,
+            ..Default::default()
+        };
+    }
+    checks.push(signature_check);
+
+    // The signing committee must be one the store has verified: the current
+    // committee, or the next one once an update has supplied it.
+    let period_check = sync_period_check(
+        sync_committee_period::<S>(store.finalized_header.beacon().slot),
+        sync_committee_period::<S>(*finality_update.signature_slot()),
+        store.next_sync_committee.is_some(),
+    );
+    if !period_check.passed {
+        let error = period_check.detail.clone();
+        checks.push(period_check);
+        return ConsensusVerificationResult {
+            valid: false,
+            error,
+            error_code: Some(ERR_PERIOD_MISMATCH.into()),
+            checks,
+            ..Default::default()
+        };
+    }
+    checks.push(period_check);
+
     // Count sync committee participants
     let participants =
         helios_consensus_core::get_bits::<S>(&finality_update.sync_aggregate().sync_committee_bits);
+    let (sync_committee_size, participation_fraction) =
+        sync_committee_participation::<S>(participants);
+    tracing::debug!(participants, sync_committee_size, "sync aggregate counted");
+
+    // Reject an obviously broken aggregate before handing it to BLS.
+    let aggregate_check = sync_aggregate_check(
+        participants,
+        sync_committee_size,
+        &raw_sync_committee_signature(finality_update_raw),
+    );
+    if !aggregate_check.passed {
+        let error = aggregate_check.detail.clone();
+        checks.push(aggregate_check);
+        return ConsensusVerificationResult {
+            valid: false,
+            error,
+            error_code: Some(ERR_MALFORMED_SYNC_AGGREGATE.into()),
+            checks,
+            ..Default::default()
+        };
+    }
+    checks.push(aggregate_check);
 
-    match verify_finality_update::<S>(
-        &finality_update,
-        current_slot,
-        &store,
-        config.genesis_root,
-        &config.forks,
-    ) {
+    let verified = catch_verification_panic("Finality update verification", || {
+        verify_finality_update::<S>(
+            &finality_update,
+            current_slot,
+            &store,
+            config.genesis_root,
+            &config.forks,
+        )
+    });
+    let verified = match verified {
+        Ok(verified) => verified,
+        Err(error) => return fail_result(ERR_VERIFICATION_PANIC, error),
+    };
+    match verified {
         Ok(()) => {
             checks.push(ConsensusCheck {
                 id: "finality".into(),
                 label: "Finality update verification".into(),
                 passed: true,
                 detail: Some(format!(
-                    "BLS sync committee signature valid. {}/512 validators participated.",
-                    participants
+                    "BLS sync committee signature valid. {}/{} validators participated.",
+                    participants, sync_committee_size
                 )),
             });
         }
@@ -1187,9 +1796,12 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
                 verified_block_number: None,
                 state_root_matches: false,
                 sync_committee_participants: participants,
+                sync_committee_size: Some(sync_committee_size),
+                participation_fraction: Some(participation_fraction),
                 error: Some(format!("Finality verification failed: {}", e)),
                 error_code: Some(ERR_FINALITY_VERIFICATION_FAILED.into()),
                 checks,
+                ..Default::default()
             };
         }
     }
@@ -1197,6 +1809,29 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
     // Apply finality update to get the verified finalized header
     apply_finality_update(&mut store, &finality_update);
 
+    // Helios only adopts a finalized header newer than the store's, and
+    // otherwise leaves the store as the updates left it. Require the store
+    // to now hold exactly the header this finality update signed for, so
+    // the reported state root always comes from the update that was checked.
+    let lineage_check = finality_lineage_check(
+        store.finalized_header.beacon().tree_hash_root(),
+        store.finalized_header.beacon().slot,
+        finality_update.finalized_header().beacon().tree_hash_root(),
+        finality_update.finalized_header().beacon().slot,
+    );
+    if !lineage_check.passed {
+        let error = lineage_check.detail.clone();
+        checks.push(lineage_check);
+        return ConsensusVerificationResult {
+            valid: false,
+            error,
+            error_code: Some(ERR_FINALITY_LINEAGE_MISMATCH.into()),
+            checks,
+            ..Default::default()
+        };
+    }
+    checks.push(lineage_check);
+
     // Extract the execution state root from the verified finalized header
     let execution = match store.finalized_header.execution() {
         Ok(exec) => exec,
@@ -1208,9 +1843,98 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
         }
     };
 
-    let verified_state_root = format!("{:#x}", execution.state_root());
     let verified_block_number = *execution.block_number();
 
+    // Re-prove the extracted execution header against the signed beacon
+    // header's body root rather than relying on the store update alone.
+    let payload_check = execution_payload_check(
+        is_execution_payload_proof_valid::<S>(&store.finalized_header),
+        store.finalized_header.beacon().body_root,
+        verified_block_number,
+    );
+    if !payload_check.passed {
+        let error = payload_check.detail.clone();
+        checks.push(payload_check);
+        return ConsensusVerificationResult {
+            valid: false,
+            error,
+            error_code: Some(ERR_EXECUTION_PAYLOAD_UNPROVEN.into()),
+            checks,
+            ..Default::default()
+        };
+    }
+    checks.push(payload_check);
+
+    let verified_state_root = format!("{:#x}", execution.state_root());
+    let verified_receipts_root = format!("{:#x}", execution.receipts_root());
+    let (verified_blob_gas_used, verified_excess_blob_gas) = blob_gas_fields(execution);
+    let verified_block_header = verified_block_header(execution);
+    let finalized_fork =
+        fork_name_at_slot::<S>(&config.forks, store.finalized_header.beacon().slot);
+
+    bind_verified_state_root(
+        &input,
+        checks,
+        ConsensusVerificationResult {
+            verified_state_root: Some(verified_state_root),
+            verified_block_number: Some(verified_block_number),
+            verified_receipts_root: Some(verified_receipts_root),
+            verified_blob_gas_used,
+            verified_excess_blob_gas,
+            verified_block_header: Some(verified_block_header),
+            sync_committee_participants: participants,
+            sync_committee_size: Some(sync_committee_size),
+            participation_fraction: Some(participation_fraction),
+            finalized_fork: Some(finalized_fork.into()),
+            next_sync_committee_root: next_sync_committee_root::<S>(&store),
+            update_results,
+            ..Default::default()
+        },
+    )
+}
+
+/// Early success for `bootstrapOnly`: the bootstrap matched a trusted
+/// checkpoint and its sync committee proof verified.
+fn bootstrap_only_result(
+    checks: Vec<ConsensusCheck>,
+    finalized_slot: u64,
+) -> ConsensusVerificationResult {
+    ConsensusVerificationResult {
+        valid: true,
+        bootstrap_finalized_slot: Some(finalized_slot),
+        checks,
+        ..Default::default()
+    }
+}
+
+/// Bind a cryptographically verified beacon result to the independent policy
+/// root, or skip the binding when `compareStateRoot` is false.
+fn bind_verified_state_root(
+    input: &ConsensusProofInput,
+    mut checks: Vec<ConsensusCheck>,
+    verified: ConsensusVerificationResult,
+) -> ConsensusVerificationResult {
+    let verified_state_root = verified.verified_state_root.clone().unwrap_or_default();
+    let verified_block_number = verified.verified_block_number.unwrap_or_default();
+    let extraction_check = ConsensusCheck {
+        id: "state-root".into(),
+        label: "State root extraction".into(),
+        passed: true,
+        detail: Some(format!(
+            "Extracted state root {} from finalized block {}.",
+            verified_state_root, verified_block_number
+        )),
+    };
+
+    if !input.compare_state_root {
+        checks.push(extraction_check);
+        return ConsensusVerificationResult {
+            valid: true,
+            checks,
+            ..verified
+        };
+    }
+
     // Compare against independently sourced expected state root
     let expected_state_root = match parse_b256(&input.expected_state_root) {
         Ok(root) => format!("{:#x}", root),
@@ -1224,28 +1948,43 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
             );
         }
     };
-    let state_root_matches = verified_state_root.eq_ignore_ascii_case(&expected_state_root);
+    let mut acceptable_state_roots = Vec::new();
+    for (index, root) in input.acceptable_state_roots.iter().flatten().enumerate() {
+        match parse_b256(root) {
+            Ok(root) => acceptable_state_roots.push(format!("{:#x}", root)),
+            Err(e) => {
+                return fail_result(
+                    ERR_INVALID_EXPECTED_STATE_ROOT,
+                    format!("Invalid acceptableStateRoots[{}]: {}", index, e),
+                );
+            }
+        }
+    }
+    let acceptable_match = acceptable_state_roots
+        .iter()
+        .position(|root| verified_state_root.eq_ignore_ascii_case(root));
+    let state_root_matches = verified_state_root.eq_ignore_ascii_case(&expected_state_root)
+        || acceptable_match.is_some();
 
-    checks.push(ConsensusCheck {
-        id: "state-root".into(),
-        label: "State root extraction".into(),
-        passed: true,
-        detail: Some(format!(
-            "Extracted state root {} from finalized block {}.",
-            verified_state_root, verified_block_number
-        )),
-    });
+    checks.push(extraction_check);
 
     checks.push(ConsensusCheck {
         id: "state-root-match".into(),
         label: "State root matches independent policy root".into(),
         passed: state_root_matches,
-        detail: if state_root_matches {
+        detail: if let Some(index) = acceptable_match.filter(|_| {
+            !verified_state_root.eq_ignore_ascii_case(&expected_state_root)
+        }) {
+            Some(format!(
+                "The consensus-verified state root matches acceptableStateRoots[{}].",
+                index
+            ))
+        } else if state_root_matches {
             Some("The consensus-verified state root matches onchainPolicyProof.stateRoot.".into())
         } else {
             Some(format!(
-                "Mismatch: consensus says {} but onchainPolicyProof.stateRoot is {}.",
-                verified_state_root, expected_state_root
+                "Mismatch: consensus says {} at block {} but onchainPolicyProof.stateRoot is {} (package block {}).",
+                verified_state_root, verified_block_number, expected_state_root, input.block_number
             ))
         },
     });
@@ -1254,37 +1993,470 @@ fn verify_consensus_proof_for_spec<S: ConsensusSpec>(
         None
     } else {
         Some(format!(
-            "State root mismatch: Helios verified {} but onchainPolicyProof.stateRoot is {}.",
-            verified_state_root, expected_state_root
+            "State root mismatch: Helios verified {} at block {} but onchainPolicyProof.stateRoot is {} (package block {}).",
+            verified_state_root, verified_block_number, expected_state_root, input.block_number
         ))
     };
 
     ConsensusVerificationResult {
         valid: state_root_matches,
-        verified_state_root: Some(verified_state_root),
-        verified_block_number: Some(verified_block_number),
         state_root_matches,
-        sync_committee_participants: participants,
+        matched_state_root: state_root_matches.then(|| verified_state_root.clone()),
         error: mismatch_error,
         error_code: if state_root_matches {
             None
         } else {
-            Some(ERR_STATE_ROOT_MISMATCH.into())
+            Some(state_root_mismatch_code(verified_block_number, input.block_number).into())
         },
         checks,
+        ..verified
     }
 }
 
-fn fail_result(error_code: &str, error: String) -> ConsensusVerificationResult {
-    ConsensusVerificationResult {
-        valid: false,
-        verified_state_root: None,
-        verified_block_number: None,
-        state_root_matches: false,
+/// Reject oversized proof JSON before any of it is parsed.
+fn check_proof_size_limits(input: &ConsensusProofInput) -> Result<(), (&'static str, String)> {
+    fn within(
+        raw: Option<&str>,
+        max: usize,
+        code: &'static str,
+        field: &str,
+    ) -> Result<(), (&'static str, String)> {
+        match raw {
+            Some(raw) if raw.len() > max => Err((
+                code,
+                format!(
+                    "{} is {} bytes, exceeding the {} byte limit.",
+                    field,
+                    raw.len(),
+                    max
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    within(
+        input.bootstrap.as_deref(),
+        input
+            .max_bootstrap_bytes
+            .unwrap_or(DEFAULT_MAX_BOOTSTRAP_BYTES),
+        ERR_INVALID_BOOTSTRAP,
+        "bootstrap",
+    )?;
+    within(
+        input.finality_update.as_deref(),
+        input
+            .max_finality_update_bytes
+            .unwrap_or(DEFAULT_MAX_FINALITY_UPDATE_BYTES),
+        ERR_INVALID_FINALITY_UPDATE,
+        "finalityUpdate",
+    )?;
+    let updates = input.updates.as_deref().unwrap_or(&[]);
+    let max_updates = input.max_updates.unwrap_or(DEFAULT_MAX_UPDATES);
+    if updates.len() > max_updates {
+        return Err((
+            ERR_INVALID_UPDATE,
+            format!(
+                "updates has {} entries, exceeding the limit of {}.",
+                updates.len(),
+                max_updates
+            ),
+        ));
+    }
+    let max_update_bytes = input.max_update_bytes.unwrap_or(DEFAULT_MAX_UPDATE_BYTES);
+    for (index, update) in updates.iter().enumerate() {
+        within(
+            Some(update),
+            max_update_bytes,
+            ERR_INVALID_UPDATE,
+            &format!("updates[{}]", index),
+        )?;
+    }
+    Ok(())
+}
+
+/// Run `apply` over the updates in order, stopping at the first failure unless
+/// `keep_going` is set. A failed update is not applied, so later ones are
+/// checked against the last store that verified.
+fn walk_updates<T>(
+    updates: &[T],
+    keep_going: bool,
+    mut apply: impl FnMut(usize, &T) -> Result<FinalizedProgress, UpdateFailure>,
+) -> UpdateWalk {
+    let mut walk = UpdateWalk {
+        statuses: Vec::new(),
+        failures: Vec::new(),
+    };
+    for (index, update) in updates.iter().enumerate() {
+        match apply(index, update) {
+            Ok(progress) => walk.statuses.push(ConsensusUpdateStatus {
+                index,
+                applied: true,
+                error: None,
+                finalized_slot_before: Some(progress.before),
+                finalized_slot_after: Some(progress.after),
+            }),
+            Err(failure) => {
+                walk.statuses.push(ConsensusUpdateStatus {
+                    index,
+                    applied: false,
+                    error: Some(failure.error.clone()),
+                    finalized_slot_before: None,
+                    finalized_slot_after: None,
+                });
+                walk.failures.push(failure);
+                if !keep_going {
+                    break;
+                }
+            }
+        }
+    }
+    walk
+}
+
+/// Parses a light client object given bare, or as a beacon node's REST API
+/// returns it: wrapped in `data`, optionally next to `version` metadata.
+fn parse_beacon_json<T: DeserializeOwned>(raw: &str) -> Result<T, serde_json::Error> {
+    let value: Value = serde_json::from_str(raw)?;
+    match value {
+        Value::Object(mut fields)
+            if fields.contains_key("data")
+                && fields.keys().all(|key| key == "data" || key == "version") =>
+        {
+            serde_json::from_value(fields.remove("data").unwrap_or_default())
+        }
+        _ => serde_json::from_str(raw),
+    }
+}
+
+/// `"100 -> 200 -> 200"`: the finalized slot before the first applied update,
+/// then after each one.
+fn finalized_slot_progression(statuses: &[ConsensusUpdateStatus]) -> String {
+    let mut slots: Vec<String> = statuses
+        .iter()
+        .find_map(|status| status.finalized_slot_before)
+        .map(|slot| slot.to_string())
+        .into_iter()
+        .collect();
+    slots.extend(
+        statuses
+            .iter()
+            .filter_map(|status| status.finalized_slot_after)
+            .map(|slot| slot.to_string()),
+    );
+    slots.join(" -> ")
+}
+
+/// Name of the newest fork in `forks` whose activation epoch is at or before
+/// the epoch containing `slot`.
+fn fork_name_at_slot<S: ConsensusSpec>(forks: &Forks, slot: u64) -> &'static str {
+    let epoch = slot / S::SlotsPerEpoch::to_u64();
+    [
+        ("fulu", &forks.fulu),
+        ("electra", &forks.electra),
+        ("deneb", &forks.deneb),
+        ("capella", &forks.capella),
+        ("bellatrix", &forks.bellatrix),
+        ("altair", &forks.altair),
+    ]
+    .into_iter()
+    .find(|(_, fork)| epoch >= fork.epoch)
+    .map_or("phase0", |(name, _)| name)
+}
+
+/// `checkpoint` followed by `checkpoints`, in input order.
+fn parse_trusted_checkpoints(input: &ConsensusProofInput) -> Result<Vec<B256>, String> {
+    let mut trusted = Vec::new();
+    if let Some(checkpoint) = input.checkpoint.as_deref() {
+        trusted
+            .push(parse_b256(checkpoint).map_err(|e| format!("Invalid checkpoint hash: {}", e))?);
+    }
+    for (index, checkpoint) in input.checkpoints.iter().flatten().enumerate() {
+        trusted.push(
+            parse_b256(checkpoint)
+                .map_err(|e| format!("Invalid checkpoints[{}] hash: {}", index, e))?,
+        );
+    }
+    if trusted.is_empty() {
+        return Err("Missing checkpoint for beacon consensus proof.".into());
+    }
+    Ok(trusted)
+}
+
+/// The checkpoint the bootstrap header commits to. A lone checkpoint is
+/// returned as-is so `verify_bootstrap` reports the mismatch, as before.
+fn select_checkpoint(trusted: &[B256], bootstrap_root: B256) -> Option<B256> {
+    match trusted {
+        [only] => Some(*only),
+        _ => trusted
+            .iter()
+            .copied()
+            .find(|checkpoint| *checkpoint == bootstrap_root),
+    }
+}
+
+/// Informational check when `checkpoint` is one of the embedded `known`
+/// checkpoints; `None` otherwise, which is not a failure.
+fn known_checkpoint_check(known: &[(u64, B256)], checkpoint: B256) -> Option<ConsensusCheck> {
+    let (epoch, _) = known.iter().find(|(_, root)| *root == checkpoint)?;
+    Some(ConsensusCheck {
+        id: "known-checkpoint".into(),
+        label: "Checkpoint is a recognized known-good checkpoint".into(),
+        passed: true,
+        detail: Some(format!(
+            "Checkpoint {:#x} is the embedded checkpoint for epoch {}.",
+            checkpoint, epoch
+        )),
+    })
+}
+
+/// `None` until an update carrying the next sync committee has been applied.
+fn next_sync_committee_root<S: ConsensusSpec>(store: &LightClientStore<S>) -> Option<String> {
+    store
+        .next_sync_committee
+        .as_ref()
+        .map(|committee| format!("{:#x}", committee.tree_hash_root()))
+}
+
+/// Committee size for spec `S` and the fraction of it that signed.
+fn sync_committee_participation<S: ConsensusSpec>(participants: u64) -> (u64, f64) {
+    let size = S::SyncCommitteeSize::to_u64();
+    (size, participants as f64 / size as f64)
+}
+
+/// Spec rule `signature_slot > attested_header.beacon.slot`. A gap larger than
+/// one slot is legitimate when the slot after the attested block was missed.
+fn signature_slot_check(signature_slot: u64, attested_slot: u64) -> ConsensusCheck {
+    let passed = signature_slot > attested_slot;
+    ConsensusCheck {
+        id: "signature-slot".into(),
+        label: "Signature slot follows attested header".into(),
+        passed,
+        detail: Some(if passed {
+            format!(
+                "Sync aggregate signed at slot {} for attested slot {}.",
+                signature_slot, attested_slot
+            )
+        } else {
+            format!(
+                "Signature slot {} must be greater than attested header slot {}.",
+                signature_slot, attested_slot
+            )
+        }),
+    }
+}
+
+/// Structural sanity of a sync aggregate: at least one member signed, no
+/// more than the committee holds, and the aggregate signature is a
+/// well-formed, non-identity point.
+fn sync_aggregate_check(
+    participants: u64,
+    committee_size: u64,
+    signature: &[u8],
+) -> ConsensusCheck {
+    let bits_passed = participants > 0 && participants <= committee_size;
+    let signature_error = malformed_bls_signature(signature);
+    ConsensusCheck {
+        id: "sync-aggregate".into(),
+        label: "Sync aggregate participation bits and signature are well-formed".into(),
+        passed: bits_passed && signature_error.is_none(),
+        detail: Some(match signature_error {
+            _ if !bits_passed => format!(
+                "Sync aggregate has {} of {} participation bits set; expected between 1 and {}.",
+                participants, committee_size, committee_size
+            ),
+            Some(error) => format!("Sync aggregate signature {error}."),
+            None => format!(
+                "{} of {} sync committee bits set.",
+                participants, committee_size
+            ),
+        }),
+    }
+}
+
+/// Why a 96-byte compressed BLS12-381 G2 signature cannot be a real
+/// aggregate: wrong length, all zero, not flagged compressed, or the
+/// identity (point at infinity), which no participating committee produces.
+fn malformed_bls_signature(signature: &[u8]) -> Option<String> {
+    const COMPRESSED: u8 = 0x80;
+    const INFINITY: u8 = 0x40;
+    match signature.first() {
+        _ if signature.len() != 96 => Some(format!("is {} bytes, expected 96", signature.len())),
+        _ if signature.iter().all(|byte| *byte == 0) => Some("is all zero".into()),
+        Some(flags) if flags & COMPRESSED == 0 => {
+            Some("is not a compressed BLS12-381 point".into())
+        }
+        Some(flags) if flags & INFINITY != 0 => Some("is the point at infinity".into()),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize)]
+struct RawSyncAggregateHolder {
+    sync_aggregate: RawSyncAggregate,
+}
+
+#[derive(Deserialize)]
+struct RawSyncAggregate {
+    sync_committee_signature: String,
+}
+
+/// `sync_aggregate.sync_committee_signature` as raw bytes; helios keeps the
+/// parsed signature's bytes private. Empty when the field cannot be read.
+fn raw_sync_committee_signature(raw: &str) -> Vec<u8> {
+    parse_beacon_json::<RawSyncAggregateHolder>(raw)
+        .ok()
+        .and_then(|holder| {
+            let signature = holder.sync_aggregate.sync_committee_signature;
+            hex::decode(signature.strip_prefix("0x").unwrap_or(&signature)).ok()
+        })
+        .unwrap_or_default()
+}
+
+fn sync_committee_period<S: ConsensusSpec>(slot: u64) -> u64 {
+    slot / (S::SlotsPerEpoch::to_u64() * S::EpochsPerSyncCommitteePeriod::to_u64())
+}
+
+/// A finality update signed in the store's period uses the current committee;
+/// one period ahead is only verifiable once the next committee is known.
+fn sync_period_check(
+    store_period: u64,
+    signature_period: u64,
+    has_next_committee: bool,
+) -> ConsensusCheck {
+    let passed = signature_period == store_period
+        || (has_next_committee && signature_period == store_period + 1);
+    ConsensusCheck {
+        id: "sync-period".into(),
+        label: "Finality update signed by a verified sync committee period".into(),
+        passed,
+        detail: Some(if passed {
+            format!(
+                "Signature period {} is within reach of store period {}.",
+                signature_period, store_period
+            )
+        } else if has_next_committee {
+            format!(
+                "Signature period {} is neither store period {} nor the next period.",
+                signature_period, store_period
+            )
+        } else {
+            format!(
+                "Signature period {} differs from store period {} and no sync committee update for the next period was applied.",
+                signature_period, store_period
+            )
+        }),
+    }
+}
+
+/// Whether the store's finalized header after applying the finality update
+/// is the header that update finalized.
+fn finality_lineage_check(
+    store_root: B256,
+    store_slot: u64,
+    finalized_root: B256,
+    finalized_slot: u64,
+) -> ConsensusCheck {
+    let passed = store_root == finalized_root;
+    ConsensusCheck {
+        id: "finality-lineage".into(),
+        label: "Finality update extends the verified store".into(),
+        passed,
+        detail: Some(if passed {
+            format!(
+                "Store finalized header is the finality update's header {:#x} at slot {}.",
+                finalized_root, finalized_slot
+            )
+        } else {
+            format!(
+                "Finality update finalizes header {:#x} at slot {}, but the store finalized header is {:#x} at slot {}.",
+                finalized_root, finalized_slot, store_root, store_slot
+            )
+        }),
+    }
+}
+
+/// `(blobGasUsed, excessBlobGas)`; both `None` on pre-Deneb headers.
+fn blob_gas_fields(execution: &ExecutionPayloadHeader) -> (Option<u64>, Option<u64>) {
+    (
+        execution.blob_gas_used().ok().copied(),
+        execution.excess_blob_gas().ok().copied(),
+    )
+}
+
+fn verified_block_header(execution: &ExecutionPayloadHeader) -> VerifiedBlockHeader {
+    VerifiedBlockHeader {
+        block_hash: format!("{:#x}", execution.block_hash()),
+        parent_hash: format!("{:#x}", execution.parent_hash()),
+        fee_recipient: format!("{:#x}", execution.fee_recipient()),
+        state_root: format!("{:#x}", execution.state_root()),
+        receipts_root: format!("{:#x}", execution.receipts_root()),
+        prev_randao: format!("{:#x}", execution.prev_randao()),
+        block_number: *execution.block_number(),
+        gas_limit: *execution.gas_limit(),
+        gas_used: *execution.gas_used(),
+        timestamp: *execution.timestamp(),
+        base_fee_per_gas: execution.base_fee_per_gas().to_string(),
+    }
+}
+
+/// Whether the execution payload header's Merkle branch leads to the finalized
+/// beacon header's `body_root`.
+fn execution_payload_check(proven: bool, body_root: B256, block_number: u64) -> ConsensusCheck {
+    ConsensusCheck {
+        id: "execution-payload".into(),
+        label: "Execution payload proven against beacon body root".into(),
+        passed: proven,
+        detail: Some(if proven {
+            format!(
+                "Execution header for block {} is included under beacon body root {:#x}.",
+                block_number, body_root
+            )
+        } else {
+            format!(
+                "Execution header for block {} is not proven against beacon body root {:#x}.",
+                block_number, body_root
+            )
+        }),
+    }
+}
+
+/// Distinguish a wrong-block root mismatch from a same-block state divergence.
+fn state_root_mismatch_code(
+    verified_block_number: u64,
+    expected_block_number: u64,
+) -> &'static str {
+    if verified_block_number == expected_block_number {
+        ERR_STATE_ROOT_VALUE_MISMATCH
+    } else {
+        ERR_STATE_ROOT_BLOCK_MISMATCH
+    }
+}
+
+/// Runs a Helios verification step, turning a panic on malformed-but-parseable
+/// input into an error so the command still returns a structured result.
+fn catch_verification_panic<T>(step: &str, verify: impl FnOnce() -> T) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(verify)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        format!("{step} panicked: {message}")
+    })
+}
+
+fn fail_result(error_code: &str, error: String) -> ConsensusVerificationResult {
+    ConsensusVerificationResult {
+        valid: false,
+        verified_state_root: None,
+        verified_block_number: None,
+        state_root_matches: false,
         sync_committee_participants: 0,
         error: Some(error),
         error_code: Some(error_code.into()),
         checks: vec![],
+        ..Default::default()
     }
 }
 
@@ -1304,6 +2476,7 @@ fn fail_result_with_context(
         error: Some(error),
         error_code: Some(error_code.into()),
         checks,
+        ..Default::default()
     }
 }
 
@@ -1327,6 +2500,37 @@ fn parse_hex_u64(value: &str) -> Result<u64, String> {
         .map_err(|error| format!("Invalid proofPayload.block.number hex value: {}", error))
 }
 
+/// Latest slot an update may be signed in. Archived verification derives it
+/// from the package timestamp, allowing the same skew as envelope checks,
+/// but never past the verifier clock: a future-dated `packagedAt` must not
+/// admit updates signed in slots that have not happened yet.
+fn reference_current_slot(
+    input: &ConsensusProofInput,
+    now: SystemTime,
+    genesis_time: u64,
+    seconds_per_slot: u64,
+) -> Result<u64, String> {
+    if !input.archived_verification {
+        return Ok(expected_current_slot_for_network(
+            now,
+            genesis_time,
+            seconds_per_slot,
+        ));
+    }
+    let packaged_at = input
+        .package_packaged_at
+        .as_deref()
+        .ok_or_else(|| "packagePackagedAt is required for archived verification.".to_string())?;
+    let packaged_at = parse_rfc3339_timestamp(packaged_at, "packagePackagedAt")?;
+    let reference = u64::try_from(packaged_at.saturating_add(NON_BEACON_MAX_FUTURE_SKEW_SECS))
+        .unwrap_or_default();
+    Ok(expected_current_slot_for_network(
+        (UNIX_EPOCH + Duration::from_secs(reference)).min(now),
+        genesis_time,
+        seconds_per_slot,
+    ))
+}
+
 fn expected_current_slot_for_network(
     now: SystemTime,
     genesis_time: u64,
@@ -1338,16 +2542,40 @@ fn expected_current_slot_for_network(
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::{
-        expected_current_slot_for_network, get_network_config, parse_b256, parse_network,
-        verify_consensus_proof, ConsensusNetwork, ConsensusProofInput,
-        ERR_ENVELOPE_BLOCK_NUMBER_MISMATCH, ERR_ENVELOPE_NETWORK_MISMATCH,
-        ERR_ENVELOPE_STATE_ROOT_MISMATCH, ERR_INVALID_CHECKPOINT, ERR_INVALID_EXPECTED_STATE_ROOT,
-        ERR_INVALID_PROOF_PAYLOAD, ERR_NON_FINALIZED_CONSENSUS_ENVELOPE,
-        ERR_STALE_CONSENSUS_ENVELOPE, ERR_STATE_ROOT_MISMATCH, ERR_UNSUPPORTED_CONSENSUS_MODE,
-        ERR_UNSUPPORTED_NETWORK,
+        apply_output_number_format, bind_verified_state_root, blob_gas_fields,
+        bootstrap_only_result, catch_verification_panic, execution_payload_check,
+        expected_current_slot_for_network, finality_lineage_check, finalized_slot_progression,
+        fork_name_at_slot, get_network_config, known_checkpoint_check, known_checkpoints,
+        malformed_bls_signature, next_sync_committee_root, parse_allowed_network, parse_b256,
+        parse_beacon_json, parse_network, parse_trusted_checkpoints, raw_sync_committee_signature,
+        reference_current_slot, select_checkpoint, signature_slot_check, state_root_mismatch_code,
+        supported_networks, sync_aggregate_check, sync_committee_participation,
+        sync_committee_period, sync_period_check, verified_block_header, verify_consensus_proof,
+        walk_updates, ConsensusCheck, ConsensusNetwork, ConsensusProofInput, ConsensusUpdateStatus,
+        ConsensusVerificationResult, ConsensusVerificationSummary, FinalizedProgress,
+        GnosisConsensusSpec, UpdateFailure, UpdateWalk, CONSENSUS_RESULT_SCHEMA_VERSION,
+        DEFAULT_MAX_BOOTSTRAP_BYTES, ERR_ENVELOPE_BLOCK_NUMBER_MISMATCH,
+        ERR_ENVELOPE_NETWORK_MISMATCH, ERR_ENVELOPE_STATE_ROOT_MISMATCH,
+        ERR_EXECUTION_PAYLOAD_UNPROVEN, ERR_FINALITY_LINEAGE_MISMATCH, ERR_INVALID_BOOTSTRAP,
+        ERR_INVALID_CHECKPOINT, ERR_INVALID_EXPECTED_STATE_ROOT, ERR_INVALID_INPUT,
+        ERR_INVALID_PROOF_PAYLOAD, ERR_INVALID_UPDATE, ERR_L2_OUTPUT_ROOT_UNPROVEN,
+        ERR_MALFORMED_SYNC_AGGREGATE, ERR_NON_FINALIZED_CONSENSUS_ENVELOPE, ERR_PERIOD_MISMATCH,
+        ERR_SIGNATURE_SLOT_MISMATCH, ERR_STALE_CONSENSUS_ENVELOPE, ERR_STATE_ROOT_BLOCK_MISMATCH,
+        ERR_STATE_ROOT_VALUE_MISMATCH, ERR_UNSUPPORTED_CONSENSUS_MODE, ERR_UNSUPPORTED_NETWORK,
+        ERR_UPDATE_VERIFICATION_FAILED, ERR_VERIFICATION_PANIC,
+    };
+    use crate::{l2_output::tests::output_root_fixture, number_format::OutputNumberFormat};
+    use alloy::primitives::{b256, B256, U256};
+    use helios_consensus_core::{
+        consensus_spec::MainnetConsensusSpec,
+        types::{
+            ExecutionPayloadHeader, ExecutionPayloadHeaderCapella, ExecutionPayloadHeaderDeneb,
+            LightClientStore, SyncCommittee,
+        },
     };
+    use serde::Deserialize;
     use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
@@ -1419,6 +2647,618 @@ mod tests {
         );
     }
 
+    #[test]
+    fn selects_the_trusted_checkpoint_matching_the_bootstrap() {
+        let bootstrap_root = B256::repeat_byte(0x22);
+        let input = ConsensusProofInput {
+            checkpoints: Some(vec![
+                format!("{:#x}", B256::repeat_byte(0x11)),
+                format!("{:#x}", bootstrap_root),
+            ]),
+            ..Default::default()
+        };
+
+        let trusted = parse_trusted_checkpoints(&input).expect("valid checkpoints");
+
+        assert_eq!(
+            select_checkpoint(&trusted, bootstrap_root),
+            Some(bootstrap_root)
+        );
+        assert_eq!(select_checkpoint(&trusted, B256::repeat_byte(0x33)), None);
+    }
+
+    #[test]
+    fn keeps_a_single_checkpoint_for_bootstrap_verification() {
+        let checkpoint = B256::repeat_byte(0x11);
+
+        assert_eq!(
+            select_checkpoint(&[checkpoint], B256::repeat_byte(0x22)),
+            Some(checkpoint)
+        );
+        let err = parse_trusted_checkpoints(&ConsensusProofInput {
+            checkpoints: Some(vec!["0x1234".into()]),
+            ..Default::default()
+        })
+        .expect_err("short checkpoint");
+        assert!(err.starts_with("Invalid checkpoints[0] hash"), "{err}");
+    }
+
+    #[test]
+    fn rejects_supported_network_outside_allowlist() {
+        let allowed = vec!["mainnet".to_string()];
+
+        let err = parse_allowed_network("gnosis", Some(&allowed))
+            .expect_err("gnosis is not on the allowlist");
+        assert!(err.contains("allowedNetworks policy (mainnet)"), "{err}");
+        assert_eq!(
+            parse_allowed_network("mainnet", Some(&allowed)),
+            Ok(ConsensusNetwork::Mainnet)
+        );
+        assert_eq!(
+            parse_allowed_network("xdai", Some(&["gnosis".to_string()])),
+            Ok(ConsensusNetwork::Gnosis)
+        );
+
+        let result = verify_consensus_proof(ConsensusProofInput {
+            network: "gnosis".to_string(),
+            allowed_networks: Some(allowed),
+            ..Default::default()
+        });
+        assert!(!result.valid);
+        assert_eq!(result.error_code.as_deref(), Some(ERR_UNSUPPORTED_NETWORK));
+    }
+
+    #[test]
+    fn classifies_state_root_mismatch_at_the_package_block_as_value_mismatch() {
+        assert_eq!(
+            state_root_mismatch_code(21_000_000, 21_000_000),
+            ERR_STATE_ROOT_VALUE_MISMATCH
+        );
+    }
+
+    #[test]
+    fn classifies_state_root_mismatch_at_another_block_as_block_mismatch() {
+        assert_eq!(
+            state_root_mismatch_code(21_000_001, 21_000_000),
+            ERR_STATE_ROOT_BLOCK_MISMATCH
+        );
+    }
+
+    #[test]
+    fn rejects_signature_slot_not_after_attested_slot() {
+        let check = signature_slot_check(100, 100);
+        assert!(!check.passed);
+        assert_eq!(check.id, "signature-slot");
+        let detail = check.detail.expect("detail");
+        assert!(detail.contains("Signature slot 100"));
+        assert!(detail.contains("attested header slot 100"));
+        assert!(!signature_slot_check(99, 100).passed);
+        assert_eq!(ERR_SIGNATURE_SLOT_MISMATCH, "signature-slot-mismatch");
+    }
+
+    #[test]
+    fn extracts_blob_gas_fields_from_deneb_execution_headers() {
+        let deneb = ExecutionPayloadHeader::Deneb(ExecutionPayloadHeaderDeneb {
+            block_number: 19_426_587,
+            blob_gas_used: 393_216,
+            excess_blob_gas: 0,
+            ..Default::default()
+        });
+        let capella = ExecutionPayloadHeader::Capella(ExecutionPayloadHeaderCapella {
+            block_number: 19_426_586,
+            ..Default::default()
+        });
+
+        assert_eq!(blob_gas_fields(&deneb), (Some(393_216), Some(0)));
+        assert_eq!(blob_gas_fields(&capella), (None, None));
+    }
+
+    #[test]
+    fn extracts_the_verified_block_header_from_the_execution_payload() {
+        let execution = ExecutionPayloadHeader::Deneb(ExecutionPayloadHeaderDeneb {
+            parent_hash: B256::repeat_byte(0x11),
+            block_number: 19_426_587,
+            gas_limit: 30_000_000,
+            gas_used: 12_345_678,
+            timestamp: 1_710_338_135,
+            base_fee_per_gas: U256::from(41_732_156_373u64),
+            ..Default::default()
+        });
+
+        let header = verified_block_header(&execution);
+
+        assert_eq!(header.block_number, 19_426_587);
+        assert_eq!(header.timestamp, 1_710_338_135);
+        assert_eq!(header.base_fee_per_gas, "41732156373");
+        assert_eq!(header.gas_used, 12_345_678);
+        assert_eq!(
+            header.parent_hash,
+            format!("{:#x}", B256::repeat_byte(0x11))
+        );
+    }
+
+    #[test]
+    fn round_trips_the_base_fee_between_decimal_and_hex() {
+        let execution = ExecutionPayloadHeader::Deneb(ExecutionPayloadHeaderDeneb {
+            base_fee_per_gas: U256::from(41_732_156_373u64),
+            ..Default::default()
+        });
+        let mut result = ConsensusVerificationResult {
+            verified_block_header: Some(verified_block_header(&execution)),
+            ..Default::default()
+        };
+        let base_fee = |result: &ConsensusVerificationResult| {
+            result
+                .verified_block_header
+                .as_ref()
+                .map(|header| header.base_fee_per_gas.clone())
+        };
+
+        apply_output_number_format(&mut result, OutputNumberFormat::Hex);
+        assert_eq!(base_fee(&result).as_deref(), Some("0x9b76e2bd5"));
+
+        apply_output_number_format(&mut result, OutputNumberFormat::Decimal);
+        assert_eq!(base_fee(&result).as_deref(), Some("41732156373"));
+    }
+
+    #[test]
+    fn notes_a_recognized_checkpoint_only_when_it_is_known() {
+        let known = known_checkpoints(ConsensusNetwork::Mainnet);
+        let genesis_root =
+            b256!("4d611d5b93fdab69013a7f0a2f961caca0c853f87cfe9595fe50038163079360");
+
+        let check = known_checkpoint_check(known, genesis_root).expect("known checkpoint");
+        assert_eq!(check.id, "known-checkpoint");
+        assert!(check.passed);
+        assert!(check.detail.expect("detail").contains("epoch 0"));
+        assert!(known_checkpoint_check(known, B256::repeat_byte(0x43)).is_none());
+        assert!(
+            known_checkpoint_check(known_checkpoints(ConsensusNetwork::Gnosis), genesis_root)
+                .is_none()
+        );
+    }
+
+    /// A compressed, non-identity G2 encoding: flag bits `0b100`.
+    fn well_formed_signature() -> Vec<u8> {
+        let mut signature = vec![0x11; 96];
+        signature[0] = 0x8a;
+        signature
+    }
+
+    #[test]
+    fn rejects_an_all_zero_participation_bitfield() {
+        // An all-zero `sync_committee_bits` counts zero participants.
+        let check = sync_aggregate_check(0, 512, &well_formed_signature());
+
+        assert!(!check.passed);
+        assert_eq!(check.id, "sync-aggregate");
+        assert!(check.detail.expect("detail").contains("0 of 512"));
+        assert!(sync_aggregate_check(1, 512, &well_formed_signature()).passed);
+        assert!(!sync_aggregate_check(513, 512, &well_formed_signature()).passed);
+        assert_eq!(ERR_MALFORMED_SYNC_AGGREGATE, "malformed-sync-aggregate");
+    }
+
+    #[test]
+    fn rejects_an_all_zero_or_identity_aggregate_signature() {
+        let mut infinity = vec![0; 96];
+        infinity[0] = 0xc0;
+
+        let zero = sync_aggregate_check(400, 512, &[0; 96]);
+        let identity = sync_aggregate_check(400, 512, &infinity);
+
+        assert!(!zero.passed);
+        assert!(zero.detail.expect("detail").contains("is all zero"));
+        assert!(!identity.passed);
+        assert!(identity
+            .detail
+            .expect("detail")
+            .contains("point at infinity"));
+        assert!(malformed_bls_signature(&well_formed_signature()).is_none());
+        assert!(malformed_bls_signature(&well_formed_signature()[..48]).is_some());
+        assert!(malformed_bls_signature(&[0x11; 96]).is_some());
+
+        let update = format!(
+            r#"{{"data":{{"sync_aggregate":{{"sync_committee_bits":"0x01","sync_committee_signature":"0x{}"}}}}}}"#,
+            hex::encode(&infinity)
+        );
+        assert_eq!(raw_sync_committee_signature(&update), infinity);
+    }
+
+    #[test]
+    fn rejects_execution_header_not_proven_against_body_root() {
+        let body_root = B256::repeat_byte(0xbb);
+        let check = execution_payload_check(false, body_root, 21_000_000);
+
+        assert!(!check.passed);
+        assert_eq!(check.id, "execution-payload");
+        let detail = check.detail.expect("detail");
+        assert!(detail.contains("not proven"));
+        assert!(detail.contains(&format!("{:#x}", body_root)));
+        assert!(execution_payload_check(true, body_root, 21_000_000).passed);
+        assert_eq!(ERR_EXECUTION_PAYLOAD_UNPROVEN, "execution-payload-unproven");
+    }
+
+    #[test]
+    fn rejects_finality_update_one_period_ahead_without_committee_update() {
+        let period_slots = 32 * 256;
+        let store_period = sync_committee_period::<MainnetConsensusSpec>(10 * period_slots + 5);
+        let signature_period = sync_committee_period::<MainnetConsensusSpec>(11 * period_slots + 1);
+        assert_eq!((store_period, signature_period), (10, 11));
+
+        let check = sync_period_check(store_period, signature_period, false);
+        assert!(!check.passed);
+        assert_eq!(check.id, "sync-period");
+        assert!(check
+            .detail
+            .expect("detail")
+            .contains("no sync committee update"));
+        assert_eq!(ERR_PERIOD_MISMATCH, "sync-period-mismatch");
+
+        assert!(sync_period_check(10, 11, true).passed);
+        assert!(sync_period_check(10, 10, false).passed);
+        assert!(!sync_period_check(10, 12, true).passed);
+        assert!(!sync_period_check(10, 9, true).passed);
+    }
+
+    #[test]
+    fn rejects_a_finality_update_not_adopted_by_the_store() {
+        let store_root = B256::repeat_byte(0xaa);
+        let grafted_root = B256::repeat_byte(0xbb);
+
+        let check = finality_lineage_check(store_root, 8_200, grafted_root, 8_000);
+
+        assert!(!check.passed);
+        assert_eq!(check.id, "finality-lineage");
+        let detail = check.detail.expect("detail");
+        assert!(detail.contains(&format!("{:#x}", grafted_root)));
+        assert!(detail.contains(&format!("{:#x}", store_root)));
+        assert!(finality_lineage_check(grafted_root, 8_000, grafted_root, 8_000).passed);
+        assert_eq!(ERR_FINALITY_LINEAGE_MISMATCH, "finality-lineage-mismatch");
+    }
+
+    #[test]
+    fn parses_bare_and_data_wrapped_beacon_responses() {
+        #[derive(Debug, Clone, PartialEq, Deserialize)]
+        struct Header {
+            slot: String,
+        }
+        let header = Header {
+            slot: "8192".into(),
+        };
+
+        let bare: Header = parse_beacon_json(r#"{"slot":"8192"}"#).expect("bare");
+        let wrapped: Header = parse_beacon_json(r#"{"data":{"slot":"8192"}}"#).expect("wrapped");
+        let versioned: Header =
+            parse_beacon_json(r#"{"version":"electra","data":{"slot":"8192"}}"#)
+                .expect("versioned");
+
+        assert_eq!(bare, header);
+        assert_eq!(wrapped, header);
+        assert_eq!(versioned, header);
+        assert!(parse_beacon_json::<Header>(r#"{"data":{"slot":"1"},"slot":"2"}"#).is_err());
+    }
+
+    fn good_good_bad_walk(keep_going: bool) -> UpdateWalk {
+        walk_updates(&["good", "good", "bad"], keep_going, |index, update| {
+            if *update == "good" {
+                Ok(FinalizedProgress {
+                    before: index as u64,
+                    after: index as u64 + 1,
+                })
+            } else {
+                Err(UpdateFailure {
+                    index,
+                    code: ERR_UPDATE_VERIFICATION_FAILED,
+                    error: format!("Update {} verification failed: invalid signature", index),
+                    detail: "Verification failed: invalid signature".into(),
+                })
+            }
+        })
+    }
+
+    #[test]
+    fn reports_per_update_status_after_a_failed_update() {
+        let walk = good_good_bad_walk(true);
+
+        assert_eq!(
+            walk.statuses,
+            vec![
+                ConsensusUpdateStatus {
+                    index: 0,
+                    applied: true,
+                    error: None,
+                    finalized_slot_before: Some(0),
+                    finalized_slot_after: Some(1),
+                },
+                ConsensusUpdateStatus {
+                    index: 1,
+                    applied: true,
+                    error: None,
+                    finalized_slot_before: Some(1),
+                    finalized_slot_after: Some(2),
+                },
+                ConsensusUpdateStatus {
+                    index: 2,
+                    applied: false,
+                    error: Some("Update 2 verification failed: invalid signature".into()),
+                    finalized_slot_before: None,
+                    finalized_slot_after: None,
+                },
+            ]
+        );
+        assert_eq!(walk.failures.len(), 1);
+        assert_eq!(walk.failures[0].code, ERR_UPDATE_VERIFICATION_FAILED);
+    }
+
+    #[test]
+    fn stops_walking_updates_at_the_first_failure_by_default() {
+        let walk = walk_updates(&["good", "bad", "good"], false, |index, update| {
+            if *update == "good" {
+                Ok(FinalizedProgress {
+                    before: 0,
+                    after: 0,
+                })
+            } else {
+                Err(UpdateFailure {
+                    index,
+                    code: ERR_INVALID_UPDATE,
+                    error: "parse".into(),
+                    detail: "parse".into(),
+                })
+            }
+        });
+
+        assert_eq!(walk.statuses.len(), 2);
+        assert!(!walk.statuses[1].applied);
+    }
+
+    #[test]
+    fn lists_every_supported_network_with_its_slot_time() {
+        let networks = supported_networks();
+
+        let summary: Vec<_> = networks
+            .iter()
+            .map(|network| (network.id.as_str(), network.seconds_per_slot))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("mainnet", 12),
+                ("sepolia", 12),
+                ("holesky", 12),
+                ("hoodi", 12),
+                ("gnosis", 5),
+            ]
+        );
+        assert_eq!(networks[4].aliases, vec!["xdai".to_string()]);
+        assert_eq!(networks[0].genesis_time, 1606824023);
+        for network in &networks {
+            assert!(parse_network(&network.id).is_ok());
+            for alias in &network.aliases {
+                assert_eq!(parse_network(alias), parse_network(&network.id));
+            }
+        }
+    }
+
+    #[test]
+    fn turns_a_panicking_update_verification_into_a_failure() {
+        // Deserializable but adversarial: the participant index points past
+        // the committee, which panics inside an unchecked verifier.
+        let committee = [0u8; 4];
+        let walk = walk_updates(&[1usize, 9], true, |index, &participant| {
+            catch_verification_panic("Update verification", || committee[participant]).map_err(
+                |error| UpdateFailure {
+                    index,
+                    code: ERR_VERIFICATION_PANIC,
+                    error: format!("Update {}: {}", index, error),
+                    detail: error,
+                },
+            )?;
+            Ok(FinalizedProgress {
+                before: 0,
+                after: 0,
+            })
+        });
+
+        assert_eq!(walk.statuses.len(), 2);
+        assert!(walk.statuses[0].applied);
+        assert_eq!(walk.failures.len(), 1);
+        assert_eq!(walk.failures[0].code, ERR_VERIFICATION_PANIC);
+        assert!(walk.failures[0]
+            .detail
+            .starts_with("Update verification panicked: index out of bounds"));
+        assert_eq!(catch_verification_panic("Bootstrap", || 7), Ok(7));
+    }
+
+    #[test]
+    fn reports_finalized_slot_progression_per_update() {
+        // The first update advances finality; the second only rotates the
+        // sync committee.
+        let walk = walk_updates(&[(100, 200), (200, 200)], false, |_, &(before, after)| {
+            Ok(FinalizedProgress { before, after })
+        });
+
+        let progression: Vec<_> = walk
+            .statuses
+            .iter()
+            .map(|status| (status.finalized_slot_before, status.finalized_slot_after))
+            .collect();
+        assert_eq!(
+            progression,
+            vec![(Some(100), Some(200)), (Some(200), Some(200))]
+        );
+        assert_eq!(
+            finalized_slot_progression(&walk.statuses),
+            "100 -> 200 -> 200"
+        );
+    }
+
+    #[test]
+    fn accepts_signature_slot_after_attested_slot() {
+        assert!(signature_slot_check(101, 100).passed);
+        // A missed slot after the attested block widens the gap legitimately.
+        assert!(signature_slot_check(103, 100).passed);
+    }
+
+    #[test]
+    fn reports_committee_size_and_participation_fraction_per_spec() {
+        assert_eq!(
+            sync_committee_participation::<MainnetConsensusSpec>(384),
+            (512, 0.75)
+        );
+        assert_eq!(
+            sync_committee_participation::<GnosisConsensusSpec>(512),
+            (512, 1.0)
+        );
+    }
+
+    #[test]
+    fn reports_next_sync_committee_root_once_store_carries_one() {
+        let mut store = LightClientStore::<MainnetConsensusSpec>::default();
+        assert_eq!(next_sync_committee_root(&store), None);
+
+        store.next_sync_committee = Some(SyncCommittee::default());
+
+        // hash_tree_root of an all-zero 512-member committee.
+        assert_eq!(
+            next_sync_committee_root(&store).as_deref(),
+            Some("0x173669ae8794c057def63b20372114a628abb029354a2ef50d7a1aaa9a3dab4a")
+        );
+    }
+
+    #[test]
+    fn names_finalized_fork_from_mainnet_schedule() {
+        let forks = get_network_config(ConsensusNetwork::Mainnet).forks;
+        // Epoch 281250 (deneb starts at 269568, electra at 364032).
+        assert_eq!(
+            fork_name_at_slot::<MainnetConsensusSpec>(&forks, 9_000_000),
+            "deneb"
+        );
+        // Epoch 375000 (fulu starts at 411392).
+        assert_eq!(
+            fork_name_at_slot::<MainnetConsensusSpec>(&forks, 12_000_000),
+            "electra"
+        );
+        assert_eq!(
+            fork_name_at_slot::<MainnetConsensusSpec>(&forks, 411_392 * 32),
+            "fulu"
+        );
+        assert_eq!(
+            fork_name_at_slot::<MainnetConsensusSpec>(&forks, 0),
+            "phase0"
+        );
+    }
+
+    #[test]
+    fn serializes_schema_version_as_first_field() {
+        let json = serde_json::to_string(&ConsensusVerificationResult::default())
+            .expect("serialize consensus result");
+
+        assert_eq!(CONSENSUS_RESULT_SCHEMA_VERSION, 12);
+        assert!(
+            json.starts_with(r#"{"schema_version":12,"#),
+            "unexpected prefix: {json}"
+        );
+    }
+
+    fn verified_beacon_result() -> ConsensusVerificationResult {
+        ConsensusVerificationResult {
+            verified_state_root: Some(
+                "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+            ),
+            verified_block_number: Some(100),
+            sync_committee_participants: 400,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_valid_without_root_comparison_when_disabled() {
+        let input = ConsensusProofInput {
+            expected_state_root:
+                "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(),
+            block_number: 100,
+            compare_state_root: false,
+            ..Default::default()
+        };
+
+        let result = bind_verified_state_root(&input, vec![], verified_beacon_result());
+
+        assert!(result.valid);
+        assert!(!result.state_root_matches);
+        assert_eq!(result.error_code, None);
+        assert_eq!(
+            result.verified_state_root.as_deref(),
+            Some("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+        );
+        assert_eq!(result.verified_block_number, Some(100));
+        assert!(result
+            .checks
+            .iter()
+            .all(|check| check.id != "state-root-match"));
+    }
+
+    #[test]
+    fn rejects_differing_roots_when_comparison_enabled() {
+        let input = ConsensusProofInput {
+            expected_state_root:
+                "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(),
+            block_number: 100,
+            ..Default::default()
+        };
+
+        let result = bind_verified_state_root(&input, vec![], verified_beacon_result());
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_STATE_ROOT_VALUE_MISMATCH)
+        );
+        assert_eq!(result.sync_committee_participants, 400);
+    }
+
+    #[test]
+    fn accepts_a_verified_root_matching_an_acceptable_root() {
+        let verified_root = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let input = ConsensusProofInput {
+            expected_state_root:
+                "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(),
+            acceptable_state_roots: Some(vec![
+                "0xcccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc".into(),
+                verified_root.to_uppercase().replacen("0X", "0x", 1),
+            ]),
+            block_number: 100,
+            ..Default::default()
+        };
+
+        let result = bind_verified_state_root(&input, vec![], verified_beacon_result());
+
+        assert!(result.valid, "{:?}", result.error);
+        assert!(result.state_root_matches);
+        assert_eq!(result.matched_state_root.as_deref(), Some(verified_root));
+        let check = result
+            .checks
+            .iter()
+            .find(|check| check.id == "state-root-match")
+            .expect("state root check");
+        assert!(check
+            .detail
+            .as_deref()
+            .expect("detail")
+            .contains("acceptableStateRoots[1]"));
+
+        let without = bind_verified_state_root(
+            &ConsensusProofInput {
+                acceptable_state_roots: None,
+                ..input
+            },
+            vec![],
+            verified_beacon_result(),
+        );
+        assert!(!without.valid);
+        assert_eq!(without.matched_state_root, None);
+    }
+
     #[test]
     fn slot_calculation_respects_seconds_per_slot() {
         let now = UNIX_EPOCH + Duration::from_secs(100);
@@ -1426,6 +3266,72 @@ mod tests {
         assert_eq!(expected_current_slot_for_network(now, 0, 12), 8);
     }
 
+    #[test]
+    fn archived_verification_checks_updates_against_the_package_time() {
+        // Mainnet timing; the package is verified a year after packaging.
+        // There are no real BLS-signed update fixtures in the repo, so this
+        // exercises the reference slot rather than a full archived verify.
+        let (genesis_time, seconds_per_slot) = (1_606_824_023, 12);
+        let now = UNIX_EPOCH + Duration::from_secs(1_772_323_200 + 365 * 86_400);
+        let input = ConsensusProofInput {
+            package_packaged_at: Some("2026-03-01T00:00:00Z".into()),
+            archived_verification: true,
+            ..Default::default()
+        };
+        // Finality update signed a few minutes before packaging, months ago.
+        let signature_slot = (1_772_323_200 - 300 - genesis_time) / seconds_per_slot;
+
+        let live = reference_current_slot(
+            &ConsensusProofInput {
+                archived_verification: false,
+                ..Default::default()
+            },
+            now,
+            genesis_time,
+            seconds_per_slot,
+        )
+        .expect("live slot");
+        let archived = reference_current_slot(&input, now, genesis_time, seconds_per_slot)
+            .expect("archived slot");
+
+        assert!(live > archived);
+        assert!(archived >= signature_slot);
+        assert_eq!(
+            archived,
+            (1_772_323_200 + 60 - genesis_time) / seconds_per_slot
+        );
+        let missing = reference_current_slot(
+            &ConsensusProofInput {
+                archived_verification: true,
+                ..Default::default()
+            },
+            now,
+            genesis_time,
+            seconds_per_slot,
+        )
+        .expect_err("missing packagedAt");
+        assert!(missing.contains("packagePackagedAt is required"));
+    }
+
+    #[test]
+    fn archived_reference_slot_never_passes_the_verifier_clock() {
+        let (genesis_time, seconds_per_slot) = (1_606_824_023, 12);
+        let now = UNIX_EPOCH + Duration::from_secs(1_772_323_200);
+        let future_dated = ConsensusProofInput {
+            package_packaged_at: Some("2027-03-01T00:00:00Z".into()),
+            archived_verification: true,
+            ..Default::default()
+        };
+
+        let archived = reference_current_slot(&future_dated, now, genesis_time, seconds_per_slot)
+            .expect("archived slot");
+
+        assert_eq!(
+            archived,
+            expected_current_slot_for_network(now, genesis_time, seconds_per_slot)
+        );
+    }
+
     #[test]
     fn returns_machine_readable_error_code_for_unsupported_network() {
         let result = verify_consensus_proof(ConsensusProofInput {
@@ -1445,12 +3351,49 @@ mod tests {
             block_number: 0,
             package_chain_id: None,
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
         assert_eq!(result.error_code.as_deref(), Some(ERR_UNSUPPORTED_NETWORK));
     }
 
+    #[test]
+    fn bootstrap_only_succeeds_with_the_store_finalized_slot() {
+        let checks = vec![ConsensusCheck {
+            id: "bootstrap".into(),
+            label: "Bootstrap verification".into(),
+            passed: true,
+            detail: None,
+        }];
+
+        let result = bootstrap_only_result(checks, 8_000_000);
+
+        assert!(result.valid);
+        assert_eq!(result.bootstrap_finalized_slot, Some(8_000_000));
+        assert_eq!(result.verified_state_root, None);
+        assert!(!result.state_root_matches);
+        assert_eq!(result.error_code, None);
+    }
+
+    #[test]
+    fn bootstrap_only_fails_when_the_bootstrap_cannot_be_checked() {
+        let result = verify_consensus_proof(ConsensusProofInput {
+            checkpoints: Some(vec![
+                "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+                "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
+            ]),
+            bootstrap: Some("{}".to_string()),
+            network: "mainnet".to_string(),
+            bootstrap_only: true,
+            ..Default::default()
+        });
+
+        assert!(!result.valid);
+        assert_eq!(result.error_code.as_deref(), Some(ERR_INVALID_BOOTSTRAP));
+        assert_eq!(result.bootstrap_finalized_slot, None);
+    }
+
     #[test]
     fn returns_machine_readable_error_code_for_invalid_checkpoint() {
         let result = verify_consensus_proof(ConsensusProofInput {
@@ -1468,6 +3411,7 @@ mod tests {
             block_number: 0,
             package_chain_id: None,
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1493,6 +3437,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(result.valid);
@@ -1521,6 +3466,169 @@ mod tests {
             .any(|check| check.id == "mode-verification" && check.passed));
     }
 
+    #[test]
+    fn rejects_proof_json_beyond_size_and_count_limits() {
+        let too_many_updates = verify_consensus_proof(ConsensusProofInput {
+            network: "mainnet".to_string(),
+            bootstrap: Some("{}".to_string()),
+            updates: Some(vec!["{}".to_string(); 3]),
+            max_updates: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(
+            too_many_updates.error_code.as_deref(),
+            Some(ERR_INVALID_UPDATE)
+        );
+        assert!(too_many_updates
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("exceeding the limit of 2"));
+
+        let oversized_bootstrap = verify_consensus_proof(ConsensusProofInput {
+            network: "mainnet".to_string(),
+            bootstrap: Some(" ".repeat(DEFAULT_MAX_BOOTSTRAP_BYTES + 1)),
+            ..Default::default()
+        });
+        assert_eq!(
+            oversized_bootstrap.error_code.as_deref(),
+            Some(ERR_INVALID_BOOTSTRAP)
+        );
+        assert!(oversized_bootstrap
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("byte limit"));
+    }
+
+    #[test]
+    fn reports_every_malformed_input_field_at_once() {
+        let result = verify_consensus_proof(ConsensusProofInput {
+            consensus_mode: "opstack".to_string(),
+            network: " ".to_string(),
+            l1_verified_state_root: Some("0x1234".to_string()),
+            ..Default::default()
+        });
+
+        assert!(!result.valid);
+        assert_eq!(result.error_code.as_deref(), Some(ERR_INVALID_INPUT));
+        let input_errors = result.input_errors.expect("input errors");
+        assert_eq!(input_errors.len(), 2, "{input_errors:?}");
+        assert!(input_errors[0].contains("network"));
+        assert!(input_errors[1].contains("l1VerifiedStateRoot"));
+    }
+
+    #[test]
+    fn leaves_envelope_fields_to_the_envelope_path() {
+        let result = verify_consensus_proof(ConsensusProofInput {
+            consensus_mode: "opstack".to_string(),
+            network: "base".to_string(),
+            state_root: "0x1234".to_string(),
+            package_packaged_at: Some("yesterday".to_string()),
+            ..Default::default()
+        });
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_INVALID_PROOF_PAYLOAD)
+        );
+        assert_eq!(result.input_errors, None);
+    }
+
+    pub(crate) fn output_root_envelope_input(
+        l1_verified_state_root: Option<String>,
+    ) -> ConsensusProofInput {
+        let (l1_state_root, proof, header) = output_root_fixture(1);
+        let state_root = format!("{:#x}", header.state_root);
+        let payload = serde_json::json!({
+            "schema": "execution-block-header-v1",
+            "consensusMode": "opstack",
+            "chainId": 10,
+            "blockTag": "finalized",
+            "block": {
+                "number": "0x1",
+                "hash": format!("{:#x}", header.hash_slow()),
+                "parentHash": format!("{:#x}", header.parent_hash),
+                "stateRoot": state_root,
+                "timestamp": "2026-01-01T00:00:00Z"
+            },
+            "outputRootProof": {
+                "outputIndex": proof.output_index,
+                "oracleAccount": {
+                    "nonce": proof.oracle_account.nonce,
+                    "balance": proof.oracle_account.balance,
+                    "storageHash": proof.oracle_account.storage_hash,
+                    "codeHash": proof.oracle_account.code_hash
+                },
+                "accountProof": proof.account_proof,
+                "storageProof": proof.storage_proof,
+                "messagePasserStorageRoot": proof.message_passer_storage_root,
+                "l2Header": proof.l2_header
+            }
+        });
+        ConsensusProofInput {
+            consensus_mode: "opstack".to_string(),
+            network: "optimism".to_string(),
+            proof_payload: Some(payload.to_string()),
+            state_root: state_root.clone(),
+            expected_state_root: state_root,
+            block_number: 1,
+            package_chain_id: Some(10),
+            package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            l1_verified_state_root: l1_verified_state_root
+                .or(Some(format!("{:#x}", l1_state_root))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn proves_opstack_envelope_header_through_l1_output_root() {
+        let result = verify_consensus_proof(output_root_envelope_input(None));
+
+        assert!(result.valid, "{:?}", result.error);
+        assert!(result.l2_output_root.is_some());
+        assert!(result
+            .checks
+            .iter()
+            .any(|check| check.id == "l2-output-root" && check.passed));
+    }
+
+    #[test]
+    fn summarizes_passed_and_total_checks() {
+        let valid = verify_consensus_proof(output_root_envelope_input(None));
+        let invalid = verify_consensus_proof(output_root_envelope_input(Some(format!(
+            "{:#x}",
+            B256::repeat_byte(0x01)
+        ))));
+
+        assert!(valid.valid, "{:?}", valid.error);
+        assert!(valid.checks_total > 0);
+        assert_eq!(valid.checks_passed, valid.checks_total);
+        for result in [valid, invalid] {
+            assert_eq!(result.checks_total, result.checks.len());
+            assert_eq!(
+                result.checks_passed,
+                result.checks.iter().filter(|check| check.passed).count()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_opstack_output_root_proof_against_another_l1_root() {
+        let result = verify_consensus_proof(output_root_envelope_input(Some(format!(
+            "{:#x}",
+            B256::repeat_byte(0x01)
+        ))));
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_L2_OUTPUT_ROOT_UNPROVEN)
+        );
+        assert_eq!(result.l2_output_root, None);
+    }
+
     #[test]
     fn verifies_linea_mode_when_execution_envelope_checks_pass() {
         let result = verify_consensus_proof(ConsensusProofInput {
@@ -1540,6 +3648,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(59144),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(result.valid);
@@ -1565,6 +3674,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(42161),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1598,6 +3708,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(8453),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1630,6 +3741,7 @@ mod tests {
             block_number: 1,
             package_chain_id: None,
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1662,6 +3774,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1696,6 +3809,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1732,6 +3846,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1768,6 +3883,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(59144),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1804,6 +3920,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1845,6 +3962,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(result.valid);
@@ -1874,6 +3992,7 @@ mod tests {
             block_number: 2,
             package_chain_id: Some(59144),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1915,10 +4034,14 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
-        assert_eq!(result.error_code.as_deref(), Some(ERR_STATE_ROOT_MISMATCH));
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_STATE_ROOT_VALUE_MISMATCH)
+        );
         assert_eq!(
             result.error.as_deref(),
             Some("Envelope state root does not match onchainPolicyProof.stateRoot.")
@@ -1944,6 +4067,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -1985,6 +4109,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: None,
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2023,6 +4148,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: Some("2026-01-03T00:00:01Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2059,6 +4185,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: Some("2025-12-31T23:59:55Z".to_string()),
+            ..Default::default()
         });
 
         assert!(result.valid);
@@ -2095,6 +4222,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(59144),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2127,10 +4255,14 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(59144),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
-        assert_eq!(result.error_code.as_deref(), Some(ERR_STATE_ROOT_MISMATCH));
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_STATE_ROOT_VALUE_MISMATCH)
+        );
         assert_eq!(
             result.error.as_deref(),
             Some("Envelope state root does not match onchainPolicyProof.stateRoot.")
@@ -2157,6 +4289,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(59144),
             package_packaged_at: Some("2026-01-03T00:00:01Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2193,6 +4326,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(42161),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2226,6 +4360,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(59141),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2259,6 +4394,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2297,6 +4433,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(10),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(result.valid);
@@ -2326,6 +4463,7 @@ mod tests {
             block_number: 2,
             package_chain_id: Some(8453),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(result.valid);
@@ -2355,6 +4493,7 @@ mod tests {
             block_number: 2,
             package_chain_id: Some(8453),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2393,6 +4532,7 @@ mod tests {
             block_number: 1,
             package_chain_id: Some(59144),
             package_packaged_at: Some("2026-01-01T00:05:00Z".to_string()),
+            ..Default::default()
         });
 
         assert!(!result.valid);
@@ -2411,4 +4551,38 @@ mod tests {
             .iter()
             .any(|check| check.id == "envelope-network" && !check.passed));
     }
+
+    #[test]
+    fn summary_keeps_the_verdict_and_drops_the_checks() {
+        let result = ConsensusVerificationResult {
+            valid: true,
+            verified_state_root: Some(format!("{:#x}", B256::repeat_byte(0xaa))),
+            verified_block_number: Some(21_000_000),
+            state_root_matches: true,
+            checks: vec![ConsensusCheck {
+                id: "state-root".into(),
+                label: "State root".into(),
+                passed: true,
+                detail: Some("matched".into()),
+            }],
+            checks_passed: 1,
+            checks_total: 1,
+            ..Default::default()
+        };
+
+        let summary = serde_json::to_value(ConsensusVerificationSummary::from(&result))
+            .expect("summary json");
+
+        assert_eq!(summary["valid"], true);
+        assert_eq!(summary["verified_block_number"], 21_000_000);
+        assert_eq!(
+            summary["verified_state_root"],
+            format!("{:#x}", B256::repeat_byte(0xaa))
+        );
+        assert_eq!(summary["error_code"], serde_json::Value::Null);
+        assert_eq!(summary["checks_passed"], 1);
+        for heavy in ["checks", "verified_block_header", "update_results"] {
+            assert!(summary.get(heavy).is_none(), "{heavy} in summary");
+        }
+    }
 }
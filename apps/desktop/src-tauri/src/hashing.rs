@@ -0,0 +1,116 @@
+//! Keccak-256 hashing exposed to the frontend.
+//!
+//! Hashing through the verifier's own implementation keeps input
+//! canonicalization (hex decoding, `0x` prefixes, UTF-8 text) identical
+//! between the TypeScript side and the Rust checks.
+
+use alloy::primitives::{keccak256, Address, Bytes, B256};
+use serde::Deserialize;
+use std::str::FromStr;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashInputEncoding {
+    /// `data` is a hex byte string, with or without `0x`.
+    #[default]
+    Hex,
+    /// `data` is hashed as its UTF-8 bytes.
+    Utf8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Keccak256Input {
+    pub data: String,
+    #[serde(default)]
+    pub encoding: HashInputEncoding,
+}
+
+/// Returns the `0x`-prefixed lowercase digest.
+pub fn keccak256_hex(input: &Keccak256Input) -> Result<String, String> {
+    let digest = match input.encoding {
+        HashInputEncoding::Hex => {
+            let bytes = Bytes::from_str(input.data.trim())
+                .map_err(|err| format!("invalid hex data: {err}"))?;
+            keccak256(&bytes)
+        }
+        HashInputEncoding::Utf8 => keccak256(input.data.as_bytes()),
+    };
+    Ok(format!("{digest:#x}"))
+}
+
+/// EIP-1014 address: the last 20 bytes of
+/// `keccak256(0xff ++ deployer ++ salt ++ initCodeHash)`.
+pub fn compute_create2_address(deployer: Address, salt: B256, init_code_hash: B256) -> Address {
+    let mut preimage = [0u8; 85];
+    preimage[0] = 0xff;
+    preimage[1..21].copy_from_slice(deployer.as_slice());
+    preimage[21..53].copy_from_slice(salt.as_slice());
+    preimage[53..].copy_from_slice(init_code_hash.as_slice());
+    Address::from_slice(&keccak256(preimage)[12..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(data: &str, encoding: HashInputEncoding) -> Result<String, String> {
+        keccak256_hex(&Keccak256Input {
+            data: data.to_string(),
+            encoding,
+        })
+    }
+
+    #[test]
+    fn hashes_empty_input_to_known_vector() {
+        let expected = "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+        assert_eq!(hash("0x", HashInputEncoding::Hex).as_deref(), Ok(expected));
+        assert_eq!(hash("", HashInputEncoding::Hex).as_deref(), Ok(expected));
+        assert_eq!(hash("", HashInputEncoding::Utf8).as_deref(), Ok(expected));
+    }
+
+    #[test]
+    fn hashes_abc_identically_as_text_and_hex() {
+        let expected = "0x4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45";
+        assert_eq!(
+            hash("abc", HashInputEncoding::Utf8).as_deref(),
+            Ok(expected)
+        );
+        assert_eq!(
+            hash("0x616263", HashInputEncoding::Hex).as_deref(),
+            Ok(expected)
+        );
+        assert_eq!(
+            hash("616263", HashInputEncoding::Hex).as_deref(),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn computes_eip1014_create2_vectors() {
+        let init_code_hash = keccak256([0x00]);
+        let deadbeef = Address::from_str("0xdeadbeef00000000000000000000000000000000").unwrap();
+        let feed_salt =
+            B256::from_str("0x000000000000000000000000feed000000000000000000000000000000000000")
+                .unwrap();
+
+        assert_eq!(
+            compute_create2_address(Address::ZERO, B256::ZERO, init_code_hash),
+            Address::from_str("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38").unwrap()
+        );
+        assert_eq!(
+            compute_create2_address(deadbeef, B256::ZERO, init_code_hash),
+            Address::from_str("0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3").unwrap()
+        );
+        assert_eq!(
+            compute_create2_address(deadbeef, feed_salt, init_code_hash),
+            Address::from_str("0xD04116cDd17beBE565EB2422F2497E06cC1C9833").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_hex() {
+        let error = hash("0xzz", HashInputEncoding::Hex).unwrap_err();
+        assert!(error.starts_with("invalid hex data"), "{error}");
+    }
+}
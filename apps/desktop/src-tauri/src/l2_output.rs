@@ -0,0 +1,292 @@
+//! OP Stack output-root proofs anchored in a verified L1 state root.
+//!
+//! Proves an L2 block header through the chain's `L2OutputOracle` on L1:
+//! the oracle account sits under the L1 state root, its `l2Outputs[index]`
+//! slot holds the output root, and the v0 output root commits to the L2
+//! state root, the message passer storage root and the L2 block hash. The
+//! header is accepted only if its hash is the one committed to.
+//!
+//! Only legacy `L2OutputOracle` outputs are supported. OP Mainnet and Base
+//! now settle through fault-proof dispute games, whose root claims live in
+//! clone immutables rather than oracle storage, and their oracles stopped
+//! receiving outputs when they switched. Blocks proposed after the switch
+//! cannot be proven here and fail with `l2-output-root-unproven`.
+
+use alloy::{
+    consensus::Header,
+    primitives::{address, keccak256, Address, Bytes, B256, U256},
+    rlp::Decodable,
+    trie::{proof::verify_proof, Nibbles},
+};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Storage slot of `L2OutputOracle.l2Outputs`; each entry spans two slots
+/// (`outputRoot`, then packed `timestamp`/`l2BlockNumber`).
+const L2_OUTPUTS_SLOT: u64 = 3;
+
+/// Legacy `L2OutputOracle` proxies on Ethereum mainnet, by L2 chain id. Both
+/// chains now propose through dispute games, so these only hold outputs for
+/// blocks from before their fault-proof upgrades.
+const L2_OUTPUT_ORACLES: &[(u64, Address)] = &[
+    (10, address!("dfe97868233d1aa22e815a266982f2cf17685a27")),
+    (8453, address!("56315b90c40730925ec5485cf004d835058518a0")),
+];
+
+/// `proofPayload.outputRootProof` for OP Stack envelopes.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputRootProof {
+    pub output_index: u64,
+    /// The oracle account as returned by `eth_getProof` on L1.
+    pub oracle_account: OracleAccount,
+    /// `accountProof` for the oracle, root first.
+    pub account_proof: Vec<String>,
+    /// Storage proof for the `l2Outputs[outputIndex].outputRoot` slot.
+    pub storage_proof: Vec<String>,
+    pub message_passer_storage_root: String,
+    /// RLP-encoded L2 block header.
+    pub l2_header: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleAccount {
+    pub nonce: u64,
+    pub balance: String,
+    pub storage_hash: String,
+    pub code_hash: String,
+}
+
+/// An L2 header proven against the L1 state root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenL2Header {
+    pub block_hash: B256,
+    pub block_number: u64,
+    pub state_root: B256,
+    pub output_root: B256,
+}
+
+pub fn verify_output_root_proof(
+    chain_id: u64,
+    l1_state_root: B256,
+    proof: &OutputRootProof,
+) -> Result<ProvenL2Header, String> {
+    let oracle = L2_OUTPUT_ORACLES
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map(|(_, oracle)| *oracle)
+        .ok_or_else(|| format!("No L2OutputOracle is pinned for chainId {chain_id}."))?;
+
+    let header_rlp = parse_bytes(&proof.l2_header, "outputRootProof.l2Header")?;
+    let header = Header::decode(&mut header_rlp.as_ref())
+        .map_err(|err| format!("invalid outputRootProof.l2Header: {err}"))?;
+    let block_hash = keccak256(&header_rlp);
+    let message_passer_storage_root = parse_b256(
+        &proof.message_passer_storage_root,
+        "outputRootProof.messagePasserStorageRoot",
+    )?;
+    let output_root = output_root_v0(header.state_root, message_passer_storage_root, block_hash);
+
+    let storage_root = parse_b256(
+        &proof.oracle_account.storage_hash,
+        "outputRootProof.oracleAccount.storageHash",
+    )?;
+    verify_proof(
+        l1_state_root,
+        Nibbles::unpack(keccak256(oracle)),
+        Some(oracle_account_leaf(&proof.oracle_account, storage_root)?),
+        &parse_nodes(&proof.account_proof, "outputRootProof.accountProof")?,
+    )
+    .map_err(|err| {
+        format!("L2OutputOracle {oracle:#x} is not proven under L1 state root {l1_state_root:#x}: {err}")
+    })?;
+
+    let slot = output_root_slot(proof.output_index);
+    verify_proof(
+        storage_root,
+        Nibbles::unpack(keccak256(slot)),
+        Some(alloy::rlp::encode(trim_leading_zeros(
+            output_root.as_slice(),
+        ))),
+        &parse_nodes(&proof.storage_proof, "outputRootProof.storageProof")?,
+    )
+    .map_err(|err| {
+        format!(
+            "Output root {output_root:#x} is not stored at l2Outputs[{}] of {oracle:#x}: {err}",
+            proof.output_index
+        )
+    })?;
+
+    Ok(ProvenL2Header {
+        block_hash,
+        block_number: header.number,
+        state_root: header.state_root,
+        output_root,
+    })
+}
+
+/// `keccak256(version ++ stateRoot ++ messagePasserStorageRoot ++ blockHash)`
+/// with a zero version.
+fn output_root_v0(state_root: B256, message_passer_storage_root: B256, block_hash: B256) -> B256 {
+    let mut preimage = [0u8; 128];
+    preimage[32..64].copy_from_slice(state_root.as_slice());
+    preimage[64..96].copy_from_slice(message_passer_storage_root.as_slice());
+    preimage[96..].copy_from_slice(block_hash.as_slice());
+    keccak256(preimage)
+}
+
+fn output_root_slot(output_index: u64) -> B256 {
+    let base = U256::from_be_bytes(keccak256(B256::from(U256::from(L2_OUTPUTS_SLOT))).0);
+    B256::from(base + U256::from(output_index) * U256::from(2))
+}
+
+fn oracle_account_leaf(account: &OracleAccount, storage_root: B256) -> Result<Vec<u8>, String> {
+    let balance = crate::number_format::parse_quantity(&account.balance)
+        .map_err(|err| format!("invalid outputRootProof.oracleAccount.balance: {err}"))?;
+    let code_hash = parse_b256(&account.code_hash, "outputRootProof.oracleAccount.codeHash")?;
+    Ok(crate::state_root::encode_account(
+        account.nonce,
+        balance,
+        storage_root,
+        code_hash,
+    ))
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn parse_nodes(nodes: &[String], field: &str) -> Result<Vec<Bytes>, String> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| parse_bytes(node, &format!("{field}[{index}]")))
+        .collect()
+}
+
+fn parse_bytes(raw: &str, field: &str) -> Result<Bytes, String> {
+    Bytes::from_str(raw.trim()).map_err(|err| format!("invalid {field}: {err}"))
+}
+
+fn parse_b256(raw: &str, field: &str) -> Result<B256, String> {
+    B256::from_str(raw.trim()).map_err(|err| format!("invalid {field}: {err}"))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy::trie::{proof::ProofRetainer, HashBuilder};
+
+    const MESSAGE_PASSER_STORAGE_ROOT: B256 = B256::repeat_byte(0x77);
+
+    /// Root and proof for a single-leaf trie.
+    fn single_leaf_trie(key: B256, value: &[u8]) -> (B256, Vec<String>) {
+        let key = Nibbles::unpack(key);
+        let mut builder = HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![key]));
+        builder.add_leaf(key, value);
+        let root = builder.root();
+        let proof = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| format!("{node}"))
+            .collect();
+        (root, proof)
+    }
+
+    /// An OP Mainnet output proof for an L2 header at `l2_block_number`,
+    /// plus the L1 state root it is anchored in.
+    pub(crate) fn output_root_fixture(l2_block_number: u64) -> (B256, OutputRootProof, Header) {
+        let header = Header {
+            number: l2_block_number,
+            state_root: B256::repeat_byte(0xaa),
+            ..Default::default()
+        };
+        let header_rlp = alloy::rlp::encode(&header);
+        let output_root = output_root_v0(
+            header.state_root,
+            MESSAGE_PASSER_STORAGE_ROOT,
+            keccak256(&header_rlp),
+        );
+
+        let output_index = 7;
+        let (storage_root, storage_proof) = single_leaf_trie(
+            keccak256(output_root_slot(output_index)),
+            &alloy::rlp::encode(trim_leading_zeros(output_root.as_slice())),
+        );
+        let oracle_account = OracleAccount {
+            nonce: 1,
+            balance: "0".into(),
+            storage_hash: format!("{storage_root:#x}"),
+            code_hash: format!("{:#x}", B256::repeat_byte(0xcc)),
+        };
+        let oracle = L2_OUTPUT_ORACLES[0].1;
+        let (l1_state_root, account_proof) = single_leaf_trie(
+            keccak256(oracle),
+            &oracle_account_leaf(&oracle_account, storage_root).expect("account"),
+        );
+
+        let proof = OutputRootProof {
+            output_index,
+            oracle_account,
+            account_proof,
+            storage_proof,
+            message_passer_storage_root: format!("{MESSAGE_PASSER_STORAGE_ROOT:#x}"),
+            l2_header: format!("0x{}", hex::encode(header_rlp)),
+        };
+        (l1_state_root, proof, header)
+    }
+
+    #[test]
+    fn proves_l2_header_through_the_output_oracle() {
+        let (l1_state_root, proof, header) = output_root_fixture(120_000_000);
+
+        let proven = verify_output_root_proof(10, l1_state_root, &proof).expect("proven");
+
+        assert_eq!(proven.block_number, 120_000_000);
+        assert_eq!(proven.state_root, header.state_root);
+        assert_eq!(proven.block_hash, header.hash_slow());
+    }
+
+    #[test]
+    fn rejects_header_not_committed_to_by_the_output_root() {
+        let (l1_state_root, mut proof, header) = output_root_fixture(120_000_000);
+        let forged = Header {
+            state_root: B256::repeat_byte(0xbb),
+            ..header
+        };
+        proof.l2_header = format!("0x{}", hex::encode(alloy::rlp::encode(&forged)));
+
+        let error = verify_output_root_proof(10, l1_state_root, &proof).expect_err("forged");
+
+        assert!(error.contains("is not stored at l2Outputs[7]"), "{error}");
+    }
+
+    #[test]
+    fn rejects_output_proof_against_a_different_l1_state_root() {
+        let (_, proof, _) = output_root_fixture(1);
+
+        let error = verify_output_root_proof(10, B256::repeat_byte(0x01), &proof)
+            .expect_err("wrong L1 root");
+
+        assert!(
+            error.contains("is not proven under L1 state root"),
+            "{error}"
+        );
+        assert!(verify_output_root_proof(42, B256::ZERO, &proof)
+            .unwrap_err()
+            .contains("No L2OutputOracle is pinned"));
+    }
+
+    #[test]
+    fn places_output_roots_two_slots_apart() {
+        let first = U256::from_be_bytes(output_root_slot(0).0);
+        let second = U256::from_be_bytes(output_root_slot(1).0);
+        assert_eq!(second - first, U256::from(2));
+    }
+}
@@ -1,8 +1,92 @@
 #[cfg(target_os = "macos")]
 use tauri::Manager;
 
+mod block_consistency;
+mod confirmation_signatures;
 mod consensus;
+mod hashing;
+mod l2_output;
+mod nonce_sequence;
+mod number_format;
+mod offline;
+mod package;
+mod package_structure;
+mod receipt_proof;
+mod replay_input_diff;
+mod result_encoding;
+mod safe_account;
+mod safe_tx_hash;
 mod simulation_replay;
+mod state_root;
+mod state_root_binding;
+mod trace;
+
+#[tauri::command]
+fn keccak256(input: hashing::Keccak256Input) -> Result<String, String> {
+    hashing::keccak256_hex(&input)
+}
+
+#[tauri::command]
+fn compute_safe_tx_hash(
+    input: safe_tx_hash::SafeTxHashInput,
+) -> Result<safe_tx_hash::SafeTxHashResult, String> {
+    safe_tx_hash::compute_safe_tx_hash(&input)
+}
+
+#[tauri::command]
+fn canonical_replay_result(
+    result: simulation_replay::SimulationReplayVerificationResult,
+) -> Result<String, String> {
+    simulation_replay::canonical_json(&result)
+}
+
+#[tauri::command]
+fn compare_replay_inputs(
+    a: simulation_replay::SimulationReplayInput,
+    b: simulation_replay::SimulationReplayInput,
+) -> Result<Vec<replay_input_diff::ReplayInputDifference>, String> {
+    replay_input_diff::compare_replay_inputs(&a, &b)
+}
+
+#[tauri::command]
+fn verify_nonce_sequence(
+    input: nonce_sequence::NonceSequenceInput,
+) -> Result<nonce_sequence::NonceSequenceResult, String> {
+    nonce_sequence::verify_nonce_sequence(&input)
+}
+
+#[tauri::command]
+fn prune_replay_witness(
+    input: simulation_replay::SimulationReplayInput,
+) -> Result<Vec<simulation_replay::ReplayWitnessAccount>, String> {
+    simulation_replay::prune_replay_witness(&input)
+}
+
+#[tauri::command]
+fn supported_networks() -> Vec<consensus::SupportedNetwork> {
+    consensus::supported_networks()
+}
+
+#[tauri::command]
+fn validate_package_structure(
+    package: serde_json::Value,
+) -> Result<package_structure::PackageStructureResult, String> {
+    Ok(package_structure::validate_package_structure(&package))
+}
+
+#[tauri::command]
+fn verify_block_consistency(
+    input: block_consistency::BlockConsistencyInput,
+) -> Result<block_consistency::BlockConsistencyResult, String> {
+    Ok(block_consistency::verify_block_consistency(&input))
+}
+
+#[tauri::command]
+fn verify_confirmation_signatures(
+    input: confirmation_signatures::ConfirmationSignaturesInput,
+) -> Result<confirmation_signatures::ConfirmationSignaturesResult, String> {
+    confirmation_signatures::verify_confirmation_signatures(input)
+}
 
 #[tauri::command]
 fn verify_consensus_proof(
@@ -11,6 +95,46 @@ fn verify_consensus_proof(
     Ok(consensus::verify_consensus_proof(input))
 }
 
+/// `verify_consensus_proof` as raw bytes in the requested encoding.
+#[tauri::command]
+fn verify_consensus_proof_encoded(
+    input: consensus::ConsensusProofInput,
+    encoding: result_encoding::ResultEncoding,
+) -> Result<tauri::ipc::Response, String> {
+    let result = consensus::verify_consensus_proof(input);
+    result_encoding::encode_result(&result, encoding).map(tauri::ipc::Response::new)
+}
+
+/// `verify_consensus_proof` reduced to its verdict, for pass/fail views.
+#[tauri::command]
+fn verify_consensus_proof_summary(
+    input: consensus::ConsensusProofInput,
+) -> Result<consensus::ConsensusVerificationSummary, String> {
+    Ok((&consensus::verify_consensus_proof(input)).into())
+}
+
+#[tauri::command]
+fn verify_log_inclusion(
+    input: receipt_proof::LogInclusionInput,
+) -> Result<receipt_proof::LogInclusionResult, String> {
+    Ok(receipt_proof::verify_log_inclusion(input))
+}
+
+/// Both package sections, cross-checked with `verify_block_consistency`.
+#[tauri::command]
+fn verify_package(
+    input: package::PackageInput,
+) -> Result<package::PackageVerificationResult, String> {
+    package::verify_package(input)
+}
+
+#[tauri::command]
+fn verify_safe_account_proof(
+    input: safe_account::SafeAccountProofInput,
+) -> Result<safe_account::SafeAccountProofResult, String> {
+    Ok(safe_account::verify_safe_account_proof(&input))
+}
+
 #[tauri::command]
 fn verify_simulation_replay(
     input: simulation_replay::SimulationReplayInput,
@@ -18,13 +142,66 @@ fn verify_simulation_replay(
     Ok(simulation_replay::verify_simulation_replay(input))
 }
 
+/// `verify_simulation_replay` as raw bytes in the requested encoding.
+#[tauri::command]
+fn verify_simulation_replay_encoded(
+    input: simulation_replay::SimulationReplayInput,
+    encoding: result_encoding::ResultEncoding,
+) -> Result<tauri::ipc::Response, String> {
+    let result = simulation_replay::verify_simulation_replay(input);
+    result_encoding::encode_result(&result, encoding).map(tauri::ipc::Response::new)
+}
+
+#[tauri::command]
+fn verify_simulation_replay_sequence(
+    input: simulation_replay::SimulationReplaySequenceInput,
+) -> Result<simulation_replay::SimulationReplaySequenceResult, String> {
+    Ok(simulation_replay::verify_simulation_replay_sequence(input))
+}
+
+/// `verify_simulation_replay` reduced to its verdict, for pass/fail views.
+#[tauri::command]
+fn verify_simulation_replay_summary(
+    input: simulation_replay::SimulationReplayInput,
+) -> Result<simulation_replay::SimulationReplaySummary, String> {
+    Ok((&simulation_replay::verify_simulation_replay(input)).into())
+}
+
+#[tauri::command]
+fn verify_state_root_binding(
+    input: state_root_binding::StateRootBindingInput,
+) -> Result<state_root_binding::StateRootBindingResult, String> {
+    Ok(state_root_binding::verify_state_root_binding(&input))
+}
+
 fn main() {
+    trace::init_from_env();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
+            canonical_replay_result,
+            compare_replay_inputs,
+            compute_safe_tx_hash,
+            keccak256,
+            prune_replay_witness,
+            supported_networks,
+            validate_package_structure,
+            verify_block_consistency,
+            verify_confirmation_signatures,
             verify_consensus_proof,
-            verify_simulation_replay
+            verify_consensus_proof_encoded,
+            verify_consensus_proof_summary,
+            verify_log_inclusion,
+            verify_nonce_sequence,
+            verify_package,
+            verify_safe_account_proof,
+            verify_simulation_replay,
+            verify_simulation_replay_encoded,
+            verify_simulation_replay_sequence,
+            verify_simulation_replay_summary,
+            verify_state_root_binding
         ])
         .setup(|app| {
             #[cfg(target_os = "macos")]
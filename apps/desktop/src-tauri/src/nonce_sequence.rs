@@ -0,0 +1,126 @@
+//! Structural check that a queue of Safe transactions uses consecutive nonces.
+//!
+//! A Safe executes nonce `n` only after `n - 1`, so a packaged queue with a
+//! gap can never fully execute and a reused nonce means two transactions
+//! compete for the same slot.
+
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceSequenceInput {
+    pub safe_address: String,
+    /// Nonces in queue order.
+    pub nonces: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceSequenceResult {
+    pub safe_address: String,
+    pub valid: bool,
+    pub breaks: Vec<NonceBreak>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceBreak {
+    /// Position in `nonces`.
+    pub index: usize,
+    pub expected: u64,
+    pub nonce: u64,
+    pub kind: NonceBreakKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum NonceBreakKind {
+    /// Skips one or more nonces.
+    Gap,
+    /// At or below an earlier nonce in the queue.
+    Reuse,
+}
+
+/// Each nonce must be its predecessor plus one. After a break the walk
+/// continues from the offending nonce, so one misplaced entry is reported once.
+pub fn verify_nonce_sequence(input: &NonceSequenceInput) -> Result<NonceSequenceResult, String> {
+    let safe_address = Address::from_str(input.safe_address.trim())
+        .map_err(|err| format!("invalid safeAddress ({}): {err}", input.safe_address))?;
+
+    let mut breaks = Vec::new();
+    for (index, pair) in input.nonces.windows(2).enumerate() {
+        let (previous, nonce) = (pair[0], pair[1]);
+        let expected = previous.saturating_add(1);
+        if nonce == expected {
+            continue;
+        }
+        breaks.push(NonceBreak {
+            index: index + 1,
+            expected,
+            nonce,
+            kind: if nonce > expected {
+                NonceBreakKind::Gap
+            } else {
+                NonceBreakKind::Reuse
+            },
+        });
+    }
+
+    Ok(NonceSequenceResult {
+        safe_address: format!("{safe_address:#x}"),
+        valid: breaks.is_empty(),
+        breaks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(nonces: &[u64]) -> NonceSequenceResult {
+        verify_nonce_sequence(&NonceSequenceInput {
+            safe_address: "0x849D52316331967b6fF1198e5E32A0eB168D039d".to_string(),
+            nonces: nonces.to_vec(),
+        })
+        .expect("valid input")
+    }
+
+    #[test]
+    fn accepts_consecutive_nonces() {
+        let result = sequence(&[0, 1, 2]);
+
+        assert!(result.valid);
+        assert!(result.breaks.is_empty());
+        assert_eq!(
+            result.safe_address,
+            "0x849d52316331967b6ff1198e5e32a0eb168d039d"
+        );
+    }
+
+    #[test]
+    fn reports_gap_between_nonces() {
+        let result = sequence(&[0, 2]);
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.breaks,
+            vec![NonceBreak {
+                index: 1,
+                expected: 1,
+                nonce: 2,
+                kind: NonceBreakKind::Gap,
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_reused_nonce() {
+        let result = sequence(&[4, 5, 5, 6]);
+
+        assert_eq!(result.breaks.len(), 1);
+        assert_eq!(result.breaks[0].index, 2);
+        assert_eq!(result.breaks[0].kind, NonceBreakKind::Reuse);
+    }
+}
@@ -0,0 +1,102 @@
+//! Output encoding for numeric quantities in verification results.
+//!
+//! Results default to decimal strings. Callers that interoperate with
+//! JSON-RPC tooling can request `0x`-prefixed minimal hex quantities instead.
+
+use alloy::primitives::U256;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputNumberFormat {
+    #[default]
+    Decimal,
+    Hex,
+}
+
+impl OutputNumberFormat {
+    pub fn format_u64(self, value: u64) -> String {
+        self.format_u256(U256::from(value))
+    }
+
+    pub fn format_u256(self, value: U256) -> String {
+        match self {
+            Self::Decimal => value.to_string(),
+            // `{:#x}` already yields minimal form ("0x0", "0x2a").
+            Self::Hex => format!("{value:#x}"),
+        }
+    }
+
+    /// Re-encode a decimal or `0x` quantity string in this format. A leading
+    /// `-` and `min..=max` ranges are kept, with each quantity re-encoded.
+    /// Values that do not parse as a quantity are returned unchanged.
+    pub fn reformat(self, value: &str) -> String {
+        if let Some((min, max)) = value.split_once("..=") {
+            return format!("{}..={}", self.reformat(min), self.reformat(max));
+        }
+        if let Some(magnitude) = value.strip_prefix('-') {
+            return format!("-{}", self.reformat(magnitude));
+        }
+        match parse_quantity(value) {
+            Ok(parsed) => self.format_u256(parsed),
+            Err(_) => value.to_string(),
+        }
+    }
+}
+
+/// Parse a quantity string in either decimal or `0x`-prefixed hex form.
+pub fn parse_quantity(raw: &str) -> Result<U256, String> {
+    let trimmed = raw.trim();
+    if let Some(hex) = trimmed.strip_prefix("0x") {
+        U256::from_str_radix(hex, 16).map_err(|err| err.to_string())
+    } else {
+        U256::from_str_radix(trimmed, 10).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_quantity, OutputNumberFormat};
+    use alloy::primitives::U256;
+
+    #[test]
+    fn round_trips_values_through_decimal_format() {
+        let value = U256::from(1_000_000_000_000_000_000u64);
+        let encoded = OutputNumberFormat::Decimal.format_u256(value);
+        assert_eq!(encoded, "1000000000000000000");
+        assert_eq!(parse_quantity(&encoded), Ok(value));
+    }
+
+    #[test]
+    fn round_trips_values_through_hex_format() {
+        let value = U256::from(1_000_000_000_000_000_000u64);
+        let encoded = OutputNumberFormat::Hex.format_u256(value);
+        assert_eq!(encoded, "0xde0b6b3a7640000");
+        assert_eq!(parse_quantity(&encoded), Ok(value));
+    }
+
+    #[test]
+    fn hex_format_uses_minimal_quantity_encoding() {
+        assert_eq!(OutputNumberFormat::Hex.format_u64(0), "0x0");
+        assert_eq!(OutputNumberFormat::Hex.format_u64(42), "0x2a");
+        assert_eq!(OutputNumberFormat::Hex.reformat("0x002a"), "0x2a");
+        assert_eq!(OutputNumberFormat::Decimal.reformat("0x2a"), "42");
+    }
+
+    #[test]
+    fn reformats_signed_values_and_ranges_in_both_directions() {
+        for (decimal, hex) in [("-123", "-0x7b"), ("21000..=42000", "0x5208..=0xa410")] {
+            assert_eq!(OutputNumberFormat::Hex.reformat(decimal), hex);
+            assert_eq!(OutputNumberFormat::Decimal.reformat(hex), decimal);
+        }
+    }
+
+    #[test]
+    fn deserializes_lowercase_format_names() {
+        let hex: OutputNumberFormat = serde_json::from_str("\"hex\"").expect("hex format");
+        let decimal: OutputNumberFormat =
+            serde_json::from_str("\"decimal\"").expect("decimal format");
+        assert_eq!(hex, OutputNumberFormat::Hex);
+        assert_eq!(decimal, OutputNumberFormat::Decimal);
+    }
+}
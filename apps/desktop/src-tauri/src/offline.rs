@@ -0,0 +1,33 @@
+//! `offline-only` build reporting.
+//!
+//! The verification modules depend on `helios-consensus-core`, `alloy`,
+//! `revm`, `tree_hash` and `tracing`, none of which are built with a
+//! network-capable feature. Building with `--features offline-only` makes
+//! `build.rs` reject a manifest that turns such a feature on for `alloy` or
+//! `revm`, and verification results then report `offline: true`.
+
+/// Whether this binary was built with the `offline-only` feature.
+pub const OFFLINE_ONLY: bool = cfg!(feature = "offline-only");
+
+#[cfg(test)]
+mod tests {
+    use crate::{consensus, simulation_replay};
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "offline-only"),
+        ignore = "requires --features offline-only"
+    )]
+    fn verification_results_report_offline_only_builds() {
+        let consensus =
+            consensus::verify_consensus_proof(consensus::ConsensusProofInput::default());
+        let replay = simulation_replay::verify_simulation_replay(
+            simulation_replay::SimulationReplayInput::default(),
+        );
+
+        assert!(!consensus.valid);
+        assert!(consensus.offline);
+        assert!(!replay.success);
+        assert!(replay.offline);
+    }
+}
@@ -0,0 +1,198 @@
+//! Combined verification of a package's consensus and replay sections.
+//!
+//! Verifying the sections one command at a time leaves the cross-checks to
+//! the caller. `verify_package` runs both and checks them against each other
+//! with `verify_block_consistency`, taking the chain ids and block numbers
+//! from the section inputs themselves so none can be left out.
+//!
+//! An OP Stack `consensus` section is anchored by `l1Consensus`, a beacon
+//! proof for L1. Its verified state root is the only `l1VerifiedStateRoot`
+//! the OP Stack section sees; one supplied by the caller is discarded.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    block_consistency::{verify_block_consistency, BlockConsistencyInput, BlockConsistencyResult},
+    consensus::{verify_consensus_proof, ConsensusProofInput, ConsensusVerificationResult},
+    simulation_replay::{
+        verify_simulation_replay, SimulationReplayInput, SimulationReplayVerificationResult,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageInput {
+    pub consensus: ConsensusProofInput,
+    /// Beacon proof for L1 whose verified state root anchors an OP Stack
+    /// `consensus.proofPayload.outputRootProof`.
+    pub l1_consensus: Option<ConsensusProofInput>,
+    pub replay: SimulationReplayInput,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageVerificationResult {
+    /// Both sections verified and refer to the same block on the same chain.
+    pub valid: bool,
+    pub consensus: ConsensusVerificationResult,
+    /// Set when `l1Consensus` was given; `valid` then requires it too.
+    pub l1_consensus: Option<ConsensusVerificationResult>,
+    pub replay: SimulationReplayVerificationResult,
+    pub block_consistency: BlockConsistencyResult,
+}
+
+pub fn verify_package(input: PackageInput) -> Result<PackageVerificationResult, String> {
+    let consensus_chain_id = input.consensus.package_chain_id.ok_or(
+        "consensus.packageChainId is required to check the sections are for the same chain.",
+    )?;
+    let replay_chain_id = input.replay.chain_id;
+    let simulation_block_number = input.replay.simulation.block_number;
+
+    let l1_consensus = input.l1_consensus.map(verify_consensus_proof);
+    let mut consensus_input = input.consensus;
+    consensus_input.l1_verified_state_root = verified_l1_state_root(l1_consensus.as_ref());
+    let consensus = verify_consensus_proof(consensus_input);
+    let replay = verify_simulation_replay(input.replay);
+    let block_consistency = verify_block_consistency(&BlockConsistencyInput {
+        simulation_block_number,
+        verified_block_number: consensus.verified_block_number,
+        consensus_chain_id,
+        replay_chain_id,
+    });
+
+    Ok(PackageVerificationResult {
+        valid: consensus.valid
+            && l1_consensus.as_ref().map_or(true, |l1| l1.valid)
+            && replay.success
+            && block_consistency.valid,
+        consensus,
+        l1_consensus,
+        replay,
+        block_consistency,
+    })
+}
+
+/// The L1 state root an OP Stack section may be anchored to: only one the
+/// L1 beacon proof actually verified.
+fn verified_l1_state_root(l1_consensus: Option<&ConsensusVerificationResult>) -> Option<String> {
+    l1_consensus
+        .filter(|l1| l1.valid)
+        .and_then(|l1| l1.verified_state_root.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        consensus::tests::output_root_envelope_input, l2_output::tests::output_root_fixture,
+    };
+
+    #[test]
+    fn requires_the_consensus_chain_id() {
+        let error = verify_package(PackageInput {
+            consensus: ConsensusProofInput::default(),
+            l1_consensus: None,
+            replay: SimulationReplayInput {
+                chain_id: 1,
+                ..Default::default()
+            },
+        })
+        .expect_err("missing packageChainId");
+
+        assert!(error.contains("consensus.packageChainId"));
+    }
+
+    #[test]
+    fn rejects_a_simulation_at_an_unverified_block() {
+        let result = verify_package(PackageInput {
+            consensus: ConsensusProofInput {
+                package_chain_id: Some(1),
+                ..Default::default()
+            },
+            l1_consensus: None,
+            replay: SimulationReplayInput {
+                chain_id: 1,
+                ..Default::default()
+            },
+        })
+        .expect("package result");
+
+        assert!(!result.valid);
+        assert_eq!(result.consensus.verified_block_number, None);
+        assert_eq!(
+            result.block_consistency.error_code.as_deref(),
+            Some("consensus-block-unverified")
+        );
+    }
+
+    #[test]
+    fn rejects_sections_for_different_chains() {
+        let result = verify_package(PackageInput {
+            consensus: ConsensusProofInput {
+                package_chain_id: Some(1),
+                ..Default::default()
+            },
+            l1_consensus: None,
+            replay: SimulationReplayInput {
+                chain_id: 100,
+                ..Default::default()
+            },
+        })
+        .expect("package result");
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.block_consistency.error_code.as_deref(),
+            Some("chain-id-inconsistent")
+        );
+    }
+
+    #[test]
+    fn anchors_an_opstack_section_only_to_a_verified_l1_root() {
+        let (l1_state_root, _, _) = output_root_fixture(1);
+        let l1_state_root = format!("{:#x}", l1_state_root);
+        let verified_l1 = ConsensusVerificationResult {
+            valid: true,
+            verified_state_root: Some(l1_state_root.clone()),
+            ..Default::default()
+        };
+        let unverified_l1 = ConsensusVerificationResult {
+            verified_state_root: Some(l1_state_root),
+            ..Default::default()
+        };
+
+        let mut anchored = output_root_envelope_input(None);
+        anchored.l1_verified_state_root = verified_l1_state_root(Some(&verified_l1));
+        let anchored = verify_consensus_proof(anchored);
+        assert!(anchored.valid, "{:?}", anchored.error);
+        assert!(anchored.l2_output_root.is_some());
+
+        assert_eq!(verified_l1_state_root(Some(&unverified_l1)), None);
+        assert_eq!(verified_l1_state_root(None), None);
+    }
+
+    #[test]
+    fn discards_a_caller_supplied_l1_state_root() {
+        let result = verify_package(PackageInput {
+            consensus: output_root_envelope_input(None),
+            l1_consensus: None,
+            replay: SimulationReplayInput {
+                chain_id: 10,
+                ..Default::default()
+            },
+        })
+        .expect("package result");
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.consensus.error_code.as_deref(),
+            Some("l2-output-root-unproven")
+        );
+        assert!(result
+            .consensus
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("requires l1VerifiedStateRoot"));
+    }
+}
@@ -0,0 +1,316 @@
+//! Structural pre-check of a whole evidence package.
+//!
+//! Walks the package JSON once and reports every missing or mistyped field
+//! of the top level and of the consensus proof, simulation and witness
+//! sections, without running BLS or the EVM. Passing says nothing about the
+//! package's validity; it only means the deep verifiers get well-formed
+//! input, so a corrupt file fails fast with the whole list of problems.
+
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    String,
+    Integer,
+    Bool,
+    Array,
+    Object,
+}
+
+impl FieldKind {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Integer => value.is_u64() || value.is_i64(),
+            Self::Bool => value.is_boolean(),
+            Self::Array => value.is_array(),
+            Self::Object => value.is_object(),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::String => "a string",
+            Self::Integer => "an integer",
+            Self::Bool => "a boolean",
+            Self::Array => "an array",
+            Self::Object => "an object",
+        }
+    }
+}
+
+const PACKAGE_FIELDS: &[(&str, FieldKind)] = &[
+    ("version", FieldKind::String),
+    ("safeAddress", FieldKind::String),
+    ("safeTxHash", FieldKind::String),
+    ("chainId", FieldKind::Integer),
+    ("transaction", FieldKind::Object),
+    ("confirmations", FieldKind::Array),
+    ("confirmationsRequired", FieldKind::Integer),
+    ("sources", FieldKind::Object),
+    ("packagedAt", FieldKind::String),
+];
+
+const CONSENSUS_PROOF_FIELDS: &[(&str, FieldKind)] = &[
+    ("stateRoot", FieldKind::String),
+    ("blockNumber", FieldKind::Integer),
+    ("network", FieldKind::String),
+];
+
+const BEACON_PROOF_FIELDS: &[(&str, FieldKind)] = &[
+    ("checkpoint", FieldKind::String),
+    ("bootstrap", FieldKind::String),
+    ("updates", FieldKind::Array),
+    ("finalityUpdate", FieldKind::String),
+];
+
+const ENVELOPE_PROOF_FIELDS: &[(&str, FieldKind)] = &[("proofPayload", FieldKind::String)];
+
+const SIMULATION_FIELDS: &[(&str, FieldKind)] = &[
+    ("success", FieldKind::Bool),
+    ("gasUsed", FieldKind::String),
+    ("logs", FieldKind::Array),
+    ("blockNumber", FieldKind::Integer),
+];
+
+const WITNESS_FIELDS: &[(&str, FieldKind)] = &[
+    ("chainId", FieldKind::Integer),
+    ("safeAddress", FieldKind::String),
+    ("blockNumber", FieldKind::Integer),
+    ("stateRoot", FieldKind::String),
+    ("safeAccountProof", FieldKind::Object),
+    ("overriddenSlots", FieldKind::Array),
+    ("simulationDigest", FieldKind::String),
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageStructureResult {
+    pub valid: bool,
+    /// Every structural problem found; empty when `valid`.
+    pub errors: Vec<PackageStructureError>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageStructureError {
+    /// `package`, `consensusProof`, `simulation` or `simulationWitness`.
+    pub section: String,
+    /// Dotted path of the offending field, e.g. `simulation.logs`.
+    pub path: String,
+    pub error: String,
+}
+
+pub fn validate_package_structure(package: &Value) -> PackageStructureResult {
+    let mut errors = Vec::new();
+    let Some(fields) = package.as_object() else {
+        errors.push(structure_error(
+            "package",
+            "package",
+            "must be a JSON object".into(),
+        ));
+        return PackageStructureResult {
+            valid: false,
+            errors,
+        };
+    };
+
+    check_fields("package", package, PACKAGE_FIELDS, &mut errors);
+
+    if let Some(proof) = section(package, "consensusProof", &mut errors) {
+        check_fields("consensusProof", proof, CONSENSUS_PROOF_FIELDS, &mut errors);
+        let mode_fields = match proof.get("consensusMode").and_then(Value::as_str) {
+            None | Some("beacon") => BEACON_PROOF_FIELDS,
+            Some(_) => ENVELOPE_PROOF_FIELDS,
+        };
+        check_fields("consensusProof", proof, mode_fields, &mut errors);
+    }
+    if let Some(simulation) = section(package, "simulation", &mut errors) {
+        check_fields("simulation", simulation, SIMULATION_FIELDS, &mut errors);
+    }
+    if let Some(witness) = section(package, "simulationWitness", &mut errors) {
+        check_fields("simulationWitness", witness, WITNESS_FIELDS, &mut errors);
+    }
+
+    // Optional sections become required once another part of the package
+    // depends on them.
+    let artifacts = package.pointer("/exportContract/artifacts");
+    let claimed = |artifact: &str| {
+        artifacts
+            .and_then(|artifacts| artifacts.get(artifact))
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    let present = |name: &str| fields.get(name).is_some_and(|value| !value.is_null());
+    if !present("simulation") {
+        if present("simulationWitness") {
+            errors.push(structure_error(
+                "simulation",
+                "simulation",
+                "missing, but simulationWitness is present to replay it".into(),
+            ));
+        } else if claimed("simulation") {
+            errors.push(structure_error(
+                "simulation",
+                "simulation",
+                "missing, but exportContract.artifacts.simulation is true".into(),
+            ));
+        }
+    }
+    if !present("consensusProof") && claimed("consensusProof") {
+        errors.push(structure_error(
+            "consensusProof",
+            "consensusProof",
+            "missing, but exportContract.artifacts.consensusProof is true".into(),
+        ));
+    }
+
+    PackageStructureResult {
+        valid: errors.is_empty(),
+        errors,
+    }
+}
+
+/// The section's object when present and well-typed; `null` counts as absent.
+fn section<'a>(
+    package: &'a Value,
+    name: &str,
+    errors: &mut Vec<PackageStructureError>,
+) -> Option<&'a Value> {
+    match package.get(name) {
+        None | Some(Value::Null) => None,
+        Some(value) if value.is_object() => Some(value),
+        Some(_) => {
+            errors.push(structure_error(name, name, "must be an object".into()));
+            None
+        }
+    }
+}
+
+/// Fields of the top level are reported under section `package` by bare
+/// name, fields of a section as `section.field`.
+fn check_fields(
+    section: &str,
+    object: &Value,
+    fields: &[(&str, FieldKind)],
+    errors: &mut Vec<PackageStructureError>,
+) {
+    for (name, kind) in fields {
+        let path = if section == "package" {
+            name.to_string()
+        } else {
+            format!("{section}.{name}")
+        };
+        match object.get(name) {
+            None | Some(Value::Null) => {
+                errors.push(structure_error(section, &path, "missing".into()))
+            }
+            Some(value) if !kind.matches(value) => errors.push(structure_error(
+                section,
+                &path,
+                format!("must be {}", kind.describe()),
+            )),
+            Some(_) => {}
+        }
+    }
+}
+
+fn structure_error(section: &str, path: &str, error: String) -> PackageStructureError {
+    PackageStructureError {
+        section: section.to_string(),
+        path: path.to_string(),
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn package() -> Value {
+        json!({
+            "version": "1.2",
+            "safeAddress": "0x1000000000000000000000000000000000000001",
+            "safeTxHash": format!("0x{}", "11".repeat(32)),
+            "chainId": 1,
+            "transaction": {},
+            "confirmations": [],
+            "confirmationsRequired": 1,
+            "sources": {},
+            "packagedAt": "2026-01-01T00:00:00Z",
+            "simulation": {
+                "success": true,
+                "gasUsed": "21000",
+                "logs": [],
+                "blockNumber": 21000000
+            },
+            "simulationWitness": {
+                "chainId": 1,
+                "safeAddress": "0x1000000000000000000000000000000000000001",
+                "blockNumber": 21000000,
+                "stateRoot": format!("0x{}", "22".repeat(32)),
+                "safeAccountProof": {},
+                "overriddenSlots": [],
+                "simulationDigest": format!("0x{}", "33".repeat(32))
+            }
+        })
+    }
+
+    #[test]
+    fn accepts_a_well_formed_package() {
+        let result = validate_package_structure(&package());
+
+        assert!(result.valid, "{result:?}");
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn reports_a_missing_simulation_section() {
+        let mut package = package();
+        package
+            .as_object_mut()
+            .expect("object")
+            .remove("simulation");
+
+        let result = validate_package_structure(&package);
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.errors,
+            vec![structure_error(
+                "simulation",
+                "simulation",
+                "missing, but simulationWitness is present to replay it".into(),
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_every_malformed_field_at_once() {
+        let mut package = package();
+        package["chainId"] = json!("1");
+        package["simulation"]["logs"] = json!({});
+        package["consensusProof"] = json!({ "stateRoot": "0x00", "blockNumber": 1 });
+
+        let paths: Vec<String> = validate_package_structure(&package)
+            .errors
+            .into_iter()
+            .map(|error| error.path)
+            .collect();
+
+        assert_eq!(
+            paths,
+            [
+                "chainId",
+                "consensusProof.network",
+                "consensusProof.checkpoint",
+                "consensusProof.bootstrap",
+                "consensusProof.updates",
+                "consensusProof.finalityUpdate",
+                "simulation.logs",
+            ]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Receipt-trie inclusion proofs for individual logs.
+//!
+//! Given the consensus-verified receipts root, proves that a receipt sits at
+//! its transaction index in the block's receipt trie and that a specific log
+//! appears in it. This binds an event to the verified block cryptographically
+//! instead of trusting replay or RPC logs.
+
+use alloy::{
+    consensus::ReceiptEnvelope,
+    eips::eip2718::Decodable2718,
+    primitives::{Address, Bytes, Log, B256},
+    trie::{proof::verify_proof, Nibbles},
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogInclusionInput {
+    /// Receipts root of the verified block (`verifiedReceiptsRoot`).
+    pub receipts_root: String,
+    /// Position of the transaction in the block; the trie key is its RLP
+    /// encoding.
+    pub transaction_index: u64,
+    /// EIP-2718 encoded receipt (type byte prefix for typed receipts).
+    pub receipt: String,
+    /// Merkle-Patricia proof nodes, root first (as in `eth_getProof`).
+    pub proof: Vec<String>,
+    pub log: InclusionLog,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogInclusionResult {
+    pub valid: bool,
+    /// The receipt is proven under `receiptsRoot` at `transactionIndex`.
+    pub receipt_included: bool,
+    /// Index of the matching log within the receipt, when found.
+    pub log_index: Option<usize>,
+    pub error: Option<String>,
+}
+
+pub fn verify_log_inclusion(input: LogInclusionInput) -> LogInclusionResult {
+    match prove_log_inclusion(&input) {
+        Ok(log_index) => LogInclusionResult {
+            valid: true,
+            receipt_included: true,
+            log_index: Some(log_index),
+            error: None,
+        },
+        Err((receipt_included, error)) => LogInclusionResult {
+            valid: false,
+            receipt_included,
+            log_index: None,
+            error: Some(error),
+        },
+    }
+}
+
+/// Returns the matching log index, or whether the receipt itself was proven
+/// alongside the failure reason.
+fn prove_log_inclusion(input: &LogInclusionInput) -> Result<usize, (bool, String)> {
+    let not_included = |error: String| (false, error);
+    let receipts_root = B256::from_str(&input.receipts_root)
+        .map_err(|err| not_included(format!("invalid receiptsRoot: {err}")))?;
+    let receipt = parse_hex(&input.receipt, "receipt").map_err(not_included)?;
+    let proof = input
+        .proof
+        .iter()
+        .enumerate()
+        .map(|(index, node)| parse_hex(node, &format!("proof[{index}]")))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(not_included)?;
+    let expected_log = parse_log(&input.log).map_err(not_included)?;
+
+    verify_proof(
+        receipts_root,
+        receipt_trie_key(input.transaction_index),
+        Some(receipt.to_vec()),
+        &proof,
+    )
+    .map_err(|err| {
+        not_included(format!(
+            "receipt is not included at transaction index {} under receipts root {receipts_root:#x}: {err}",
+            input.transaction_index
+        ))
+    })?;
+
+    let envelope = ReceiptEnvelope::decode_2718(&mut receipt.as_ref())
+        .map_err(|err| (true, format!("failed to decode receipt: {err}")))?;
+    envelope
+        .logs()
+        .iter()
+        .position(|log| *log == expected_log)
+        .ok_or_else(|| {
+            (
+                true,
+                format!(
+                    "log from {:#x} does not appear in the receipt at transaction index {}.",
+                    expected_log.address, input.transaction_index
+                ),
+            )
+        })
+}
+
+/// Receipt trie keys are the RLP encoding of the transaction index.
+fn receipt_trie_key(transaction_index: u64) -> Nibbles {
+    Nibbles::unpack(alloy::rlp::encode(transaction_index))
+}
+
+fn parse_log(log: &InclusionLog) -> Result<Log, String> {
+    let address = Address::from_str(&log.address)
+        .map_err(|err| format!("invalid log.address ({}): {err}", log.address))?;
+    let topics = log
+        .topics
+        .iter()
+        .map(|topic| {
+            B256::from_str(topic).map_err(|err| format!("invalid log topic ({topic}): {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let data = parse_hex(&log.data, "log.data")?;
+    Log::new(address, topics, data).ok_or_else(|| "log has more than four topics.".to_string())
+}
+
+fn parse_hex(raw: &str, field: &str) -> Result<Bytes, String> {
+    Bytes::from_str(raw.trim()).map_err(|err| format!("invalid {field}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        consensus::{Eip658Value, Receipt},
+        eips::eip2718::Encodable2718,
+        trie::{proof::ProofRetainer, HashBuilder},
+    };
+    use std::collections::BTreeMap;
+
+    fn transfer_log(amount: u8) -> Log {
+        Log::new(
+            Address::repeat_byte(0x11),
+            vec![B256::repeat_byte(0xdd), B256::repeat_byte(0x01)],
+            Bytes::from(vec![0, 0, 0, amount]),
+        )
+        .expect("valid log")
+    }
+
+    fn encoded_receipt(cumulative_gas_used: u64, logs: Vec<Log>) -> Vec<u8> {
+        ReceiptEnvelope::Eip1559(
+            Receipt {
+                status: Eip658Value::Eip658(true),
+                cumulative_gas_used,
+                logs,
+            }
+            .with_bloom(),
+        )
+        .encoded_2718()
+    }
+
+    /// Build a three-receipt block trie and a proof for `target_index`.
+    fn receipt_fixture(target_index: u64) -> (B256, Vec<u8>, Vec<String>) {
+        let receipts = [
+            encoded_receipt(21_000, vec![]),
+            encoded_receipt(80_000, vec![transfer_log(1), transfer_log(2)]),
+            encoded_receipt(120_000, vec![transfer_log(3)]),
+        ];
+        let target_key = receipt_trie_key(target_index);
+        let leaves: BTreeMap<Nibbles, &Vec<u8>> = receipts
+            .iter()
+            .enumerate()
+            .map(|(index, receipt)| (receipt_trie_key(index as u64), receipt))
+            .collect();
+        let mut builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![target_key]));
+        for (key, receipt) in &leaves {
+            builder.add_leaf(*key, receipt);
+        }
+        let root = builder.root();
+        let proof = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| format!("{node}"))
+            .collect();
+        (root, receipts[target_index as usize].clone(), proof)
+    }
+
+    fn inclusion_input(log: &Log) -> LogInclusionInput {
+        let (root, receipt, proof) = receipt_fixture(1);
+        LogInclusionInput {
+            receipts_root: format!("{root:#x}"),
+            transaction_index: 1,
+            receipt: format!("0x{}", hex::encode(receipt)),
+            proof,
+            log: InclusionLog {
+                address: format!("{:#x}", log.address),
+                topics: log
+                    .topics()
+                    .iter()
+                    .map(|topic| format!("{topic:#x}"))
+                    .collect(),
+                data: format!("{}", log.data.data),
+            },
+        }
+    }
+
+    #[test]
+    fn proves_log_included_under_receipts_root() {
+        let result = verify_log_inclusion(inclusion_input(&transfer_log(2)));
+
+        assert!(result.valid, "{result:?}");
+        assert!(result.receipt_included);
+        assert_eq!(result.log_index, Some(1));
+    }
+
+    #[test]
+    fn rejects_tampered_log_in_included_receipt() {
+        let result = verify_log_inclusion(inclusion_input(&transfer_log(9)));
+
+        assert!(!result.valid);
+        assert!(result.receipt_included);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("does not appear in the receipt"));
+    }
+
+    #[test]
+    fn rejects_receipt_proven_at_wrong_transaction_index() {
+        let mut input = inclusion_input(&transfer_log(2));
+        input.transaction_index = 2;
+
+        let result = verify_log_inclusion(input);
+
+        assert!(!result.valid);
+        assert!(!result.receipt_included);
+    }
+
+    #[test]
+    fn encodes_transaction_index_as_rlp_trie_key() {
+        assert_eq!(receipt_trie_key(0), Nibbles::unpack([0x80]));
+        assert_eq!(receipt_trie_key(1), Nibbles::unpack([0x01]));
+        assert_eq!(receipt_trie_key(128), Nibbles::unpack([0x81, 0x80]));
+    }
+}
@@ -0,0 +1,213 @@
+//! Field-level comparison of two replay inputs.
+//!
+//! Lets an operator holding the same package from two sources confirm they
+//! describe the same transaction without running the EVM. Only the package
+//! content is compared (chain ids, Safe, transaction, simulation, witness);
+//! verifier options such as `outputNumberFormat` are not.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::simulation_replay::SimulationReplayInput;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayInputDifference {
+    /// Dotted path to the field, e.g. `simulationWitness.replayAccounts[0].balance`.
+    pub path: String,
+    /// `None` when the field is absent from the first input.
+    pub left: Option<Value>,
+    /// `None` when the field is absent from the second input.
+    pub right: Option<Value>,
+}
+
+/// Differences between `a` and `b` after canonicalization, ordered by path.
+/// Hex strings and hex map keys compare case-insensitively and replay
+/// accounts compare by address rather than list position.
+pub fn compare_replay_inputs(
+    a: &SimulationReplayInput,
+    b: &SimulationReplayInput,
+) -> Result<Vec<ReplayInputDifference>, String> {
+    let mut differences = Vec::new();
+    diff_values(
+        "",
+        Some(&canonical_package(a)?),
+        Some(&canonical_package(b)?),
+        &mut differences,
+    );
+    Ok(differences)
+}
+
+fn canonical_package(input: &SimulationReplayInput) -> Result<Value, String> {
+    let mut package = Map::new();
+    package.insert("chainId".into(), Value::from(input.chain_id));
+    package.insert("packageChainId".into(), Value::from(input.package_chain_id));
+    package.insert(
+        "safeAddress".into(),
+        Value::from(input.safe_address.clone()),
+    );
+    package.insert("transaction".into(), to_json(&input.transaction)?);
+    package.insert("simulation".into(), to_json(&input.simulation)?);
+    package.insert(
+        "simulationWitness".into(),
+        to_json(&input.simulation_witness)?,
+    );
+
+    let mut package = canonicalize(Value::Object(package));
+    if let Some(Value::Array(accounts)) = package.pointer_mut("/simulationWitness/replayAccounts") {
+        accounts.sort_by(|left, right| {
+            left["address"]
+                .as_str()
+                .unwrap_or_default()
+                .cmp(right["address"].as_str().unwrap_or_default())
+        });
+    }
+    Ok(package)
+}
+
+fn to_json(value: &impl Serialize) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|err| format!("failed to serialize replay input: {err}"))
+}
+
+/// Lowercase every `0x`-prefixed string and object key.
+pub(crate) fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(canonical_hex(text)),
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (canonical_hex(key), canonicalize(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn canonical_hex(text: String) -> String {
+    if text.starts_with("0x") || text.starts_with("0X") {
+        text.to_ascii_lowercase()
+    } else {
+        text
+    }
+}
+
+fn diff_values(
+    path: &str,
+    left: Option<&Value>,
+    right: Option<&Value>,
+    differences: &mut Vec<ReplayInputDifference>,
+) {
+    match (left, right) {
+        (Some(Value::Object(left)), Some(Value::Object(right))) => {
+            let mut keys: Vec<&String> = left.keys().chain(right.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_values(
+                    &child_path(path, key),
+                    left.get(key),
+                    right.get(key),
+                    differences,
+                );
+            }
+        }
+        (Some(Value::Array(left)), Some(Value::Array(right))) => {
+            for index in 0..left.len().max(right.len()) {
+                diff_values(
+                    &format!("{path}[{index}]"),
+                    left.get(index),
+                    right.get(index),
+                    differences,
+                );
+            }
+        }
+        _ if left == right => {}
+        _ => differences.push(ReplayInputDifference {
+            path: path.to_string(),
+            left: left.cloned(),
+            right: right.cloned(),
+        }),
+    }
+}
+
+/// Hex map keys (storage slots, prestate addresses) are bracketed so the path
+/// stays readable.
+fn child_path(parent: &str, key: &str) -> String {
+    match (parent.is_empty(), key.starts_with("0x")) {
+        (_, true) => format!("{parent}[{key}]"),
+        (true, false) => key.to_string(),
+        (false, false) => format!("{parent}.{key}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn replay_input(slot_value: &str) -> SimulationReplayInput {
+        let caller = "0x1000000000000000000000000000000000000001";
+        serde_json::from_value(json!({
+            "chainId": 1,
+            "safeAddress": "0x1000000000000000000000000000000000000001",
+            "transaction": {
+                "to": "0x2000000000000000000000000000000000000002",
+                "value": "0",
+                "data": "0xA9059CBB",
+                "operation": 0
+            },
+            "simulation": { "success": true, "gasUsed": "50000" },
+            "simulationWitness": {
+                "replayCaller": caller,
+                "replayAccounts": [
+                    {
+                        "address": "0x2000000000000000000000000000000000000002",
+                        "balance": "0",
+                        "nonce": 1,
+                        "code": "0x00",
+                        "storage": { "0x01": slot_value }
+                    },
+                    {
+                        "address": caller,
+                        "balance": "1000",
+                        "nonce": 0,
+                        "code": "0x"
+                    }
+                ]
+            }
+        }))
+        .expect("replay input")
+    }
+
+    #[test]
+    fn reports_only_the_differing_storage_value() {
+        let a = replay_input("0x05");
+        let b = replay_input("0x06");
+
+        let differences = compare_replay_inputs(&a, &b).expect("comparison");
+
+        assert_eq!(
+            differences,
+            vec![ReplayInputDifference {
+                path: "simulationWitness.replayAccounts[1].storage[0x01]".into(),
+                left: Some(json!("0x05")),
+                right: Some(json!("0x06")),
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_hex_casing_and_account_order() {
+        let a = replay_input("0xAB");
+        let mut b = replay_input("0xab");
+        b.transaction.data = Some("0xa9059cbb".into());
+        b.simulation_witness
+            .replay_accounts
+            .as_mut()
+            .expect("accounts")
+            .reverse();
+
+        assert_eq!(compare_replay_inputs(&a, &b), Ok(Vec::new()));
+    }
+}
@@ -0,0 +1,84 @@
+//! Binary encodings of verification results for machine-to-machine use.
+//!
+//! Results are first serialized to the same `serde_json::Value` the JSON IPC
+//! path produces, then written out, so every encoding carries exactly the
+//! JSON field set. CBOR (RFC 8949) is written with `ciborium`.
+//!
+//! MessagePack is not offered. CBOR is a standardized compact encoding of
+//! the same data model, so a second binary format would only add a
+//! dependency and another encoding consumers have to pick between.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultEncoding {
+    /// UTF-8 JSON, identical to the plain commands' payload.
+    #[default]
+    Json,
+    Cbor,
+}
+
+pub fn encode_result<T: Serialize>(
+    result: &T,
+    encoding: ResultEncoding,
+) -> Result<Vec<u8>, String> {
+    let value = serde_json::to_value(result)
+        .map_err(|err| format!("failed to serialize verification result: {err}"))?;
+    Ok(match encoding {
+        ResultEncoding::Json => value.to_string().into_bytes(),
+        ResultEncoding::Cbor => {
+            let mut out = Vec::new();
+            ciborium::into_writer(&value, &mut out)
+                .map_err(|err| format!("failed to encode verification result as CBOR: {err}"))?;
+            out
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation_replay::{
+        verify_simulation_replay, SimulationReplayInput, SimulationReplayVerificationResult,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn encodes_rfc_8949_examples() {
+        let cbor = |value: Value| encode_result(&value, ResultEncoding::Cbor).expect("cbor");
+
+        assert_eq!(cbor(json!(23)), [0x17]);
+        assert_eq!(cbor(json!(1000)), [0x19, 0x03, 0xe8]);
+        assert_eq!(cbor(json!(-1000)), [0x39, 0x03, 0xe7]);
+        assert_eq!(
+            cbor(json!(1.1)),
+            hex::decode("fb3ff199999999999a").expect("hex")
+        );
+        assert_eq!(cbor(json!("IETF")), hex::decode("6449455446").expect("hex"));
+        assert_eq!(
+            cbor(json!({"a": 1, "b": [2, 3]})),
+            hex::decode("a26161016162820203").expect("hex")
+        );
+    }
+
+    #[test]
+    fn round_trips_a_cbor_encoded_replay_result() {
+        let result = verify_simulation_replay(SimulationReplayInput::default());
+
+        let encoded = encode_result(&result, ResultEncoding::Cbor).expect("cbor");
+        let value: Value = ciborium::from_reader(encoded.as_slice()).expect("cbor value");
+        let decoded: SimulationReplayVerificationResult =
+            ciborium::from_reader(encoded.as_slice()).expect("cbor result");
+
+        assert_eq!(value, serde_json::to_value(&result).expect("json"));
+        assert_eq!(decoded, result);
+        assert!(
+            encoded.len()
+                < encode_result(&result, ResultEncoding::Json)
+                    .expect("json")
+                    .len()
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Proof that the Safe is deployed at the consensus-verified block.
+//!
+//! Every other check assumes `safeAddress` is a contract. An `eth_getProof`
+//! account proof under the verified state root settles that: the account
+//! must be present and carry code. A typo'd address either has no leaf
+//! (exclusion proof) or an EOA-shaped leaf with the empty code hash.
+
+use alloy::{
+    primitives::{keccak256, Address, Bytes, B256, KECCAK_EMPTY},
+    trie::{proof::verify_proof, Nibbles},
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const ERR_SAFE_NOT_DEPLOYED: &str = "safe-not-deployed";
+const ERR_SAFE_ACCOUNT_UNPROVEN: &str = "safe-account-unproven";
+const ERR_SAFE_STORAGE_UNPROVEN: &str = "safe-storage-unproven";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeAccountProofInput {
+    pub safe_address: String,
+    /// `verified_state_root` from `verify_consensus_proof`.
+    pub state_root: String,
+    /// The account as returned by `eth_getProof`; absent when the proof is
+    /// an exclusion proof.
+    pub account: Option<ProvenAccount>,
+    /// `accountProof` for the Safe, root first.
+    pub account_proof: Vec<String>,
+    /// `storageProof` entries for the Safe's slots, proven against the
+    /// account's `storageHash`.
+    #[serde(default)]
+    pub storage_proof: Vec<StorageSlotProof>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSlotProof {
+    pub key: String,
+    pub value: String,
+    /// Storage trie nodes, root first.
+    pub proof: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenAccount {
+    pub nonce: u64,
+    pub balance: String,
+    pub storage_hash: String,
+    pub code_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeAccountProofResult {
+    pub valid: bool,
+    pub safe_address: String,
+    /// The account proof was accepted and shows an account with code, even
+    /// when a storage proof then failed (`safe-storage-unproven`).
+    pub deployed: bool,
+    /// Storage root every `storageProof` entry verified against; `None`
+    /// when no storage proofs were given or any of them failed.
+    pub storage_root: Option<String>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+pub fn verify_safe_account_proof(input: &SafeAccountProofInput) -> SafeAccountProofResult {
+    let mut storage_root = None;
+    let proven = Address::from_str(input.safe_address.trim())
+        .map_err(|err| format!("invalid safeAddress: {err}"))
+        .and_then(|safe| {
+            let state_root = parse_b256(&input.state_root, "stateRoot")?;
+            prove_account(
+                safe,
+                state_root,
+                input.account.as_ref(),
+                &input.account_proof,
+            )
+        });
+    let deployed = matches!(&proven, Ok(Some(leaf)) if leaf.code_hash != KECCAK_EMPTY);
+    let failure = match proven {
+        Ok(Some(proven)) if proven.code_hash != KECCAK_EMPTY => {
+            match prove_storage(proven.storage_root, &input.storage_proof) {
+                Ok(()) => {
+                    storage_root = (!input.storage_proof.is_empty()).then_some(proven.storage_root);
+                    None
+                }
+                Err(error) => Some((ERR_SAFE_STORAGE_UNPROVEN, error)),
+            }
+        }
+        Ok(_) => Some((
+            ERR_SAFE_NOT_DEPLOYED,
+            format!(
+                "Safe {} has no code under the verified state root.",
+                input.safe_address
+            ),
+        )),
+        Err(error) => Some((ERR_SAFE_ACCOUNT_UNPROVEN, error)),
+    };
+
+    SafeAccountProofResult {
+        valid: failure.is_none(),
+        safe_address: input.safe_address.clone(),
+        deployed,
+        storage_root: storage_root.map(|root| format!("{root:#x}")),
+        error: failure.as_ref().map(|(_, error)| error.clone()),
+        error_code: failure.map(|(code, _)| code.to_string()),
+    }
+}
+
+/// Hashes committed to by a proven account leaf.
+pub(crate) struct ProvenLeaf {
+    pub(crate) code_hash: B256,
+    pub(crate) storage_root: B256,
+}
+
+/// Returns the proven leaf, or `None` when the account is proven absent.
+pub(crate) fn prove_account(
+    address: Address,
+    state_root: B256,
+    account: Option<&ProvenAccount>,
+    account_proof: &[String],
+) -> Result<Option<ProvenLeaf>, String> {
+    let proof = account_proof
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            Bytes::from_str(node.trim())
+                .map_err(|err| format!("invalid accountProof[{index}]: {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (leaf, proven) = match account {
+        Some(account) => {
+            let code_hash = parse_b256(&account.code_hash, "account.codeHash")?;
+            let storage_root = parse_b256(&account.storage_hash, "account.storageHash")?;
+            (
+                Some(proven_account_leaf(account, code_hash)?),
+                Some(ProvenLeaf {
+                    code_hash,
+                    storage_root,
+                }),
+            )
+        }
+        None => (None, None),
+    };
+
+    verify_proof(
+        state_root,
+        Nibbles::unpack(keccak256(address)),
+        leaf,
+        &proof,
+    )
+    .map_err(|err| {
+        format!("Account {address:#x} is not proven under state root {state_root:#x}: {err}")
+    })?;
+    Ok(proven)
+}
+
+/// Every slot proof must lead to `storage_root`; zero values are proven by
+/// exclusion, as they are absent from the storage trie.
+pub(crate) fn prove_storage(storage_root: B256, slots: &[StorageSlotProof]) -> Result<(), String> {
+    for (index, slot) in slots.iter().enumerate() {
+        let key = crate::number_format::parse_quantity(&slot.key)
+            .map(B256::from)
+            .map_err(|err| format!("invalid storageProof[{index}].key: {err}"))?;
+        let value = crate::number_format::parse_quantity(&slot.value)
+            .map_err(|err| format!("invalid storageProof[{index}].value: {err}"))?;
+        let proof = slot
+            .proof
+            .iter()
+            .enumerate()
+            .map(|(node_index, node)| {
+                Bytes::from_str(node.trim()).map_err(|err| {
+                    format!("invalid storageProof[{index}].proof[{node_index}]: {err}")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let leaf = (!value.is_zero()).then(|| alloy::rlp::encode(value));
+        verify_proof(storage_root, Nibbles::unpack(keccak256(key)), leaf, &proof).map_err(
+            |err| {
+                format!("Slot {key:#x} is not proven under storage root {storage_root:#x}: {err}")
+            },
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn proven_account_leaf(
+    account: &ProvenAccount,
+    code_hash: B256,
+) -> Result<Vec<u8>, String> {
+    let balance = crate::number_format::parse_quantity(&account.balance)
+        .map_err(|err| format!("invalid account.balance: {err}"))?;
+    let storage_root = parse_b256(&account.storage_hash, "account.storageHash")?;
+    Ok(crate::state_root::encode_account(
+        account.nonce,
+        balance,
+        storage_root,
+        code_hash,
+    ))
+}
+
+fn parse_b256(raw: &str, field: &str) -> Result<B256, String> {
+    B256::from_str(raw.trim()).map_err(|err| format!("invalid {field}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        primitives::U256,
+        trie::{proof::ProofRetainer, HashBuilder, EMPTY_ROOT_HASH},
+    };
+
+    const SAFE: &str = "0x1000000000000000000000000000000000000001";
+
+    /// Input proving `account` for `SAFE` as the only leaf of the state trie.
+    fn single_account_input(account: ProvenAccount) -> SafeAccountProofInput {
+        let safe = Address::from_str(SAFE).expect("address");
+        let code_hash = B256::from_str(&account.code_hash).expect("code hash");
+        let key = Nibbles::unpack(keccak256(safe));
+        let mut builder = HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![key]));
+        builder.add_leaf(
+            key,
+            &proven_account_leaf(&account, code_hash).expect("account"),
+        );
+        let state_root = builder.root();
+        let account_proof = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| format!("{node}"))
+            .collect();
+
+        SafeAccountProofInput {
+            safe_address: SAFE.into(),
+            state_root: format!("{state_root:#x}"),
+            account: Some(account),
+            account_proof,
+            storage_proof: Vec::new(),
+        }
+    }
+
+    fn account_with_code_hash(code_hash: B256) -> ProvenAccount {
+        ProvenAccount {
+            nonce: 1,
+            balance: "0".into(),
+            storage_hash: format!("{EMPTY_ROOT_HASH:#x}"),
+            code_hash: format!("{code_hash:#x}"),
+        }
+    }
+
+    #[test]
+    fn accepts_a_proven_account_with_code() {
+        let result = verify_safe_account_proof(&single_account_input(account_with_code_hash(
+            B256::repeat_byte(0xcc),
+        )));
+
+        assert!(result.valid, "{result:?}");
+        assert!(result.deployed);
+        assert_eq!(result.error_code, None);
+    }
+
+    #[test]
+    fn rejects_a_safe_whose_proof_shows_an_empty_account() {
+        let result = verify_safe_account_proof(&single_account_input(ProvenAccount {
+            nonce: 0,
+            ..account_with_code_hash(KECCAK_EMPTY)
+        }));
+
+        assert!(!result.valid);
+        assert!(!result.deployed);
+        assert_eq!(result.error_code.as_deref(), Some(ERR_SAFE_NOT_DEPLOYED));
+    }
+
+    #[test]
+    fn reports_the_storage_root_the_slot_proofs_verify_against() {
+        let slot = B256::with_last_byte(4);
+        let key = Nibbles::unpack(keccak256(slot));
+        let mut builder = HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![key]));
+        builder.add_leaf(key, &alloy::rlp::encode(U256::from(2)));
+        let storage_root = builder.root();
+        let proof = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| format!("{node}"))
+            .collect();
+        let mut input = single_account_input(ProvenAccount {
+            storage_hash: format!("{storage_root:#x}"),
+            ..account_with_code_hash(B256::repeat_byte(0xcc))
+        });
+        let without_slots = verify_safe_account_proof(&input);
+        input.storage_proof = vec![StorageSlotProof {
+            key: "0x4".into(),
+            value: "0x2".into(),
+            proof,
+        }];
+
+        let result = verify_safe_account_proof(&input);
+
+        assert!(result.valid, "{result:?}");
+        assert_eq!(
+            result.storage_root,
+            input
+                .account
+                .as_ref()
+                .map(|account| account.storage_hash.clone())
+        );
+        assert_eq!(without_slots.storage_root, None);
+
+        input.storage_proof[0].value = "0x3".into();
+        let tampered = verify_safe_account_proof(&input);
+        assert!(!tampered.valid);
+        assert!(tampered.deployed);
+        assert_eq!(tampered.storage_root, None);
+        assert_eq!(
+            tampered.error_code.as_deref(),
+            Some(ERR_SAFE_STORAGE_UNPROVEN)
+        );
+    }
+
+    #[test]
+    fn rejects_an_account_not_proven_under_the_state_root() {
+        let mut input = single_account_input(account_with_code_hash(B256::repeat_byte(0xcc)));
+        input.state_root = format!("{:#x}", B256::repeat_byte(0x11));
+
+        let result = verify_safe_account_proof(&input);
+
+        assert!(!result.valid);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_SAFE_ACCOUNT_UNPROVEN)
+        );
+    }
+}
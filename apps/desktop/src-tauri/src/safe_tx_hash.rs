@@ -0,0 +1,182 @@
+//! Safe transaction hash (EIP-712), computed exactly as the Safe contracts'
+//! `getTransactionHash` does for v1.3.0+ (chain-bound domain).
+//!
+//! Lets the desktop verifier confirm the packaged `safeTxHash` from the
+//! transaction fields instead of trusting the package.
+
+use alloy::primitives::{keccak256, Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::number_format::parse_quantity;
+
+const DOMAIN_TYPE: &str = "EIP712Domain(uint256 chainId,address verifyingContract)";
+const SAFE_TX_TYPE: &str = "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTxHashInput {
+    pub safe_address: String,
+    pub chain_id: u64,
+    pub to: String,
+    /// Decimal or `0x` quantity.
+    pub value: String,
+    pub data: Option<String>,
+    pub operation: u8,
+    pub safe_tx_gas: String,
+    pub base_gas: String,
+    pub gas_price: String,
+    pub gas_token: String,
+    pub refund_receiver: String,
+    pub nonce: u64,
+}
+
+/// Mirrors `SafeTxHashDetails` in `@safelens/core`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTxHashResult {
+    pub safe_tx_hash: String,
+    pub domain_separator: String,
+    pub message_hash: String,
+}
+
+pub fn compute_safe_tx_hash(input: &SafeTxHashInput) -> Result<SafeTxHashResult, String> {
+    let safe_address = parse_address(&input.safe_address, "safeAddress")?;
+    if input.operation > 1 {
+        return Err(format!(
+            "invalid operation: expected 0 (CALL) or 1 (DELEGATECALL), got {}",
+            input.operation
+        ));
+    }
+    let data = match input.data.as_deref() {
+        Some(raw) => Bytes::from_str(raw.trim()).map_err(|err| format!("invalid data: {err}"))?,
+        None => Bytes::new(),
+    };
+
+    let domain_separator = hash_words(&[
+        keccak256(DOMAIN_TYPE),
+        word(U256::from(input.chain_id)),
+        safe_address.into_word(),
+    ]);
+    let message_hash = hash_words(&[
+        keccak256(SAFE_TX_TYPE),
+        parse_address(&input.to, "to")?.into_word(),
+        word(parse_uint(&input.value, "value")?),
+        keccak256(&data),
+        word(U256::from(input.operation)),
+        word(parse_uint(&input.safe_tx_gas, "safeTxGas")?),
+        word(parse_uint(&input.base_gas, "baseGas")?),
+        word(parse_uint(&input.gas_price, "gasPrice")?),
+        parse_address(&input.gas_token, "gasToken")?.into_word(),
+        parse_address(&input.refund_receiver, "refundReceiver")?.into_word(),
+        word(U256::from(input.nonce)),
+    ]);
+    let safe_tx_hash = keccak256(
+        [
+            &[0x19, 0x01][..],
+            domain_separator.as_slice(),
+            message_hash.as_slice(),
+        ]
+        .concat(),
+    );
+
+    Ok(SafeTxHashResult {
+        safe_tx_hash: format!("{safe_tx_hash:#x}"),
+        domain_separator: format!("{domain_separator:#x}"),
+        message_hash: format!("{message_hash:#x}"),
+    })
+}
+
+/// `keccak256(abi.encode(words...))` for static 32-byte words.
+fn hash_words(words: &[B256]) -> B256 {
+    keccak256(words.iter().flat_map(|word| word.0).collect::<Vec<u8>>())
+}
+
+fn word(value: U256) -> B256 {
+    B256::from(value.to_be_bytes())
+}
+
+fn parse_address(raw: &str, field: &str) -> Result<Address, String> {
+    Address::from_str(raw.trim()).map_err(|err| format!("invalid {field} ({raw}): {err}"))
+}
+
+fn parse_uint(raw: &str, field: &str) -> Result<U256, String> {
+    parse_quantity(raw).map_err(|err| format!("invalid {field} ({raw}): {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// WXDAI deposit from a Gnosis Chain Safe (`test-wxdai-gnosis.json`).
+    fn wxdai_deposit() -> SafeTxHashInput {
+        SafeTxHashInput {
+            safe_address: "0x849D52316331967b6fF1198e5E32A0eB168D039d".to_string(),
+            chain_id: 100,
+            to: "0x1643E812aE58766192Cf7D2Cf9567dF2C37e9B7F".to_string(),
+            value: "1000000000000000000".to_string(),
+            data: Some("0xd0e30db0".to_string()),
+            operation: 0,
+            safe_tx_gas: "0".to_string(),
+            base_gas: "0".to_string(),
+            gas_price: "0".to_string(),
+            gas_token: "0x0000000000000000000000000000000000000000".to_string(),
+            refund_receiver: "0x0000000000000000000000000000000000000000".to_string(),
+            nonce: 43,
+        }
+    }
+
+    #[test]
+    fn computes_known_safe_tx_hash() {
+        let result = compute_safe_tx_hash(&wxdai_deposit()).expect("valid input");
+
+        assert_eq!(
+            result.safe_tx_hash,
+            "0xd5c256c2064c39fa9e3f0a14032c79265dc7b53451e7ffccba877a9593f55cac"
+        );
+    }
+
+    #[test]
+    fn accepts_hex_quantities_for_value_and_gas_fields() {
+        let decimal = compute_safe_tx_hash(&wxdai_deposit()).expect("valid input");
+        let hex = compute_safe_tx_hash(&SafeTxHashInput {
+            value: "0xde0b6b3a7640000".to_string(),
+            safe_tx_gas: "0x0".to_string(),
+            ..wxdai_deposit()
+        })
+        .expect("valid input");
+
+        assert_eq!(hex, decimal);
+    }
+
+    #[test]
+    fn binds_hash_to_chain_and_nonce() {
+        let base = compute_safe_tx_hash(&wxdai_deposit()).expect("valid input");
+        let other_chain = compute_safe_tx_hash(&SafeTxHashInput {
+            chain_id: 1,
+            ..wxdai_deposit()
+        })
+        .expect("valid input");
+        let other_nonce = compute_safe_tx_hash(&SafeTxHashInput {
+            nonce: 44,
+            ..wxdai_deposit()
+        })
+        .expect("valid input");
+
+        assert_ne!(other_chain.domain_separator, base.domain_separator);
+        assert_eq!(other_chain.message_hash, base.message_hash);
+        assert_ne!(other_nonce.message_hash, base.message_hash);
+        assert_ne!(other_nonce.safe_tx_hash, base.safe_tx_hash);
+    }
+
+    #[test]
+    fn rejects_unknown_operation() {
+        let error = compute_safe_tx_hash(&SafeTxHashInput {
+            operation: 2,
+            ..wxdai_deposit()
+        })
+        .unwrap_err();
+
+        assert!(error.starts_with("invalid operation"), "{error}");
+    }
+}
@@ -1,35 +1,326 @@
 use revm::{
-    context::{result::ExecutionResult, BlockEnv, Context, TxEnv},
-    database::CacheDB,
+    bytecode::opcode::{
+        ADD, BALANCE, CALL, CALLCODE, CALLDATACOPY, CODECOPY, CREATE, CREATE2, DELEGATECALL,
+        DIFFICULTY, EXTCODECOPY, EXTCODEHASH, EXTCODESIZE, LOG0, LOG4, LT, MCOPY, MLOAD, MSIZE,
+        MSTORE, MSTORE8, RETURNDATACOPY, SAR, SELFDESTRUCT, SIGNEXTEND, SLOAD, SSTORE, STATICCALL,
+        TLOAD, TSTORE,
+    },
+    context::{
+        result::{ExecutionResult, HaltReason},
+        BlockEnv, Context, TxEnv,
+    },
+    context_interface::{
+        block::BlobExcessGasAndPrice,
+        transaction::{AccessList, AccessListItem},
+        CreateScheme,
+    },
+    database::{CacheDB, DbAccount},
     database_interface::EmptyDB,
-    handler::{MainBuilder, MainContext},
+    handler::{ExecuteEvm, MainBuilder, MainContext},
     inspector::{InspectEvm, Inspector},
-    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
-    primitives::{Address, Bytes, Log, TxKind, B256, U256},
-    state::{AccountInfo, Bytecode},
+    interpreter::{
+        interpreter::EthInterpreter, interpreter_types::Jumps, CallInputs, CallOutcome, CallScheme,
+        CreateInputs, CreateOutcome, Interpreter,
+    },
+    precompile::Precompiles,
+    primitives::{
+        eip4844::{BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE, GAS_PER_BLOB},
+        hardfork::SpecId,
+        keccak256, Address, Bloom, Bytes, Log, TxKind, B256, KECCAK_EMPTY, U256,
+    },
+    state::{AccountInfo, Bytecode, EvmState},
+    DatabaseCommit,
 };
-use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, str::FromStr};
-
-const REASON_REPLAY_MATCHED: &str = "simulation-replay-matched";
-const REASON_REPLAY_EXEC_ERROR: &str = "simulation-replay-exec-error";
-const REASON_REPLAY_MISMATCH_SUCCESS: &str = "simulation-replay-mismatch-success";
-const REASON_REPLAY_MISMATCH_RETURN_DATA: &str = "simulation-replay-mismatch-return-data";
-const REASON_REPLAY_MISMATCH_LOGS: &str = "simulation-replay-mismatch-logs";
-const REASON_REPLAY_MISMATCH_GAS: &str = "simulation-replay-mismatch-gas";
-const REASON_WITNESS_INCOMPLETE: &str = "simulation-witness-incomplete";
-
-#[derive(Debug, Deserialize)]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    consensus::VerifiedBlockHeader,
+    hashing::compute_create2_address,
+    number_format::OutputNumberFormat,
+    offline::OFFLINE_ONLY,
+    state_root::{state_root, StateAccount},
+};
+
+/// Every `reason` a replay result can carry. The serialized strings are the
+/// frontend's `SimulationVerificationReason` union and must not change;
+/// `as_str` is their only definition, used by serde in both directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayReason {
+    Matched,
+    InvalidInput,
+    ExecError,
+    MismatchSuccess,
+    MismatchReturnData,
+    MismatchLogs,
+    MismatchGas,
+    MismatchCreate2,
+    MismatchPostStateRoot,
+    WitnessIncomplete,
+    WitnessOnlyLogsPresent,
+}
+
+impl ReplayReason {
+    const ALL: [Self; 11] = [
+        Self::Matched,
+        Self::InvalidInput,
+        Self::ExecError,
+        Self::MismatchSuccess,
+        Self::MismatchReturnData,
+        Self::MismatchLogs,
+        Self::MismatchGas,
+        Self::MismatchCreate2,
+        Self::MismatchPostStateRoot,
+        Self::WitnessIncomplete,
+        Self::WitnessOnlyLogsPresent,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Matched => "simulation-replay-matched",
+            Self::InvalidInput => "invalid-input",
+            Self::ExecError => "simulation-replay-exec-error",
+            Self::MismatchSuccess => "simulation-replay-mismatch-success",
+            Self::MismatchReturnData => "simulation-replay-mismatch-return-data",
+            Self::MismatchLogs => "simulation-replay-mismatch-logs",
+            Self::MismatchGas => "simulation-replay-mismatch-gas",
+            Self::MismatchCreate2 => "simulation-replay-mismatch-create2",
+            Self::MismatchPostStateRoot => "simulation-replay-mismatch-post-state-root",
+            Self::WitnessIncomplete => "simulation-witness-incomplete",
+            Self::WitnessOnlyLogsPresent => "simulation-witness-only-logs-present",
+        }
+    }
+}
+
+impl Serialize for ReplayReason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ReplayReason {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::ALL
+            .into_iter()
+            .find(|reason| reason.as_str() == value)
+            .ok_or_else(|| de::Error::custom(format!("unknown replay reason `{value}`")))
+    }
+}
+
+impl fmt::Display for ReplayReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Execution-layer genesis timestamps for chains whose genesis is not at
+/// timestamp zero. Every replay block must come strictly after genesis.
+const GENESIS_TIMESTAMPS: &[(u64, u64)] = &[
+    (1, 1_438_269_973), // Ethereum mainnet
+    // Gnosis (xDai) genesis carries no timestamp; its launch day
+    // (2018-10-08 UTC) bounds the first block from below.
+    (100, 1_538_956_800),
+    (11_155_111, 1_633_267_481), // Sepolia
+    (17_000, 1_695_902_100),     // Holesky
+    (560_048, 1_742_212_800),    // Hoodi
+];
+
+/// How far past the verifier's clock a replay block timestamp may lie,
+/// allowing for clock skew and a simulated pending block.
+const MAX_REPLAY_BLOCK_FUTURE_SECS: u64 = 15 * 60;
+
+/// Typical block gas limit per chain, used as the replay gas limit when
+/// neither `replayGasLimit` nor a non-zero `safeTxGas` is given.
+const DEFAULT_REPLAY_GAS_LIMITS: &[(u64, u64)] = &[
+    (1, 36_000_000),          // Ethereum
+    (10, 40_000_000),         // OP Mainnet
+    (100, 17_000_000),        // Gnosis
+    (137, 45_000_000),        // Polygon PoS
+    (8453, 150_000_000),      // Base
+    (42_161, 32_000_000),     // Arbitrum One (per-transaction limit)
+    (11_155_111, 60_000_000), // Sepolia
+];
+const FALLBACK_REPLAY_GAS_LIMIT: u64 = 30_000_000;
+/// EIP-7825 per-transaction gas limit cap, active from Osaka.
+const OSAKA_TX_GAS_LIMIT_CAP: u64 = 1 << 24;
+/// Plausible range for a witness block gas limit: geth's `MinGasLimit` up to
+/// the 2^50 Arbitrum reports for its blocks.
+const MIN_REPLAY_BLOCK_GAS_LIMIT: u64 = 5_000;
+const MAX_REPLAY_BLOCK_GAS_LIMIT: u64 = 1 << 50;
+
+/// EIP-2294 upper bound on chain ids.
+const MAX_CHAIN_ID: u64 = u64::MAX / 2 - 36;
+const DEFAULT_MAX_REPLAY_ACCOUNTS: usize = 1_000;
+const DEFAULT_MAX_STORAGE_ENTRIES_PER_ACCOUNT: usize = 10_000;
+
+/// Ethereum mainnet activations, used when `pinSpecToBlock` is set without a
+/// `forkSchedule`. Post-merge forks activate by timestamp.
+const MAINNET_FORK_SCHEDULE: &[(SpecId, ForkCondition)] = &[
+    (SpecId::FRONTIER, ForkCondition::Block(0)),
+    (SpecId::HOMESTEAD, ForkCondition::Block(1_150_000)),
+    (SpecId::DAO_FORK, ForkCondition::Block(1_920_000)),
+    (SpecId::TANGERINE, ForkCondition::Block(2_463_000)),
+    (SpecId::SPURIOUS_DRAGON, ForkCondition::Block(2_675_000)),
+    (SpecId::BYZANTIUM, ForkCondition::Block(4_370_000)),
+    (SpecId::PETERSBURG, ForkCondition::Block(7_280_000)),
+    (SpecId::ISTANBUL, ForkCondition::Block(9_069_000)),
+    (SpecId::MUIR_GLACIER, ForkCondition::Block(9_200_000)),
+    (SpecId::BERLIN, ForkCondition::Block(12_244_000)),
+    (SpecId::LONDON, ForkCondition::Block(12_965_000)),
+    (SpecId::ARROW_GLACIER, ForkCondition::Block(13_773_000)),
+    (SpecId::GRAY_GLACIER, ForkCondition::Block(15_050_000)),
+    (SpecId::MERGE, ForkCondition::Block(15_537_394)),
+    (SpecId::SHANGHAI, ForkCondition::Timestamp(1_681_338_455)),
+    (SpecId::CANCUN, ForkCondition::Timestamp(1_710_338_135)),
+    (SpecId::PRAGUE, ForkCondition::Timestamp(1_746_612_311)),
+    (SpecId::OSAKA, ForkCondition::Timestamp(1_764_798_551)),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ForkCondition {
+    Block(u64),
+    Timestamp(u64),
+}
+
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SimulationReplayInput {
     pub chain_id: u64,
+    /// Package-level `chainId`; when present it must equal `chainId` so the
+    /// replay's `CHAINID` matches the chain the package was built for.
+    pub package_chain_id: Option<u64>,
     pub safe_address: String,
     pub transaction: ReplayTransaction,
     pub simulation: ReplaySimulation,
     pub simulation_witness: ReplayWitness,
+    /// Encoding for numeric string fields in the result (decimal by default).
+    /// Hashes, addresses and storage words stay hex either way.
+    #[serde(default)]
+    pub output_number_format: OutputNumberFormat,
+    /// How replay logs are compared against `simulation.logs`.
+    #[serde(default)]
+    pub log_comparison_mode: LogComparisonMode,
+    /// Warn (without failing) when the replay uses less than this percentage
+    /// of the expected gas ceiling; a large underrun can mean a different
+    /// code path. Off when unset.
+    pub gas_underrun_threshold_percent: Option<u8>,
+    /// Display metadata for the chain's native currency, echoed into the
+    /// result untouched. Transfer values stay raw wei either way.
+    pub native_decimals: Option<u8>,
+    pub native_symbol: Option<String>,
+    /// Addresses allowed to receive native value. Any other recipient in
+    /// `replayNativeTransfers` is listed in `unexpectedRecipients`; advisory
+    /// only, it never fails the replay.
+    pub expected_recipients: Option<Vec<String>>,
+    /// Reject witnesses whose resolved caller has no replay account instead of
+    /// synthesizing an empty EOA (catches omitted contract-wallet callers).
+    #[serde(default)]
+    pub require_caller_in_witness: bool,
+    /// Reject non-empty calldata to a `transaction.to` without code, which
+    /// revm would run as a plain value transfer that ignores the data
+    /// (catches witnesses missing the target's bytecode).
+    #[serde(default)]
+    pub require_target_code: bool,
+    /// Fail with `simulation-witness-incomplete` when execution touches an
+    /// account missing from the witness (other than the synthesized caller,
+    /// precompiles and accounts created during the replay), instead of
+    /// treating it as empty.
+    #[serde(default)]
+    pub require_complete_witness: bool,
+    /// Record every SLOAD/SSTORE in execution order as `storageOps`. Heavy;
+    /// meant for debugging ordering-dependent behavior.
+    #[serde(default)]
+    pub trace_storage_ops: bool,
+    /// Aggregate the gas charged by each opcode into categories, returned as
+    /// `gasProfile`. Adds a per-step hook, so off by default.
+    #[serde(default)]
+    pub profile_gas: bool,
+    /// Return the transaction fields the replay actually ran with as
+    /// `replayTxEnvelope`, after every default and fallback was applied.
+    #[serde(default)]
+    pub include_tx_envelope: bool,
+    /// Return the logs a failed replay emitted before it reverted or halted
+    /// as `preRevertLogs`.
+    #[serde(default)]
+    pub capture_pre_revert_logs: bool,
+    /// Addresses the transaction is expected to deploy via CREATE2. When set,
+    /// the replay's CREATE2 deployments must match it exactly.
+    pub expected_create2_addresses: Option<Vec<String>>,
+    /// Upper bound on replay accounts seeded from the witness
+    /// (defaults to `DEFAULT_MAX_REPLAY_ACCOUNTS`).
+    pub max_replay_accounts: Option<usize>,
+    /// Upper bound on storage slots seeded per replay account
+    /// (defaults to `DEFAULT_MAX_STORAGE_ENTRIES_PER_ACCOUNT`).
+    pub max_storage_entries_per_account: Option<usize>,
+    /// Debug-only "what-if" edits applied on top of the witness before
+    /// replay. Any override marks the result `overridden`.
+    #[serde(default)]
+    pub state_overrides: Vec<ReplayStateOverride>,
+    /// Derive the revm spec from the replay block number/timestamp instead of
+    /// running on the latest spec. Implied when `forkSchedule` is given.
+    #[serde(default)]
+    pub pin_spec_to_block: bool,
+    /// The chain's fork activations; defaults to the mainnet schedule.
+    pub fork_schedule: Option<Vec<ReplayForkActivation>>,
+    /// `verified_block_header` from `verify_consensus_proof`. Its number,
+    /// timestamp, gas limit, base fee, prevrandao and fee recipient replace
+    /// the witness `replayBlock` values; only the remaining fields (blob
+    /// pricing, difficulty) still come from the witness.
+    pub verified_block_header: Option<VerifiedBlockHeader>,
+    /// Check the replay gas limit against `verifiedBlockHeader.gasLimit`, as
+    /// no transaction in that block could have had more. Unchecked when
+    /// unset or without a verified header.
+    pub proven_gas_limit_policy: Option<ProvenGasLimitPolicy>,
+    /// Code hashes proven under the consensus-verified state root, keyed by
+    /// address (e.g. the `codeHash` of an account accepted by
+    /// `verify_safe_account_proof`). A witness account listed here must
+    /// carry code hashing to the proven value.
+    #[serde(default)]
+    pub proven_code_hashes: BTreeMap<String, String>,
+    /// Compute the state root after the replay and require it to equal
+    /// `expectedPostStateRoot`. Only sound when the witness holds the whole
+    /// world state; a partial witness always mismatches. The caller's
+    /// synthetic gas top-up is taken back out first, so the root uses the
+    /// witness balance.
+    #[serde(default)]
+    pub verify_post_state_root: bool,
+    /// The simulation's own claim about the state after the transaction.
+    /// It lives here rather than on the consensus input because consensus
+    /// only proves the pre-state root the witness is checked against; no
+    /// block commits to the state right after a simulated transaction, so
+    /// this value is only as trustworthy as the simulation that produced it.
+    pub expected_post_state_root: Option<String>,
+}
+
+/// One fork activation: exactly one of `block` or `timestamp` must be set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayForkActivation {
+    /// revm hardfork name, e.g. `shanghai` or `cancun`.
+    pub fork: String,
+    pub block: Option<u64>,
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStateOverride {
+    pub address: String,
+    pub balance: Option<String>,
+    pub nonce: Option<u64>,
+    pub code: Option<String>,
+    #[serde(default)]
+    pub storage: BTreeMap<String, String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReplayTransaction {
     pub to: String,
@@ -39,7 +330,7 @@ pub struct ReplayTransaction {
     pub safe_tx_gas: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReplaySimulation {
     pub success: bool,
@@ -49,6 +340,74 @@ pub struct ReplaySimulation {
     pub block_number: u64,
     #[serde(default)]
     pub logs: Vec<ReplaySimulationLog>,
+    /// Inclusive tolerance band for replay gas. Replaces the at-most
+    /// `gasUsed` comparison when present.
+    pub expected_gas_range: Option<ReplayGasRange>,
+    /// 4-byte custom error selector a reverting replay must revert with.
+    /// Ignored when the replay succeeds.
+    pub expected_revert_selector: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayGasRange {
+    pub min: String,
+    pub max: String,
+}
+
+/// How replay gas is judged against the packaged simulation.
+enum GasPolicy {
+    /// Replay gas must not exceed the simulation's `gasUsed`.
+    AtMost(U256),
+    /// Replay gas must fall inside `expectedGasRange`, bounds included.
+    Within { min: U256, max: U256 },
+}
+
+impl GasPolicy {
+    fn admits(&self, gas_used: u64) -> bool {
+        let gas_used = U256::from(gas_used);
+        match self {
+            Self::AtMost(limit) => gas_used <= *limit,
+            Self::Within { min, max } => *min <= gas_used && gas_used <= *max,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::AtMost(limit) => limit.to_string(),
+            Self::Within { min, max } => format!("{min}..={max}"),
+        }
+    }
+
+    fn ceiling(&self) -> U256 {
+        match self {
+            Self::AtMost(limit) => *limit,
+            Self::Within { max, .. } => *max,
+        }
+    }
+
+    /// Signed distance from `gas_used` to the policy's ceiling; negative
+    /// when the replay overshot it.
+    fn headroom(&self, gas_used: u64) -> String {
+        let ceiling = self.ceiling();
+        let gas_used = U256::from(gas_used);
+        if ceiling >= gas_used {
+            (ceiling - gas_used).to_string()
+        } else {
+            format!("-{}", gas_used - ceiling)
+        }
+    }
+
+    fn mismatch_message(&self, gas_used: u64) -> String {
+        match self {
+            Self::AtMost(limit) => format!(
+                "Replay gas policy mismatch: replayGas={gas_used} exceeds simulationGas={limit}"
+            ),
+            Self::Within { min, max } => format!(
+                "Replay gas policy mismatch: replayGas={gas_used} is outside expectedGasRange [{min}, {max}]"
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -68,18 +427,162 @@ pub struct ReplayNativeTransfer {
     pub value: String,
 }
 
-#[derive(Debug, Deserialize)]
+/// The transaction handed to the EVM. `to` is `None` for a contract
+/// creation; `value` and `gasPrice` are wei.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayTxEnvelope {
+    pub caller: String,
+    pub to: Option<String>,
+    pub value: String,
+    pub data: String,
+    pub gas_limit: u64,
+    pub gas_price: String,
+    pub nonce: u64,
+    pub chain_id: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayNonceDiff {
+    pub address: String,
+    /// Seeded witness nonce; `0` for accounts the replay created.
+    pub before: u64,
+    pub after: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogComparisonMode {
+    /// Addresses, topics and data must match byte for byte.
+    #[default]
+    Strict,
+    /// Topics match exactly, while data compares as 32-byte ABI words, so
+    /// data that stops short of a word boundary matches its zero-padded
+    /// form. Whole trailing zero words are values and still compared.
+    Semantic,
+}
+
+/// How a replay gas limit above the proven block gas limit is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvenGasLimitPolicy {
+    /// Fail the replay.
+    Reject,
+    /// Run with the block gas limit instead.
+    Clamp,
+}
+
+/// Transactions replayed in order, each on the state the previous ones left.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReplaySequenceInput {
+    /// Every entry is a full replay input. Accounts an earlier transaction
+    /// touched keep their carried state; their witness entries only supply
+    /// storage slots no earlier transaction loaded.
+    pub transactions: Vec<SimulationReplayInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReplaySequenceResult {
+    /// Every transaction's replay matched its simulation.
+    pub success: bool,
+    /// One result per replayed transaction, in order. Replay stops at the
+    /// first transaction that did not execute, since later ones would run
+    /// on state missing its effects.
+    pub results: Vec<SimulationReplayVerificationResult>,
+    /// Index of the transaction the sequence stopped at; the transactions
+    /// after it were not replayed.
+    pub stopped_at: Option<usize>,
+    /// Net change over the whole sequence of every account a transaction
+    /// touched, sorted by address. Unchanged accounts are omitted.
+    pub state_diff: Vec<ReplayAccountStateDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayAccountStateDiff {
+    pub address: String,
+    pub balance_before: String,
+    pub balance_after: String,
+    pub nonce_before: u64,
+    pub nonce_after: u64,
+    /// Changed slots only, sorted by slot.
+    pub storage: Vec<ReplayStorageSlotDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStorageSlotDiff {
+    pub slot: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReplayWitness {
     pub replay_block: Option<ReplayBlock>,
     pub replay_accounts: Option<Vec<ReplayWitnessAccount>>,
     pub replay_caller: Option<String>,
+    /// Replay transaction gas limit. Without it a direct call uses a
+    /// non-zero `safeTxGas`, else the chain's default replay gas limit.
     pub replay_gas_limit: Option<u64>,
     pub replay_calldata: Option<String>,
     pub witness_only: Option<bool>,
+    /// Compact alternative to `replayAccounts`: a base prestate plus partial
+    /// overrides, expanded into full replay accounts before execution.
+    pub replay_prestate_diff: Option<ReplayPrestateDiff>,
+    /// EIP-4844 versioned hashes carried by the replayed transaction
+    /// (read by `BLOBHASH`). Empty for non-blob transactions.
+    #[serde(default)]
+    pub blob_hashes: Vec<String>,
+    /// EIP-2930 access list of the replayed transaction. Listed addresses and
+    /// slots start warm and each entry is charged intrinsic gas, as on chain.
+    #[serde(default)]
+    pub replay_access_list: Vec<ReplayAccessListEntry>,
+    /// Caller nonce to replay with, overriding the witness account's nonce.
+    /// Pins the address of a top-level CREATE.
+    pub replay_caller_nonce: Option<u64>,
+    /// Replay with a zero gas price (and no base fee check), so no fee is
+    /// debited from the caller or credited to the beneficiary and balances
+    /// move only by the transaction value and internal transfers.
+    #[serde(default)]
+    pub zero_gas_price: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayAccessListEntry {
+    pub address: String,
+    #[serde(default)]
+    pub storage_keys: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayPrestateDiff {
+    /// Prestate tracer output keyed by address. Omitted fields default to
+    /// zero balance/nonce and empty code, as the tracer omits zero values.
+    pub base: BTreeMap<String, ReplayPrestateAccount>,
+    /// Partial accounts applied on top of `base`; every override must target
+    /// an address present in `base`.
+    #[serde(default)]
+    pub overrides: BTreeMap<String, ReplayPrestateAccount>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayPrestateAccount {
+    pub balance: Option<String>,
+    pub nonce: Option<u64>,
+    pub code: Option<String>,
+    #[serde(default)]
+    pub storage: BTreeMap<String, String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReplayBlock {
     pub timestamp: String,
@@ -88,9 +591,13 @@ pub struct ReplayBlock {
     pub beneficiary: String,
     pub prev_randao: Option<String>,
     pub difficulty: Option<String>,
+    /// Blob base fee read by `BLOBBASEFEE`. Takes precedence over deriving
+    /// it from `excessBlobGas`.
+    pub blob_base_fee: Option<String>,
+    pub excess_blob_gas: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReplayWitnessAccount {
     pub address: String,
@@ -101,17 +608,302 @@ pub struct ReplayWitnessAccount {
     pub storage: BTreeMap<String, String>,
 }
 
+/// Bumped whenever the serialized shape of
+/// `SimulationReplayVerificationResult` changes. Fields each version added:
+/// 2 `storageOps`, 3 `gasHeadroom`, 4 `haltReason`, 5 `offline`,
+/// 6 `create2Deployments`, 7 `nativeDecimals`/`nativeSymbol`, 8 `specUsed`,
+/// 9 `inputErrors`, 10 `logsByAddress`, 11 `effectsSource`,
+/// 12 `postStateRoot`, 13 `overallStatus`, 14 `nonceDiffs`, 15 `gasProfile`,
+/// 16 `reentrancyDetected`, 17 `hint`, 18 `gasUnderrunWarning`,
+/// 19 `replayTxEnvelope`, 20 `preRevertLogs`, 21 `totalNativeValueMoved`,
+/// 22 `unexpectedRecipients`.
+pub const REPLAY_RESULT_SCHEMA_VERSION: u32 = 22;
+
+/// Serialized output is byte-identical across verifiers for the same input.
+/// Ordering contract: list-derived outputs (`replayLogs`,
+/// `replayNativeTransfers`, receipt logs) keep EVM emission order, and
+/// map-derived outputs are sorted by address, then storage slot. Witness
+/// account order never affects the result.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct SimulationReplayVerificationResult {
+    /// Result schema version; always serialized first.
+    pub schema_version: u32,
     pub executed: bool,
     pub success: bool,
-    pub reason: String,
+    pub reason: ReplayReason,
     pub error: Option<String>,
+    /// Actionable next step for the operator; informational only, `reason`
+    /// stays the machine-readable code.
+    pub hint: Option<String>,
     #[serde(rename = "replayLogs")]
     pub replay_logs: Option<Vec<ReplaySimulationLog>>,
+    /// `replayLogs` grouped by emitting address, each group in emission order.
+    pub logs_by_address: Option<BTreeMap<String, Vec<ReplaySimulationLog>>>,
     #[serde(rename = "replayNativeTransfers")]
     pub replay_native_transfers: Option<Vec<ReplayNativeTransfer>>,
+    /// Sum of `replayNativeTransfers` values in wei, self-transfers excluded.
+    /// The caller's synthetic gas top-up is seeded state, not a transfer.
+    pub total_native_value_moved: Option<String>,
+    /// Native value recipients absent from `expectedRecipients`, sorted and
+    /// deduplicated. Set only when `expectedRecipients` was given and the
+    /// replay executed.
+    pub unexpected_recipients: Option<Vec<String>>,
+    /// Per-field comparison between the replay and the packaged simulation.
+    /// Populated whenever the replay executed, independently of which single
+    /// `reason` was reported, so every divergent field is visible at once.
+    pub comparison: Option<ReplayComparison>,
+    /// Receipt-shaped view of the replay, mirroring `eth_getTransactionReceipt`.
+    pub replay_receipt: Option<ReplayReceipt>,
+    /// The replay ran against `stateOverrides`, not the packaged witness, so
+    /// it says nothing about the package and must never upgrade trust.
+    pub overridden: bool,
+    /// Index-by-index log differences, set only on the logs-mismatch path.
+    pub log_diff: Option<Vec<ReplayLogDiff>>,
+    /// Ordered SLOAD/SSTORE trace, set only when `traceStorageOps` is on.
+    pub storage_ops: Option<Vec<ReplayStorageOp>>,
+    /// Logs a reverted or halted replay emitted before failing, in emission
+    /// order. The revert discarded them: they are not part of any receipt
+    /// and are never compared. Set only under `capturePreRevertLogs`.
+    pub pre_revert_logs: Option<Vec<ReplaySimulationLog>>,
+    /// Expected gas ceiling minus replay gas, as a signed quantity string
+    /// (`-` prefixed when over budget); set whenever the replay executed against a parseable gas expectation.
+    pub gas_headroom: Option<String>,
+    /// Set when the replay used less than `gasUnderrunThresholdPercent` of
+    /// the expected gas ceiling. Advisory; `success` is unaffected.
+    pub gas_underrun_warning: Option<String>,
+    /// Why the EVM halted, set only when the replay halted rather than
+    /// returning or reverting.
+    pub halt_reason: Option<ReplayHaltReason>,
+    /// Gas charged per opcode category (`storage`, `calls`, `memory`,
+    /// `arithmetic`, `logs`, `other`), set only under `profileGas`. Gas a
+    /// call or create forwards is counted in the callee's opcodes; intrinsic
+    /// gas, precompile execution and refunds are not attributed.
+    pub gas_profile: Option<BTreeMap<String, u64>>,
+    /// Advisory: a contract was entered again while an outer frame of it was
+    /// still running. A contract calling itself directly is not counted.
+    pub reentrancy_detected: bool,
+    /// Built with the `offline-only` feature.
+    pub offline: bool,
+    /// CREATE2 deployments made by a successful replay, in execution order.
+    pub create2_deployments: Option<Vec<ReplayCreate2Deployment>>,
+    /// `nativeDecimals` / `nativeSymbol` from the input, for rendering
+    /// `replayNativeTransfers` values.
+    pub native_decimals: Option<u8>,
+    pub native_symbol: Option<String>,
+    /// Hardfork rules the replay ran under, e.g. `"cancun"`; set whenever
+    /// the replay executed, including when no spec was requested.
+    pub spec_used: Option<String>,
+    /// The transaction the replay ran, set only under `includeTxEnvelope`.
+    pub replay_tx_envelope: Option<ReplayTxEnvelope>,
+    /// Every malformed input field, set only with reason `invalid-input`.
+    pub input_errors: Option<Vec<String>>,
+    /// Where the reported logs and transfers come from; `replay` means they
+    /// were derived locally and are not packaged simulation data.
+    pub effects_source: Option<ReplayEffectsSource>,
+    /// State root after the replay, set only under `verifyPostStateRoot`.
+    pub post_state_root: Option<String>,
+    /// Accounts whose nonce the replay changed, sorted by address. Empty
+    /// when the replay reverted or halted.
+    pub nonce_diffs: Option<Vec<ReplayNonceDiff>>,
+    /// Graded view of `reason` for reviewers who treat gas as advisory;
+    /// `success` stays the strict verdict.
+    pub overall_status: ReplayOverallStatus,
+}
+
+/// Pass/fail view of a `SimulationReplayVerificationResult`, without the
+/// logs, transfers and diffs.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReplaySummary {
+    pub schema_version: u32,
+    pub executed: bool,
+    pub success: bool,
+    pub reason: ReplayReason,
+    pub overall_status: ReplayOverallStatus,
+    pub error: Option<String>,
+    /// Fields of `comparison` that matched, out of `comparedEffects`; both
+    /// set only when the replay executed.
+    pub matched_effects: Option<usize>,
+    pub compared_effects: Option<usize>,
+    pub overridden: bool,
+}
+
+impl From<&SimulationReplayVerificationResult> for SimulationReplaySummary {
+    fn from(result: &SimulationReplayVerificationResult) -> Self {
+        let effects = result.comparison.as_ref().map(|comparison| {
+            [
+                Some(comparison.success.matched),
+                Some(comparison.return_data.matched),
+                comparison.logs.as_ref().map(|logs| logs.matched),
+                Some(comparison.gas.matched),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+        });
+        Self {
+            schema_version: result.schema_version,
+            executed: result.executed,
+            success: result.success,
+            reason: result.reason,
+            overall_status: result.overall_status,
+            error: result.error.clone(),
+            matched_effects: effects
+                .as_ref()
+                .map(|effects| effects.iter().filter(|matched| **matched).count()),
+            compared_effects: effects.map(|effects| effects.len()),
+            overridden: result.overridden,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReplayOverallStatus {
+    Matched,
+    /// Success, return data, logs and every other effect check passed; only
+    /// the gas comparison failed. Gas is compared last, so `MismatchGas`
+    /// implies everything before it matched.
+    EffectsMatchedGasDiffers,
+    Mismatched,
+}
+
+impl From<ReplayReason> for ReplayOverallStatus {
+    fn from(reason: ReplayReason) -> Self {
+        match reason {
+            ReplayReason::Matched => Self::Matched,
+            ReplayReason::MismatchGas => Self::EffectsMatchedGasDiffers,
+            _ => Self::Mismatched,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplayEffectsSource {
+    /// Full simulation: the packaged effects were checked against the replay.
+    Packaged,
+    /// Witness-only: the package carries no effects, so they come from replay.
+    Replay,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayCreate2Deployment {
+    pub deployer: String,
+    pub salt: String,
+    pub init_code_hash: String,
+    pub address: String,
+}
+
+/// Stable names for revm halt reasons; variants revm may add later map to
+/// `Other` rather than changing the serialized set.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReplayHaltReason {
+    OutOfGas,
+    InvalidOpcode,
+    InvalidJump,
+    StackUnderflow,
+    StackOverflow,
+    OutOfOffset,
+    CreateCollision,
+    PrecompileError,
+    NonceOverflow,
+    CreateContractSizeLimit,
+    StaticCallViolation,
+    OutOfFunds,
+    CallTooDeep,
+    Other,
+}
+
+impl From<&HaltReason> for ReplayHaltReason {
+    fn from(reason: &HaltReason) -> Self {
+        match reason {
+            HaltReason::OutOfGas(_) => Self::OutOfGas,
+            HaltReason::OpcodeNotFound | HaltReason::InvalidFEOpcode | HaltReason::NotActivated => {
+                Self::InvalidOpcode
+            }
+            HaltReason::InvalidJump => Self::InvalidJump,
+            HaltReason::StackUnderflow => Self::StackUnderflow,
+            HaltReason::StackOverflow => Self::StackOverflow,
+            HaltReason::OutOfOffset => Self::OutOfOffset,
+            HaltReason::CreateCollision => Self::CreateCollision,
+            HaltReason::PrecompileError => Self::PrecompileError,
+            HaltReason::NonceOverflow => Self::NonceOverflow,
+            HaltReason::CreateContractSizeLimit => Self::CreateContractSizeLimit,
+            HaltReason::StateChangeDuringStaticCall | HaltReason::CallNotAllowedInsideStatic => {
+                Self::StaticCallViolation
+            }
+            HaltReason::OutOfFunds => Self::OutOfFunds,
+            HaltReason::CallTooDeep => Self::CallTooDeep,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStorageOp {
+    pub pc: usize,
+    /// `SLOAD` or `SSTORE`.
+    pub op: String,
+    pub address: String,
+    pub slot: String,
+    /// Value loaded or stored.
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayLogDiff {
+    pub index: usize,
+    pub kind: ReplayLogDiffKind,
+    pub replay: Option<ReplaySimulationLog>,
+    pub expected: Option<ReplaySimulationLog>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayLogDiffKind {
+    /// Emitted by the replay but absent from the packaged simulation.
+    Added,
+    /// Present in the packaged simulation but not emitted by the replay.
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReceipt {
+    /// 1 when the replay succeeded, 0 when it reverted or halted.
+    pub status: u8,
+    pub gas_used: String,
+    pub logs_bloom: String,
+    pub logs: Vec<ReplaySimulationLog>,
+    /// Address created by a top-level CREATE, if any.
+    pub contract_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayComparison {
+    pub success: ReplayFieldComparison<bool>,
+    pub return_data: ReplayFieldComparison<String>,
+    /// `None` in witness-only mode, where packaged logs are not compared.
+    pub logs: Option<ReplayFieldComparison<Vec<ReplaySimulationLog>>>,
+    /// Gas uses the at-most policy (replay gas must not exceed the
+    /// simulation) unless `expectedGasRange` is given, in which case
+    /// `expected` reads `min..=max`.
+    pub gas: ReplayFieldComparison<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFieldComparison<T> {
+    pub matched: bool,
+    pub replay: T,
+    pub expected: T,
 }
 
 #[derive(Debug)]
@@ -120,16 +912,152 @@ struct ReplayExecution {
     return_data: String,
     gas_used: u64,
     logs: Vec<ReplaySimulationLog>,
+    logs_bloom: Bloom,
+    created_address: Option<Address>,
     native_transfers: Vec<ReplayNativeTransfer>,
+    storage_ops: Option<Vec<ReplayStorageOp>>,
+    halt_reason: Option<ReplayHaltReason>,
+    create2_deployments: Vec<ReplayCreate2Deployment>,
+    /// Accounts execution touched that the witness did not seed; only
+    /// collected under `requireCompleteWitness`.
+    unseeded_accounts: Vec<Address>,
+    /// What execution touched, when access tracking was on.
+    access: Option<ReplayAccess>,
+    spec: SpecId,
+    /// Root over the witness accounts with the replay's changes applied;
+    /// computed only under `verifyPostStateRoot`.
+    post_state_root: Option<B256>,
+    nonce_diffs: Vec<ReplayNonceDiff>,
+    gas_profile: Option<BTreeMap<String, u64>>,
+    reentrancy_detected: bool,
+    tx_envelope: Option<ReplayTxEnvelope>,
+    pre_revert_logs: Option<Vec<ReplaySimulationLog>>,
 }
 
+/// Accounts and storage slots a replay touched.
 #[derive(Debug, Default)]
-struct NativeTransferInspector {
+struct ReplayAccess {
+    /// Call targets, code sources and `BALANCE`/`EXTCODE*`/`SELFDESTRUCT`
+    /// operands, minus accounts created during the replay.
+    accounts: BTreeSet<Address>,
+    /// `SLOAD`/`SSTORE` keys by storage owner.
+    slots: BTreeMap<Address, BTreeSet<U256>>,
+}
+
+/// Collects native transfers and, when enabled, the storage operation trace
+/// and the set of accounts execution touched.
+#[derive(Debug, Default)]
+struct ReplayInspector {
     frame_stack: Vec<Vec<ReplayNativeTransfer>>,
     finalized: Vec<ReplayNativeTransfer>,
+    storage_ops: Option<Vec<ReplayStorageOp>>,
+    /// SLOAD awaiting its loaded value from `step_end`.
+    pending_sload: Option<(usize, Address, U256)>,
+    create2_deployments: Vec<ReplayCreate2Deployment>,
+    access: Option<ReplayAccess>,
+    created_accounts: BTreeSet<Address>,
+    gas_profile: Option<BTreeMap<String, u64>>,
+    /// Opcode and gas remaining before it ran, awaiting `step_end`.
+    pending_gas: Option<(u8, u64)>,
+    /// Storage context of each active frame; `None` for frames that do not
+    /// switch it (DELEGATECALL, CALLCODE) and for creates.
+    call_contexts: Vec<Option<Address>>,
+    reentrancy_detected: bool,
+    /// `0x44` is `PREVRANDAO` from the merge on; before it, `DIFFICULTY`
+    /// reads the block's own field and needs no witness randomness.
+    post_merge: bool,
+    /// Execution ran `PREVRANDAO` (`0x44`) under a post-merge spec.
+    reads_prevrandao: bool,
+    /// Every log as emitted, including ones a revert later discards.
+    emitted_logs: Option<Vec<Log>>,
 }
 
-impl NativeTransferInspector {
+impl ReplayInspector {
+    fn new(
+        trace_storage_ops: bool,
+        track_access: bool,
+        profile_gas: bool,
+        capture_logs: bool,
+        spec: SpecId,
+    ) -> Self {
+        Self {
+            post_merge: spec.is_enabled_in(SpecId::MERGE),
+            storage_ops: trace_storage_ops.then(Vec::new),
+            access: track_access.then(ReplayAccess::default),
+            gas_profile: profile_gas.then(BTreeMap::new),
+            emitted_logs: capture_logs.then(Vec::new),
+            ..Default::default()
+        }
+    }
+
+    fn record_gas(&mut self, category: &str, gas: u64) {
+        if let Some(profile) = self.gas_profile.as_mut() {
+            *profile.entry(category.to_string()).or_default() += gas;
+        }
+    }
+
+    /// A call or create opcode's step is charged the gas it forwards; take
+    /// that back once the callee returns, since its own steps count it.
+    fn refund_forwarded_gas(&mut self, forwarded: u64) {
+        if self.frame_stack.is_empty() {
+            // The top-level frame's gas was never charged by a step.
+            return;
+        }
+        if let Some(calls) = self
+            .gas_profile
+            .as_mut()
+            .and_then(|profile| profile.get_mut("calls"))
+        {
+            *calls = calls.saturating_sub(forwarded);
+        }
+    }
+
+    fn record_access(&mut self, address: Address) {
+        if let Some(access) = self.access.as_mut() {
+            access.accounts.insert(address);
+        }
+    }
+
+    fn record_slot_access(&mut self, address: Address, slot: U256) {
+        if let Some(access) = self.access.as_mut() {
+            access.slots.entry(address).or_default().insert(slot);
+        }
+    }
+
+    fn record_storage_op(
+        &mut self,
+        pc: usize,
+        op: &str,
+        address: Address,
+        slot: U256,
+        value: U256,
+    ) {
+        if let Some(ops) = self.storage_ops.as_mut() {
+            ops.push(ReplayStorageOp {
+                pc,
+                op: op.to_string(),
+                address: format!("{address:#x}"),
+                slot: format!("{slot:#x}"),
+                value: format!("{value:#x}"),
+            });
+        }
+    }
+
+    /// Flags a frame for `address` while an outer frame of it is active,
+    /// other than its direct caller: A -> B -> A, or A -> A -> A. A single
+    /// direct self-call (the Safe's own owner and module management) is not
+    /// reentrancy.
+    fn enter_context(&mut self, address: Option<Address>) {
+        if let Some(address) = address {
+            let mut outer = self.call_contexts.iter().rev().flatten();
+            let caller = outer.next();
+            if caller.is_some() && outer.any(|frame| *frame == address) {
+                self.reentrancy_detected = true;
+            }
+        }
+        self.call_contexts.push(address);
+    }
+
     fn push_frame(&mut self) {
         self.frame_stack.push(Vec::new());
     }
@@ -150,22 +1078,111 @@ impl NativeTransferInspector {
         }
     }
 
-    fn into_transfers(self) -> Vec<ReplayNativeTransfer> {
-        self.finalized
+    fn into_parts(
+        self,
+    ) -> (
+        Vec<ReplayNativeTransfer>,
+        Option<Vec<ReplayStorageOp>>,
+        Vec<ReplayCreate2Deployment>,
+        Option<ReplayAccess>,
+    ) {
+        let created = self.created_accounts;
+        let access = self.access.map(|mut access| {
+            access.accounts.retain(|address| !created.contains(address));
+            access
+        });
+        (
+            self.finalized,
+            self.storage_ops,
+            self.create2_deployments,
+            access,
+        )
+    }
+}
+
+/// Gas added to a value-transferring call's callee for free.
+const CALL_STIPEND: u64 = 2_300;
+
+fn gas_category(opcode: u8) -> &'static str {
+    match opcode {
+        SLOAD | SSTORE | TLOAD | TSTORE => "storage",
+        CALL | CALLCODE | DELEGATECALL | STATICCALL | CREATE | CREATE2 | SELFDESTRUCT => "calls",
+        MLOAD | MSTORE | MSTORE8 | MSIZE | MCOPY | CALLDATACOPY | CODECOPY | RETURNDATACOPY => {
+            "memory"
+        }
+        ADD..=SIGNEXTEND | LT..=SAR => "arithmetic",
+        LOG0..=LOG4 => "logs",
+        _ => "other",
     }
 }
 
-impl<CTX, INTR> Inspector<CTX, INTR> for NativeTransferInspector
-where
-    INTR: revm::interpreter::InterpreterTypes,
-{
-    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+impl<CTX> Inspector<CTX, EthInterpreter> for ReplayInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let opcode = interp.bytecode.opcode();
+        let address = interp.input.target_address;
+        if opcode == DIFFICULTY && self.post_merge {
+            self.reads_prevrandao = true;
+        }
+        if self.gas_profile.is_some() {
+            self.pending_gas = Some((opcode, interp.gas.remaining()));
+        }
+        if self.access.is_some() {
+            match (opcode, interp.stack.data().last()) {
+                (
+                    BALANCE | EXTCODESIZE | EXTCODECOPY | EXTCODEHASH | SELFDESTRUCT,
+                    Some(operand),
+                ) => self.record_access(Address::from_word(B256::from(*operand))),
+                (SLOAD | SSTORE, Some(slot)) => self.record_slot_access(address, *slot),
+                _ => {}
+            }
+        }
+        if self.storage_ops.is_none() {
+            return;
+        }
+        let pc = interp.bytecode.pc();
+        match (opcode, interp.stack.data().as_slice()) {
+            (SSTORE, [.., value, slot]) => {
+                let (slot, value) = (*slot, *value);
+                self.record_storage_op(pc, "SSTORE", address, slot, value);
+            }
+            (SLOAD, [.., slot]) => self.pending_sload = Some((pc, address, *slot)),
+            _ => {}
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        if let Some((opcode, before)) = self.pending_gas.take() {
+            let spent = before.saturating_sub(interp.gas.remaining());
+            self.record_gas(gas_category(opcode), spent);
+        }
+        if let Some((pc, address, slot)) = self.pending_sload.take() {
+            if let Some(value) = interp.stack.data().last().copied() {
+                self.record_storage_op(pc, "SLOAD", address, slot, value);
+            }
+        }
+    }
+
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.record_access(inputs.target_address);
+        self.record_access(inputs.bytecode_address);
+        self.enter_context(match inputs.scheme {
+            CallScheme::Call | CallScheme::StaticCall => Some(inputs.target_address),
+            CallScheme::DelegateCall | CallScheme::CallCode => None,
+        });
         self.push_frame();
         None
     }
 
     fn call_end(&mut self, _context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
         let mut frame_transfers = self.frame_stack.pop().unwrap_or_default();
+        self.call_contexts.pop();
+        // The value-transfer stipend is added to the callee's gas without
+        // being charged to the caller.
+        let stipend = match inputs.transfer_value() {
+            Some(value) if value > U256::ZERO => CALL_STIPEND,
+            _ => 0,
+        };
+        self.refund_forwarded_gas(inputs.gas_limit.saturating_sub(stipend));
 
         if outcome.instruction_result().is_ok() {
             if let Some(value) = inputs.transfer_value() {
@@ -185,6 +1202,7 @@ where
     }
 
     fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.enter_context(None);
         self.push_frame();
         None
     }
@@ -196,8 +1214,23 @@ where
         outcome: &mut CreateOutcome,
     ) {
         let mut frame_transfers = self.frame_stack.pop().unwrap_or_default();
+        self.call_contexts.pop();
+        self.refund_forwarded_gas(inputs.gas_limit());
 
+        if let Some(created) = outcome.address {
+            self.created_accounts.insert(created);
+        }
         if outcome.instruction_result().is_ok() {
+            if let (CreateScheme::Create2 { salt }, Some(created)) =
+                (inputs.scheme(), outcome.address)
+            {
+                self.create2_deployments.push(ReplayCreate2Deployment {
+                    deployer: format!("{:#x}", inputs.caller()),
+                    salt: format!("{:#x}", B256::from(salt)),
+                    init_code_hash: format!("{:#x}", keccak256(inputs.init_code())),
+                    address: format!("{created:#x}"),
+                });
+            }
             let value = inputs.value();
             if value > U256::ZERO {
                 if let Some(created) = outcome.address {
@@ -215,6 +1248,12 @@ where
         }
     }
 
+    fn log(&mut self, _context: &mut CTX, log: Log) {
+        if let Some(logs) = self.emitted_logs.as_mut() {
+            logs.push(log);
+        }
+    }
+
     fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
         if value > U256::ZERO {
             self.push_transfer(ReplayNativeTransfer {
@@ -229,133 +1268,854 @@ where
 pub fn verify_simulation_replay(
     input: SimulationReplayInput,
 ) -> SimulationReplayVerificationResult {
-    let Some(accounts) = input.simulation_witness.replay_accounts.as_ref() else {
-        return SimulationReplayVerificationResult {
-            executed: false,
-            success: false,
-            reason: REASON_WITNESS_INCOMPLETE.to_string(),
-            error: Some(
-                "simulationWitness.replayAccounts is missing; witness is incomplete for local replay."
-                    .to_string(),
-            ),
-            replay_logs: None,
-            replay_native_transfers: None,
-        };
-    };
+    verify_replay_with(input, None)
+}
 
-    let replay = match execute_replay(&input, accounts) {
-        Ok(value) => value,
-        Err(error) => {
-            return SimulationReplayVerificationResult {
-                executed: true,
-                success: false,
-                reason: REASON_REPLAY_EXEC_ERROR.to_string(),
-                error: Some(error),
-                replay_logs: None,
-                replay_native_transfers: None,
-            };
+pub fn verify_simulation_replay_sequence(
+    input: SimulationReplaySequenceInput,
+) -> SimulationReplaySequenceResult {
+    let mut sequence = ReplaySequenceState::default();
+    let mut results = Vec::new();
+    let mut stopped_at = None;
+    for (index, transaction) in input.transactions.into_iter().enumerate() {
+        let result = verify_replay_with(transaction, Some(&mut sequence));
+        let executed = result.executed;
+        results.push(result);
+        if !executed {
+            stopped_at = Some(index);
+            break;
+        }
+    }
+    SimulationReplaySequenceResult {
+        success: stopped_at.is_none()
+            && !results.is_empty()
+            && results.iter().all(|result| result.success),
+        results,
+        stopped_at,
+        state_diff: sequence.state_diff(),
+    }
+}
+
+fn verify_replay_with(
+    input: SimulationReplayInput,
+    sequence: Option<&mut ReplaySequenceState>,
+) -> SimulationReplayVerificationResult {
+    let mut result = replay_and_compare(&input, sequence);
+    result.overridden = !input.state_overrides.is_empty();
+    result.native_decimals = input.native_decimals;
+    result.native_symbol = input.native_symbol.clone();
+    result.unexpected_recipients = input
+        .expected_recipients
+        .as_deref()
+        .zip(result.replay_native_transfers.as_deref())
+        .map(|(expected, transfers)| unexpected_recipients(expected, transfers));
+    result.effects_source = Some(if input.simulation_witness.witness_only.unwrap_or(false) {
+        ReplayEffectsSource::Replay
+    } else {
+        ReplayEffectsSource::Packaged
+    });
+    result.hint = replay_hint(&input, result.reason).map(str::to_string);
+    apply_output_number_format(&mut result, input.output_number_format);
+    result
+}
+
+/// Stable remediation text per reason; DELEGATECALL transactions get their
+/// own, since they fail before execution with a generic exec error.
+fn replay_hint(input: &SimulationReplayInput, reason: ReplayReason) -> Option<&'static str> {
+    Some(match reason {
+        ReplayReason::Matched => return None,
+        ReplayReason::ExecError if input.transaction.operation == 1 => {
+            "DELEGATECALL transactions cannot be replayed locally; review the delegate target's code and the simulation manually."
+        }
+        ReplayReason::InvalidInput => "Fix the fields listed in inputErrors and verify again.",
+        ReplayReason::ExecError => {
+            "Check the witness block context, caller and chain id; the local EVM could not run the transaction."
+        }
+        ReplayReason::MismatchSuccess
+        | ReplayReason::MismatchReturnData
+        | ReplayReason::MismatchLogs => {
+            "Compare the fields in comparison; the simulation likely ran against different state than the witness."
+        }
+        ReplayReason::MismatchGas => {
+            "Effects may still match; check overallStatus and gasHeadroom before trusting the gas expectation."
+        }
+        ReplayReason::MismatchCreate2 => {
+            "Compare create2Deployments with expectedCreate2Addresses; the init code or salt differs."
+        }
+        ReplayReason::MismatchPostStateRoot => {
+            "Verify the post-state root only with a witness holding the complete world state."
+        }
+        ReplayReason::WitnessIncomplete => {
+            "Regenerate the package with the prestate tracer enabled so the witness covers every touched account."
+        }
+        ReplayReason::WitnessOnlyLogsPresent => {
+            "Regenerate the package; witness-only packages must not include simulation logs."
+        }
+    })
+}
+
+/// Byte-stable JSON for `result`: object keys sorted at every level and
+/// `0x` strings lowercased, so equal results hash and diff identically
+/// regardless of map iteration order or hex casing.
+pub fn canonical_json(result: &SimulationReplayVerificationResult) -> Result<String, String> {
+    let value = serde_json::to_value(result)
+        .map_err(|err| format!("failed to serialize replay result: {err}"))?;
+    let mut out = String::new();
+    write_sorted_json(&crate::replay_input_diff::canonicalize(value), &mut out);
+    Ok(out)
+}
+
+fn write_sorted_json(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_sorted_json(item, out);
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (index, key) in keys.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_sorted_json(&fields[key], out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Read against the same ceiling the gas comparison used.
+fn replay_gas_underrun(gas_used: u64, ceiling: U256, threshold_percent: u8) -> Option<String> {
+    let used_percent = U256::from(gas_used) * U256::from(100);
+    if ceiling.is_zero() || used_percent >= ceiling * U256::from(threshold_percent) {
+        return None;
+    }
+    Some(format!(
+        "Replay used {gas_used} gas, {}% of the expected {ceiling}, below the {threshold_percent}% underrun threshold.",
+        used_percent / ceiling
+    ))
+}
+
+fn replay_and_compare(
+    input: &SimulationReplayInput,
+    sequence: Option<&mut ReplaySequenceState>,
+) -> SimulationReplayVerificationResult {
+    let input_errors = validate_replay_input(input);
+    if !input_errors.is_empty() {
+        let mut result = failed_result(
+            false,
+            ReplayReason::InvalidInput,
+            format!(
+                "{} malformed input field(s): {}",
+                input_errors.len(),
+                input_errors.join("; ")
+            ),
+        );
+        result.input_errors = Some(input_errors);
+        return result;
+    }
+
+    if let Err(error) = validate_replay_chain_id(input) {
+        return failed_result(false, ReplayReason::ExecError, error);
+    }
+
+    // Witness-only packages strip simulation logs and recover them from
+    // replay; logs alongside the flag mean the package is malformed.
+    if input.simulation_witness.witness_only == Some(true) && !input.simulation.logs.is_empty() {
+        return failed_result(
+            false,
+            ReplayReason::WitnessOnlyLogsPresent,
+            format!(
+                "simulationWitness.witnessOnly is set but simulation.logs carries {} log(s); witness-only packages must not include simulation logs.",
+                input.simulation.logs.len()
+            ),
+        );
+    }
+
+    let mut accounts = match resolve_witness_accounts(&input.simulation_witness) {
+        Ok(Some(accounts)) => accounts,
+        Ok(None) => {
+            return failed_result(
+                false,
+                ReplayReason::WitnessIncomplete,
+                "simulationWitness.replayAccounts is missing; witness is incomplete for local replay."
+                    .to_string(),
+            );
+        }
+        Err(error) => return failed_result(false, ReplayReason::ExecError, error),
+    };
+
+    if let Err(error) = ensure_witness_within_limits(input, &accounts) {
+        return failed_result(false, ReplayReason::WitnessIncomplete, error);
+    }
+
+    if let Err(error) = ensure_unique_witness_accounts(&accounts) {
+        return failed_result(false, ReplayReason::ExecError, error);
+    }
+
+    if let Err(error) = ensure_witness_code_proven(input, &accounts) {
+        return failed_result(false, ReplayReason::ExecError, error);
+    }
+
+    if input.require_caller_in_witness {
+        if let Err(error) = ensure_caller_in_witness(input, &accounts) {
+            return failed_result(false, ReplayReason::WitnessIncomplete, error);
         }
-    };
-
-    let expected_return_data =
-        normalize_hex(input.simulation.return_data.as_deref().unwrap_or("0x"));
-    if replay.success != input.simulation.success {
-        return SimulationReplayVerificationResult {
-            executed: true,
-            success: false,
-            reason: REASON_REPLAY_MISMATCH_SUCCESS.to_string(),
-            error: Some(format!(
-                "Replay success mismatch: replay={}, simulation={}",
-                replay.success, input.simulation.success
-            )),
-            replay_logs: Some(replay.logs.clone()),
-            replay_native_transfers: Some(replay.native_transfers.clone()),
-        };
     }
 
+    if !input.state_overrides.is_empty() {
+        if let Err(error) = apply_state_overrides(accounts.to_mut(), &input.state_overrides) {
+            return failed_result(false, ReplayReason::ExecError, error);
+        }
+    }
+
+    if input.require_target_code {
+        if let Err(error) = ensure_target_has_code(input, &accounts) {
+            return failed_result(false, ReplayReason::WitnessIncomplete, error);
+        }
+    }
+
+    let replay = match execute_replay(input, &accounts, input.require_complete_witness, sequence) {
+        Ok(value) => value,
+        Err(error) => return failed_result(true, ReplayReason::ExecError, error),
+    };
+
+    let mut result = compare_executed_replay(input, &replay);
+    if let Ok(policy) = resolve_gas_policy(&input.simulation) {
+        result.gas_headroom = Some(policy.headroom(replay.gas_used));
+        result.gas_underrun_warning = input.gas_underrun_threshold_percent.and_then(|threshold| {
+            replay_gas_underrun(replay.gas_used, policy.ceiling(), threshold)
+        });
+    }
+    result
+}
+
+/// Every branch here ran the replay, so each one goes through
+/// `executed_result` and carries the replay's logs and transfers.
+fn compare_executed_replay(
+    input: &SimulationReplayInput,
+    replay: &ReplayExecution,
+) -> SimulationReplayVerificationResult {
     let witness_only = input.simulation_witness.witness_only.unwrap_or(false);
-    if replay.return_data != expected_return_data {
-        return SimulationReplayVerificationResult {
-            executed: true,
-            success: false,
-            reason: REASON_REPLAY_MISMATCH_RETURN_DATA.to_string(),
-            error: Some(format!(
+    let comparison = compare_replay(input, replay, witness_only);
+
+    if !replay.unseeded_accounts.is_empty() {
+        let missing = replay
+            .unseeded_accounts
+            .iter()
+            .map(|address| format!("{address:#x}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return executed_result(
+            replay,
+            comparison,
+            ReplayReason::WitnessIncomplete,
+            Some(format!(
+                "Replay touched account(s) missing from simulationWitness.replayAccounts: {missing}."
+            )),
+        );
+    }
+
+    if !comparison.success.matched {
+        return executed_result(
+            replay,
+            comparison.clone(),
+            ReplayReason::MismatchSuccess,
+            Some(success_mismatch_error(
+                replay.success,
+                input.simulation.success,
+                &replay.return_data,
+            )),
+        );
+    }
+
+    if !comparison.return_data.matched {
+        return executed_result(
+            replay,
+            comparison.clone(),
+            ReplayReason::MismatchReturnData,
+            Some(format!(
                 "Replay returnData mismatch: replay={}, simulation={}",
-                replay.return_data, expected_return_data
+                comparison.return_data.replay, comparison.return_data.expected
             )),
-            replay_logs: Some(replay.logs.clone()),
-            replay_native_transfers: Some(replay.native_transfers.clone()),
-        };
+        );
     }
 
-    if !witness_only {
-        let expected_logs = normalize_simulation_logs(&input.simulation.logs);
-        let replay_logs = normalize_simulation_logs(&replay.logs);
-        if replay_logs != expected_logs {
-            return SimulationReplayVerificationResult {
-                executed: true,
-                success: false,
-                reason: REASON_REPLAY_MISMATCH_LOGS.to_string(),
-                error: Some("Replay logs mismatch against packaged simulation logs.".to_string()),
-                replay_logs: Some(replay.logs.clone()),
-                replay_native_transfers: Some(replay.native_transfers.clone()),
-            };
+    if let Err(error) = check_revert_selector(&input.simulation, replay) {
+        return executed_result(
+            replay,
+            comparison,
+            ReplayReason::MismatchReturnData,
+            Some(error),
+        );
+    }
+
+    if let Some(logs) = comparison.logs.as_ref().filter(|logs| !logs.matched) {
+        let log_diff = diff_logs(&logs.replay, &logs.expected);
+        let mut result = executed_result(
+            replay,
+            comparison,
+            ReplayReason::MismatchLogs,
+            Some("Replay logs mismatch against packaged simulation logs.".to_string()),
+        );
+        result.log_diff = Some(log_diff);
+        return result;
+    }
+
+    if let Some(expected) = input.expected_create2_addresses.as_deref() {
+        if let Err(error) = check_create2_deployments(expected, &replay.create2_deployments) {
+            return executed_result(
+                replay,
+                comparison,
+                ReplayReason::MismatchCreate2,
+                Some(error),
+            );
+        }
+    }
+
+    if input.verify_post_state_root {
+        if let Err(error) = check_post_state_root(input, replay.post_state_root) {
+            return executed_result(
+                replay,
+                comparison,
+                ReplayReason::MismatchPostStateRoot,
+                Some(error),
+            );
         }
     }
 
-    let expected_gas_used = match parse_u256(&input.simulation.gas_used) {
-        Ok(v) => v,
+    let gas_policy = match resolve_gas_policy(&input.simulation) {
+        Ok(policy) => policy,
         Err(err) => {
-            return SimulationReplayVerificationResult {
-                executed: true,
-                success: false,
-                reason: REASON_REPLAY_EXEC_ERROR.to_string(),
-                error: Some(format!("Invalid simulation.gasUsed: {err}")),
-                replay_logs: Some(replay.logs.clone()),
-                replay_native_transfers: Some(replay.native_transfers.clone()),
-            };
+            return executed_result(replay, comparison, ReplayReason::ExecError, Some(err));
         }
     };
 
-    if U256::from(replay.gas_used) > expected_gas_used {
-        return SimulationReplayVerificationResult {
-            executed: true,
-            success: false,
-            reason: REASON_REPLAY_MISMATCH_GAS.to_string(),
-            error: Some(format!(
-                "Replay gas policy mismatch: replayGas={} exceeds simulationGas={}",
-                replay.gas_used, expected_gas_used
-            )),
-            replay_logs: Some(replay.logs.clone()),
-            replay_native_transfers: Some(replay.native_transfers.clone()),
-        };
+    if !comparison.gas.matched {
+        return executed_result(
+            replay,
+            comparison,
+            ReplayReason::MismatchGas,
+            Some(gas_policy.mismatch_message(replay.gas_used)),
+        );
+    }
+
+    executed_result(replay, comparison, ReplayReason::Matched, None)
+}
+
+fn check_post_state_root(
+    input: &SimulationReplayInput,
+    computed: Option<B256>,
+) -> Result<(), String> {
+    let expected = parse_b256(
+        input
+            .expected_post_state_root
+            .as_deref()
+            .unwrap_or_default(),
+        "expectedPostStateRoot",
+    )?;
+    let computed = computed.ok_or("replay did not compute a post-state root")?;
+    if computed == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "Replay post-state root {computed:#x} does not match expectedPostStateRoot {expected:#x}."
+        ))
+    }
+}
+
+/// Carries the replay's output, and the decoded reason when it reverted,
+/// since that usually explains the divergence.
+fn success_mismatch_error(
+    replay_success: bool,
+    simulation_success: bool,
+    return_data: &str,
+) -> String {
+    let mut error = format!(
+        "Replay success mismatch: replay={replay_success}, simulation={simulation_success}; replay returnData={return_data}"
+    );
+    if !replay_success {
+        if let Some(reason) = parse_hex_bytes(return_data, "replay return data")
+            .ok()
+            .and_then(|output| decode_revert_reason(&output))
+        {
+            error.push_str(&format!("; revert reason: {reason}"));
+        }
+    }
+    error
+}
+
+/// `Error(string)` and `Panic(uint256)` revert payloads, as Solidity emits
+/// them. Custom errors are left to `expectedRevertSelector`.
+fn decode_revert_reason(output: &[u8]) -> Option<String> {
+    const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+    const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+    let (selector, args) = output.split_first_chunk::<4>()?;
+    let word = |index: usize| {
+        args.get(index * 32..(index + 1) * 32)
+            .map(U256::from_be_slice)
+    };
+    match *selector {
+        ERROR_SELECTOR => {
+            let offset = usize::try_from(word(0)?).ok()?;
+            let length_word = args.get(offset..offset.checked_add(32)?)?;
+            let length = usize::try_from(U256::from_be_slice(length_word)).ok()?;
+            let start = offset + 32;
+            let message = args.get(start..start.checked_add(length)?)?;
+            Some(format!("Error({:?})", String::from_utf8_lossy(message)))
+        }
+        PANIC_SELECTOR => Some(format!("Panic({:#x})", word(0)?)),
+        _ => None,
     }
+}
+
+/// A reverting replay must revert with `expectedRevertSelector`, if set.
+fn check_revert_selector(
+    simulation: &ReplaySimulation,
+    replay: &ReplayExecution,
+) -> Result<(), String> {
+    let Some(raw) = simulation.expected_revert_selector.as_deref() else {
+        return Ok(());
+    };
+    if replay.success {
+        return Ok(());
+    }
+    let expected = parse_revert_selector(raw)?;
+    let return_data = parse_hex_bytes(&replay.return_data, "replay return data")?;
+    match return_data.get(..4) {
+        Some(selector) if selector == expected.as_slice() => Ok(()),
+        Some(selector) => Err(format!(
+            "Replay reverted with selector 0x{} but simulation.expectedRevertSelector is {}.",
+            hex::encode(selector),
+            normalize_hex(raw)
+        )),
+        None => Err(format!(
+            "Replay reverted without a selector but simulation.expectedRevertSelector is {}.",
+            normalize_hex(raw)
+        )),
+    }
+}
+
+fn parse_revert_selector(raw: &str) -> Result<[u8; 4], String> {
+    let bytes = parse_hex_bytes(raw, "simulation.expectedRevertSelector")?;
+    <[u8; 4]>::try_from(bytes.as_ref()).map_err(|_| {
+        format!(
+            "invalid simulation.expectedRevertSelector ({raw}): expected 4 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Shape-check the package fields up front so every malformed one is reported
+/// at once. Witness accounts are checked where they are seeded, so their
+/// errors keep naming the account.
+fn validate_replay_input(input: &SimulationReplayInput) -> Vec<String> {
+    let transaction = &input.transaction;
+    let witness = &input.simulation_witness;
+    let mut checks = vec![
+        parse_address(&input.safe_address, "safeAddress").map(drop),
+        parse_transaction_to(&transaction.to).map(drop),
+        parse_hex_quantity(&transaction.value, "transaction.value").map(drop),
+    ];
+    if let Some(data) = transaction.data.as_deref() {
+        checks.push(parse_hex_bytes(data, "transaction.data").map(drop));
+    }
+    if let Some(safe_tx_gas) = transaction.safe_tx_gas.as_deref() {
+        checks.push(parse_hex_quantity(safe_tx_gas, "transaction.safeTxGas").map(drop));
+    }
+    if transaction.operation > 1 {
+        checks.push(Err(format!(
+            "invalid transaction.operation: expected 0 (CALL) or 1 (DELEGATECALL), got {}",
+            transaction.operation
+        )));
+    }
+    if input.simulation.expected_gas_range.is_none() {
+        checks.push(parse_hex_quantity(&input.simulation.gas_used, "simulation.gasUsed").map(drop));
+    }
+    if let Some(return_data) = input.simulation.return_data.as_deref() {
+        checks.push(parse_hex_bytes(return_data, "simulation.returnData").map(drop));
+    }
+    if let Some(selector) = input.simulation.expected_revert_selector.as_deref() {
+        checks.push(parse_revert_selector(selector).map(drop));
+    }
+    if let Some(caller) = witness.replay_caller.as_deref() {
+        checks.push(parse_address(caller, "simulationWitness.replayCaller").map(drop));
+    }
+    if let Some(calldata) = witness.replay_calldata.as_deref() {
+        checks.push(parse_hex_bytes(calldata, "simulationWitness.replayCalldata").map(drop));
+    }
+    match input.expected_post_state_root.as_deref() {
+        Some(root) => checks.push(parse_b256(root, "expectedPostStateRoot").map(drop)),
+        None if input.verify_post_state_root => checks.push(Err(
+            "expectedPostStateRoot is required when verifyPostStateRoot is set".to_string(),
+        )),
+        None => {}
+    }
+    for (index, hash) in witness.blob_hashes.iter().enumerate() {
+        checks.push(parse_b256(hash, &format!("simulationWitness.blobHashes[{index}]")).map(drop));
+    }
+    checks.extend(
+        witness
+            .replay_access_list
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                parse_access_list_entry(
+                    entry,
+                    &format!("simulationWitness.replayAccessList[{index}]"),
+                )
+                .map(drop)
+            }),
+    );
+    for (index, address) in input
+        .expected_create2_addresses
+        .iter()
+        .flatten()
+        .enumerate()
+    {
+        checks
+            .push(parse_address(address, &format!("expectedCreate2Addresses[{index}]")).map(drop));
+    }
+    checks.into_iter().filter_map(Result::err).collect()
+}
 
+fn failed_result(
+    executed: bool,
+    reason: ReplayReason,
+    error: String,
+) -> SimulationReplayVerificationResult {
+    SimulationReplayVerificationResult {
+        schema_version: REPLAY_RESULT_SCHEMA_VERSION,
+        executed,
+        success: false,
+        overall_status: reason.into(),
+        reason,
+        error: Some(error),
+        hint: None,
+        replay_logs: None,
+        logs_by_address: None,
+        replay_native_transfers: None,
+        total_native_value_moved: None,
+        unexpected_recipients: None,
+        comparison: None,
+        replay_receipt: None,
+        overridden: false,
+        log_diff: None,
+        storage_ops: None,
+        pre_revert_logs: None,
+        gas_headroom: None,
+        gas_underrun_warning: None,
+        halt_reason: None,
+        gas_profile: None,
+        reentrancy_detected: false,
+        offline: OFFLINE_ONLY,
+        create2_deployments: None,
+        native_decimals: None,
+        native_symbol: None,
+        spec_used: None,
+        replay_tx_envelope: None,
+        input_errors: None,
+        effects_source: None,
+        post_state_root: None,
+        nonce_diffs: None,
+    }
+}
+
+fn executed_result(
+    replay: &ReplayExecution,
+    comparison: ReplayComparison,
+    reason: ReplayReason,
+    error: Option<String>,
+) -> SimulationReplayVerificationResult {
     SimulationReplayVerificationResult {
+        schema_version: REPLAY_RESULT_SCHEMA_VERSION,
         executed: true,
-        success: true,
-        reason: REASON_REPLAY_MATCHED.to_string(),
-        error: None,
-        replay_logs: Some(replay.logs),
-        replay_native_transfers: Some(replay.native_transfers),
+        success: reason == ReplayReason::Matched,
+        overall_status: reason.into(),
+        reason,
+        error,
+        hint: None,
+        replay_logs: Some(replay.logs.clone()),
+        logs_by_address: Some(group_logs_by_address(&replay.logs)),
+        replay_native_transfers: Some(replay.native_transfers.clone()),
+        total_native_value_moved: Some(total_native_value(&replay.native_transfers).to_string()),
+        unexpected_recipients: None,
+        comparison: Some(comparison),
+        replay_receipt: Some(ReplayReceipt {
+            status: u8::from(replay.success),
+            gas_used: replay.gas_used.to_string(),
+            logs_bloom: format!("{:#x}", replay.logs_bloom),
+            logs: replay.logs.clone(),
+            contract_address: replay
+                .created_address
+                .map(|address| format!("{address:#x}")),
+        }),
+        overridden: false,
+        log_diff: None,
+        storage_ops: replay.storage_ops.clone(),
+        pre_revert_logs: replay.pre_revert_logs.clone(),
+        gas_headroom: None,
+        gas_underrun_warning: None,
+        halt_reason: replay.halt_reason,
+        gas_profile: replay.gas_profile.clone(),
+        reentrancy_detected: replay.reentrancy_detected,
+        offline: OFFLINE_ONLY,
+        create2_deployments: Some(replay.create2_deployments.clone()),
+        native_decimals: None,
+        native_symbol: None,
+        spec_used: Some(spec_name(replay.spec).to_string()),
+        replay_tx_envelope: replay.tx_envelope.clone(),
+        input_errors: None,
+        effects_source: None,
+        post_state_root: replay.post_state_root.map(|root| format!("{root:#x}")),
+        nonce_diffs: Some(replay.nonce_diffs.clone()),
+    }
+}
+
+/// Each settled frame records its own transfer once, and reverted frames
+/// record none, so summing the list counts every movement exactly once.
+fn total_native_value(transfers: &[ReplayNativeTransfer]) -> U256 {
+    transfers
+        .iter()
+        .filter(|transfer| transfer.from != transfer.to)
+        .filter_map(|transfer| U256::from_str_radix(&transfer.value, 10).ok())
+        .fold(U256::ZERO, |total, value| total.saturating_add(value))
+}
+
+/// Self-transfers move nothing, so their address is not a recipient.
+fn unexpected_recipients(expected: &[String], transfers: &[ReplayNativeTransfer]) -> Vec<String> {
+    let expected: BTreeSet<String> = expected
+        .iter()
+        .map(|address| normalize_address(address.trim()))
+        .collect();
+    transfers
+        .iter()
+        .filter(|transfer| transfer.from != transfer.to)
+        .map(|transfer| normalize_address(&transfer.to))
+        .filter(|recipient| !expected.contains(recipient))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn group_logs_by_address(
+    logs: &[ReplaySimulationLog],
+) -> BTreeMap<String, Vec<ReplaySimulationLog>> {
+    let mut groups: BTreeMap<String, Vec<ReplaySimulationLog>> = BTreeMap::new();
+    for log in logs {
+        groups
+            .entry(normalize_address(&log.address))
+            .or_default()
+            .push(log.clone());
+    }
+    groups
+}
+
+fn apply_output_number_format(
+    result: &mut SimulationReplayVerificationResult,
+    format: OutputNumberFormat,
+) {
+    if format == OutputNumberFormat::Decimal {
+        return;
+    }
+    if let Some(transfers) = result.replay_native_transfers.as_mut() {
+        for transfer in transfers {
+            transfer.value = format.reformat(&transfer.value);
+        }
+    }
+    if let Some(total) = result.total_native_value_moved.as_mut() {
+        *total = format.reformat(total);
+    }
+    if let Some(headroom) = result.gas_headroom.as_mut() {
+        *headroom = format.reformat(headroom);
+    }
+    if let Some(comparison) = result.comparison.as_mut() {
+        comparison.gas.replay = format.reformat(&comparison.gas.replay);
+        comparison.gas.expected = format.reformat(&comparison.gas.expected);
+    }
+    if let Some(receipt) = result.replay_receipt.as_mut() {
+        receipt.gas_used = format.reformat(&receipt.gas_used);
+    }
+    if let Some(envelope) = result.replay_tx_envelope.as_mut() {
+        envelope.value = format.reformat(&envelope.value);
+        envelope.gas_price = format.reformat(&envelope.gas_price);
+    }
+}
+
+/// Pair replay and packaged logs by position and report every index where
+/// they differ.
+fn diff_logs(
+    replay: &[ReplaySimulationLog],
+    expected: &[ReplaySimulationLog],
+) -> Vec<ReplayLogDiff> {
+    (0..replay.len().max(expected.len()))
+        .filter_map(|index| {
+            let (replay_log, expected_log) = (replay.get(index), expected.get(index));
+            let kind = match (replay_log, expected_log) {
+                (Some(replay_log), Some(expected_log)) if replay_log == expected_log => {
+                    return None
+                }
+                (Some(_), Some(_)) => ReplayLogDiffKind::Changed,
+                (Some(_), None) => ReplayLogDiffKind::Added,
+                (None, _) => ReplayLogDiffKind::Removed,
+            };
+            Some(ReplayLogDiff {
+                index,
+                kind,
+                replay: replay_log.cloned(),
+                expected: expected_log.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn compare_replay(
+    input: &SimulationReplayInput,
+    replay: &ReplayExecution,
+    witness_only: bool,
+) -> ReplayComparison {
+    let expected_return_data =
+        normalize_hex(input.simulation.return_data.as_deref().unwrap_or("0x"));
+
+    let logs = if witness_only {
+        None
+    } else {
+        let expected_logs = normalize_simulation_logs(&input.simulation.logs);
+        let replay_logs = normalize_simulation_logs(&replay.logs);
+        Some(ReplayFieldComparison {
+            matched: match input.log_comparison_mode {
+                LogComparisonMode::Strict => replay_logs == expected_logs,
+                LogComparisonMode::Semantic => {
+                    logs_match_semantically(&replay_logs, &expected_logs)
+                }
+            },
+            replay: replay_logs,
+            expected: expected_logs,
+        })
+    };
+
+    // An unparseable gas expectation is reported as an exec error by the
+    // caller; here it simply counts as a gas mismatch.
+    let (gas_matched, expected_gas) = match resolve_gas_policy(&input.simulation) {
+        Ok(policy) => (policy.admits(replay.gas_used), policy.describe()),
+        Err(_) => match input.simulation.expected_gas_range.as_ref() {
+            Some(range) => (false, format!("{}..={}", range.min, range.max)),
+            None => (false, input.simulation.gas_used.clone()),
+        },
+    };
+
+    ReplayComparison {
+        success: ReplayFieldComparison {
+            matched: replay.success == input.simulation.success,
+            replay: replay.success,
+            expected: input.simulation.success,
+        },
+        return_data: ReplayFieldComparison {
+            matched: replay.return_data == expected_return_data,
+            replay: replay.return_data.clone(),
+            expected: expected_return_data,
+        },
+        logs,
+        gas: ReplayFieldComparison {
+            matched: gas_matched,
+            replay: replay.gas_used.to_string(),
+            expected: expected_gas,
+        },
+    }
+}
+
+/// Every replay CREATE2 deployment must be expected and every expected address
+/// deployed. Each deployment's address is also re-derived from its deployer,
+/// salt and init code hash.
+fn check_create2_deployments(
+    expected: &[String],
+    deployments: &[ReplayCreate2Deployment],
+) -> Result<(), String> {
+    let expected = expected
+        .iter()
+        .map(|address| parse_address(address, "expectedCreate2Addresses"))
+        .collect::<Result<BTreeSet<_>, _>>()?;
+    let mut deployed = BTreeSet::new();
+    for deployment in deployments {
+        let address = parse_address(&deployment.address, "CREATE2 deployment address")?;
+        let derived = compute_create2_address(
+            parse_address(&deployment.deployer, "CREATE2 deployer")?,
+            B256::from_str(&deployment.salt)
+                .map_err(|err| format!("invalid CREATE2 salt: {err}"))?,
+            B256::from_str(&deployment.init_code_hash)
+                .map_err(|err| format!("invalid CREATE2 init code hash: {err}"))?,
+        );
+        if derived != address {
+            return Err(format!(
+                "CREATE2 deployment at {address:#x} does not match its derived address {derived:#x}."
+            ));
+        }
+        if !expected.contains(&address) {
+            return Err(format!(
+                "Replay deployed {address:#x} via CREATE2, which is not in expectedCreate2Addresses."
+            ));
+        }
+        deployed.insert(address);
+    }
+    if let Some(missing) = expected.difference(&deployed).next() {
+        return Err(format!(
+            "Expected CREATE2 deployment at {missing:#x} did not occur in the replay."
+        ));
+    }
+    Ok(())
+}
+
+fn resolve_gas_policy(simulation: &ReplaySimulation) -> Result<GasPolicy, String> {
+    let Some(range) = simulation.expected_gas_range.as_ref() else {
+        return parse_hex_quantity(&simulation.gas_used, "simulation.gasUsed")
+            .map(GasPolicy::AtMost);
+    };
+    let min = parse_hex_quantity(&range.min, "simulation.expectedGasRange.min")?;
+    let max = parse_hex_quantity(&range.max, "simulation.expectedGasRange.max")?;
+    if min > max {
+        return Err(format!(
+            "invalid simulation.expectedGasRange: min {min} exceeds max {max}."
+        ));
     }
+    Ok(GasPolicy::Within { min, max })
 }
 
+/// Within a sequence, `db` starts from the state earlier transactions left
+/// and the replay's own changes are committed back into `sequence`.
 fn execute_replay(
     input: &SimulationReplayInput,
     accounts: &[ReplayWitnessAccount],
+    track_access: bool,
+    mut sequence: Option<&mut ReplaySequenceState>,
 ) -> Result<ReplayExecution, String> {
+    let _span = tracing::debug_span!("replay_execution", accounts = accounts.len()).entered();
     let witness_only = input.simulation_witness.witness_only.unwrap_or(false);
-    let mut db = CacheDB::new(EmptyDB::default());
+    let carried = sequence.as_deref();
+    let mut db = carried.map_or_else(
+        || CacheDB::new(EmptyDB::default()),
+        |sequence| sequence.db.clone(),
+    );
 
-    let caller = match input.simulation_witness.replay_caller.as_deref() {
-        Some(raw) => parse_address(raw, "simulationWitness.replayCaller")?,
-        None => parse_address(&input.safe_address, "safeAddress")?,
-    };
-    let caller_account = accounts.iter().find(|account| {
-        parse_address(&account.address, "replay account address").ok() == Some(caller)
-    });
-    let caller_nonce = caller_account.map(|account| account.nonce).unwrap_or(0);
+    let caller = resolve_replay_caller(input)?;
+    let caller_account = find_witness_account(accounts, caller);
+    let carried_caller = carried.and_then(|sequence| sequence.carried(caller));
+    let caller_nonce = input
+        .simulation_witness
+        .replay_caller_nonce
+        .or(carried_caller.map(|account| account.info.nonce))
+        .or(caller_account.map(|account| account.nonce))
+        .unwrap_or(0);
 
     // When replayCalldata is present, call execTransaction on the Safe proxy
     // instead of the inner transaction directly. This ensures the replay return
@@ -365,48 +2125,39 @@ fn execute_replay(
     let (tx_target, tx_value, tx_data, gas_limit) =
         if let Some(ref raw_calldata) = input.simulation_witness.replay_calldata {
             let safe_addr = parse_address(&input.safe_address, "safeAddress")?;
-            let calldata = parse_bytes(raw_calldata)
-                .map_err(|err| format!("invalid simulationWitness.replayCalldata: {err}"))?;
+            let calldata = parse_hex_bytes(raw_calldata, "simulationWitness.replayCalldata")?;
             let limit = input
                 .simulation_witness
                 .replay_gas_limit
                 .unwrap_or(10_000_000);
-            (safe_addr, U256::ZERO, calldata, limit)
+            (Some(safe_addr), U256::ZERO, calldata, Some(limit))
         } else {
-            let to = parse_address(&input.transaction.to, "transaction.to")?;
-            let inner_value = parse_u256(&input.transaction.value)
-                .map_err(|err| format!("invalid transaction.value: {err}"))?;
+            let to = parse_transaction_to(&input.transaction.to)?;
+            let inner_value = parse_hex_quantity(&input.transaction.value, "transaction.value")?;
             let data = match input.transaction.data.as_deref() {
-                Some(raw) => {
-                    parse_bytes(raw).map_err(|err| format!("invalid transaction.data: {err}"))?
-                }
+                Some(raw) => parse_hex_bytes(raw, "transaction.data")?,
                 None => Bytes::new(),
             };
             let limit = match input.simulation_witness.replay_gas_limit {
-                Some(limit) => limit,
+                Some(limit) => Some(limit),
                 None => match input.transaction.safe_tx_gas.as_deref() {
                     Some(raw) => {
-                        let parsed = parse_u256(raw)
-                            .map_err(|err| format!("invalid transaction.safeTxGas: {err}"))?;
+                        let parsed = parse_hex_quantity(raw, "transaction.safeTxGas")?;
                         let capped = parsed.min(U256::from(u64::MAX));
-                        let as_u64 = capped.to::<u64>();
-                        if as_u64 == 0 {
-                            3_000_000
-                        } else {
-                            as_u64
-                        }
+                        Some(capped.to::<u64>()).filter(|limit| *limit != 0)
                     }
-                    None => 3_000_000,
+                    None => None,
                 },
             };
             (to, inner_value, data, limit)
         };
 
+    let call_kind = tx_target.map_or(TxKind::Create, TxKind::Call);
     let tx_kind = if has_replay_calldata {
-        TxKind::Call(tx_target)
+        call_kind
     } else {
         match input.transaction.operation {
-            0 => TxKind::Call(tx_target),
+            0 => call_kind,
             1 => {
                 return Err(
                     "transaction.operation=1 (DELEGATECALL) is not replay-supported in the local verifier."
@@ -421,36 +2172,141 @@ fn execute_replay(
         }
     };
 
-    let gas_price = resolve_replay_gas_price(input)?;
-    let required_caller_balance = (U256::from(gas_limit) * U256::from(gas_price)) + tx_value;
+    let mut block = resolve_replay_block(input, witness_only)?;
+    // A zero placeholder lets revm accept a post-merge block without one;
+    // execution that actually reads it is rejected below.
+    let prevrandao_supplied = block.prevrandao.is_some();
+    block.prevrandao.get_or_insert(B256::ZERO);
+    let gas_price = replay_gas_price(input, &block);
+    let spec = resolve_replay_spec(input, &block)?.unwrap_or_default();
+    let gas_limit =
+        gas_limit.unwrap_or_else(|| default_replay_gas_limit(input.chain_id, &block, spec));
+    let gas_limit = bound_by_proven_gas_limit(input, gas_limit)?;
+    if gas_limit > block.gas_limit {
+        return Err(format!(
+            "Replay gas limit {gas_limit} exceeds the replay block gas limit {}; no transaction in that block could have had it.",
+            block.gas_limit
+        ));
+    }
+    let blob_hashes = input
+        .simulation_witness
+        .blob_hashes
+        .iter()
+        .map(|raw| parse_b256(raw, "simulationWitness.blobHashes"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let access_list = input
+        .simulation_witness
+        .replay_access_list
+        .iter()
+        .map(|entry| parse_access_list_entry(entry, "simulationWitness.replayAccessList"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let blob_gas_price = block
+        .blob_excess_gas_and_price
+        .map(|blob| blob.blob_gasprice)
+        .unwrap_or_default();
+    let blob_fee = U256::from(blob_hashes.len() as u64 * GAS_PER_BLOB) * U256::from(blob_gas_price);
+    let required_caller_balance =
+        (U256::from(gas_limit) * U256::from(gas_price)) + tx_value + blob_fee;
 
+    // Mirrors what is seeded into the database, but with the caller's
+    // witness balance: the top-up only lets revm charge gas the witness may
+    // not cover, and is taken back out before the post-state root.
+    let record_prestate = input.verify_post_state_root || carried.is_some();
+    let mut prestate = BTreeMap::new();
+    let mut seeded_nonces = BTreeMap::from([(caller, caller_nonce)]);
+    let mut caller_top_up = U256::ZERO;
     for account in accounts {
         let address = parse_address(&account.address, "replay account address")?;
-        let mut balance = parse_u256(&account.balance)
-            .map_err(|err| format!("invalid replay account balance for {address:#x}: {err}"))?;
-        let code = parse_bytes(&account.code)
-            .map_err(|err| format!("invalid replay account code for {address:#x}: {err}"))?;
+        let field = format!("simulationWitness.replayAccounts[{address:#x}]");
+        if let Some(carried) = carried.and_then(|sequence| sequence.carried(address)) {
+            let mut state_account = carried_state_account(carried);
+            for (slot_key, slot_value) in canonical_seed_storage(&field, &account.storage)? {
+                if carried.storage.contains_key(&slot_key) {
+                    continue;
+                }
+                db.insert_account_storage(address, slot_key, slot_value)
+                    .map_err(|err| format!("failed to seed storage for {address:#x}: {err}"))?;
+                if !slot_value.is_zero() {
+                    state_account.storage.insert(slot_key, slot_value);
+                }
+            }
+            seeded_nonces.insert(address, state_account.nonce);
+            if record_prestate {
+                prestate.insert(address, state_account);
+            }
+            continue;
+        }
+        let balance = parse_hex_quantity(&account.balance, &format!("{field}.balance"))?;
+        let code = parse_hex_bytes(&account.code, &format!("{field}.code"))?;
 
         if address == caller && balance < required_caller_balance {
-            balance = required_caller_balance;
+            caller_top_up = required_caller_balance - balance;
+        }
+
+        let nonce = if address == caller {
+            caller_nonce
+        } else {
+            account.nonce
+        };
+        seeded_nonces.insert(address, nonce);
+        let code = Bytecode::new_raw(code);
+        let storage = canonical_seed_storage(&field, &account.storage)?;
+        if record_prestate {
+            prestate.insert(
+                address,
+                StateAccount {
+                    nonce,
+                    balance,
+                    code_hash: bytecode_hash(&code),
+                    storage: storage.clone(),
+                },
+            );
         }
 
+        let seeded_balance = if address == caller {
+            balance + caller_top_up
+        } else {
+            balance
+        };
         db.insert_account_info(
             address,
-            AccountInfo::new(balance, account.nonce, B256::ZERO, Bytecode::new_raw(code)),
+            AccountInfo::new(seeded_balance, nonce, B256::ZERO, code),
         );
 
-        for (slot, value) in &account.storage {
-            let slot_key = parse_u256(slot)
-                .map_err(|err| format!("invalid storage key for {address:#x}: {err}"))?;
-            let slot_value = parse_u256(value)
-                .map_err(|err| format!("invalid storage value for {address:#x}: {err}"))?;
+        for (slot_key, slot_value) in storage {
             db.insert_account_storage(address, slot_key, slot_value)
                 .map_err(|err| format!("failed to seed storage for {address:#x}: {err}"))?;
         }
     }
 
-    if caller_account.is_none() {
+    if let Some(carried) = carried_caller {
+        if record_prestate && caller_account.is_none() {
+            prestate.insert(caller, carried_state_account(carried));
+        }
+        // Earlier transactions may have spent the previous top-up.
+        if carried.info.balance < required_caller_balance {
+            caller_top_up = required_caller_balance - carried.info.balance;
+            db.insert_account_info(
+                caller,
+                AccountInfo {
+                    balance: required_caller_balance,
+                    ..carried.info.clone()
+                },
+            );
+        }
+    } else if caller_account.is_none() {
+        caller_top_up = required_caller_balance;
+        if record_prestate {
+            prestate.insert(
+                caller,
+                StateAccount {
+                    nonce: caller_nonce,
+                    balance: U256::ZERO,
+                    code_hash: KECCAK_EMPTY,
+                    storage: BTreeMap::new(),
+                },
+            );
+        }
         db.insert_account_info(
             caller,
             AccountInfo::new(
@@ -461,6 +2317,21 @@ fn execute_replay(
             ),
         );
     }
+    // Synthetic balance seeded so far, by account, across the sequence.
+    let mut top_ups = carried.map_or_else(BTreeMap::new, |sequence| sequence.top_ups.clone());
+    if !caller_top_up.is_zero() {
+        *top_ups.entry(caller).or_insert(U256::ZERO) += caller_top_up;
+    }
+    let tx_envelope = input.include_tx_envelope.then(|| ReplayTxEnvelope {
+        caller: format!("{caller:#x}"),
+        to: tx_target.map(|to| format!("{to:#x}")),
+        value: tx_value.to_string(),
+        data: to_hex_prefixed(&tx_data),
+        gas_limit,
+        gas_price: gas_price.to_string(),
+        nonce: caller_nonce,
+        chain_id: input.chain_id,
+    });
     let tx = TxEnv::builder()
         .caller(caller)
         .kind(tx_kind)
@@ -470,32 +2341,686 @@ fn execute_replay(
         .chain_id(Some(input.chain_id))
         .value(tx_value)
         .data(tx_data)
+        .blob_hashes(blob_hashes)
+        .access_list(AccessList(access_list))
+        .max_fee_per_blob_gas(blob_gas_price)
         .build()
         .map_err(|err| format!("failed to build replay tx: {err:?}"))?;
 
-    let block = resolve_replay_block(input, witness_only)?;
     let ctx = Context::mainnet()
         .modify_cfg_chained(|cfg| {
             cfg.chain_id = input.chain_id;
+            cfg.spec = spec;
+            cfg.disable_base_fee = input.simulation_witness.zero_gas_price;
         })
         .with_block(block)
         .with_db(db);
-    let mut inspector = NativeTransferInspector::default();
+    let mut inspector = ReplayInspector::new(
+        input.trace_storage_ops,
+        track_access,
+        input.profile_gas,
+        input.capture_pre_revert_logs,
+        spec,
+    );
     let mut evm = ctx.build_mainnet_with_inspector(&mut inspector);
     let replay = evm
         .inspect_one_tx(tx)
         .map_err(|err| format!("local replay transaction failed: {err}"))?;
-    let native_transfers = inspector.into_transfers();
+    let state = evm.finalize();
+    if inspector.reads_prevrandao && !prevrandao_supplied {
+        return Err(
+            "Replay executed PREVRANDAO (0x44) but simulationWitness.replayBlock.prevRandao is missing; the replay would have read zero instead of the block's randomness."
+                .to_string(),
+        );
+    }
+    let nonce_diffs = nonce_diffs(&seeded_nonces, &state);
+    let post_state_root = if input.verify_post_state_root {
+        let mut post_state = apply_state_changes(prestate.clone(), state.clone());
+        for (address, top_up) in &top_ups {
+            let Some(account) = post_state.get_mut(address) else {
+                continue;
+            };
+            account.balance = account.balance.checked_sub(*top_up).ok_or_else(|| {
+                format!(
+                    "Replay caller {address:#x} spent more than its witness balance (the replay topped it up by {top_up} wei to cover gas and value), so no post-state root can be computed."
+                )
+            })?;
+        }
+        Some(state_root(&post_state))
+    } else {
+        None
+    };
+    if let Some(sequence) = sequence.as_deref_mut() {
+        sequence.commit(&prestate, state);
+        sequence.top_ups = top_ups;
+    }
+    let gas_profile = inspector.gas_profile.take();
+    let emitted_logs = inspector.emitted_logs.take();
+    let reentrancy_detected = inspector.reentrancy_detected;
+    let (native_transfers, storage_ops, create2_deployments, access) = inspector.into_parts();
 
-    Ok(extract_execution(replay, native_transfers))
+    let mut execution = extract_execution(replay, native_transfers);
+    execution.storage_ops = storage_ops;
+    execution.spec = spec;
+    execution.post_state_root = post_state_root;
+    execution.gas_profile = gas_profile;
+    execution.reentrancy_detected = reentrancy_detected;
+    execution.tx_envelope = tx_envelope;
+    if input.require_complete_witness {
+        let precompiles = Precompiles::latest();
+        execution.unseeded_accounts = access
+            .iter()
+            .flat_map(|access| access.accounts.iter().copied())
+            .filter(|address| *address != caller && !precompiles.contains(address))
+            .filter(|address| find_witness_account(accounts, *address).is_none())
+            .collect();
+    }
+    execution.access = access;
+    // A reverted or halted replay leaves no deployments behind.
+    if execution.success {
+        execution.create2_deployments = create2_deployments;
+        execution.nonce_diffs = nonce_diffs;
+    } else {
+        execution.pre_revert_logs =
+            emitted_logs.map(|logs| logs.into_iter().map(into_simulation_log).collect());
+    }
+    tracing::debug!(
+        success = execution.success,
+        gas_used = execution.gas_used,
+        "replay executed"
+    );
+    Ok(execution)
 }
 
-fn resolve_replay_block(
-    input: &SimulationReplayInput,
+/// State carried between the transactions of a replay sequence.
+#[derive(Debug, Default)]
+struct ReplaySequenceState {
+    /// Every account a transaction touched, as the last one left it.
+    db: CacheDB<EmptyDB>,
+    /// Each touched account as seeded before the sequence first touched it.
+    initial: BTreeMap<Address, StateAccount>,
+    /// Balance callers were topped up by to pay for gas, kept out of
+    /// `state_diff` and the post-state root.
+    top_ups: BTreeMap<Address, U256>,
+}
+
+impl ReplaySequenceState {
+    fn carried(&self, address: Address) -> Option<&DbAccount> {
+        self.db.cache.accounts.get(&address)
+    }
+
+    fn commit(&mut self, prestate: &BTreeMap<Address, StateAccount>, changes: EvmState) {
+        for (address, account) in &changes {
+            if !account.is_touched() {
+                continue;
+            }
+            let seeded = prestate.get(address);
+            let initial = self
+                .initial
+                .entry(*address)
+                .or_insert_with(|| StateAccount {
+                    nonce: seeded.map_or(0, |account| account.nonce),
+                    balance: seeded.map_or(U256::ZERO, |account| account.balance),
+                    code_hash: seeded.map_or(KECCAK_EMPTY, |account| account.code_hash),
+                    storage: BTreeMap::new(),
+                });
+            // Slots a later witness seeds were still at their initial value.
+            for (slot, value) in seeded.into_iter().flat_map(|account| &account.storage) {
+                initial.storage.entry(*slot).or_insert(*value);
+            }
+        }
+        self.db.commit(changes);
+    }
+
+    fn state_diff(&self) -> Vec<ReplayAccountStateDiff> {
+        self.initial
+            .iter()
+            .filter_map(|(address, before)| {
+                let after = self.carried(*address)?;
+                // An account that spent more than its witness balance shows
+                // zero rather than the synthetic top-up.
+                let balance_after = after
+                    .info
+                    .balance
+                    .saturating_sub(self.top_ups.get(address).copied().unwrap_or_default());
+                let slots: BTreeSet<U256> = before
+                    .storage
+                    .keys()
+                    .chain(after.storage.keys())
+                    .copied()
+                    .collect();
+                let storage: Vec<_> = slots
+                    .into_iter()
+                    .filter_map(|slot| {
+                        let old = before.storage.get(&slot).copied().unwrap_or_default();
+                        let new = after.storage.get(&slot).copied().unwrap_or(old);
+                        (old != new).then(|| ReplayStorageSlotDiff {
+                            slot: format!("{slot:#x}"),
+                            before: format!("{old:#x}"),
+                            after: format!("{new:#x}"),
+                        })
+                    })
+                    .collect();
+                let unchanged = before.nonce == after.info.nonce
+                    && before.balance == balance_after
+                    && storage.is_empty();
+                (!unchanged).then(|| ReplayAccountStateDiff {
+                    address: format!("{address:#x}"),
+                    balance_before: before.balance.to_string(),
+                    balance_after: balance_after.to_string(),
+                    nonce_before: before.nonce,
+                    nonce_after: after.info.nonce,
+                    storage,
+                })
+            })
+            .collect()
+    }
+}
+
+fn carried_state_account(account: &DbAccount) -> StateAccount {
+    StateAccount {
+        nonce: account.info.nonce,
+        balance: account.info.balance,
+        code_hash: account.info.code_hash,
+        storage: account
+            .storage
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(slot, value)| (*slot, *value))
+            .collect(),
+    }
+}
+
+/// Touched accounts whose nonce differs from what was seeded, by address.
+fn nonce_diffs(seeded: &BTreeMap<Address, u64>, changes: &EvmState) -> Vec<ReplayNonceDiff> {
+    let mut diffs: Vec<ReplayNonceDiff> = changes
+        .iter()
+        .filter(|(_, account)| account.is_touched())
+        .filter_map(|(address, account)| {
+            let before = seeded.get(address).copied().unwrap_or(0);
+            (account.info.nonce != before).then(|| ReplayNonceDiff {
+                address: format!("{address:#x}"),
+                before,
+                after: account.info.nonce,
+            })
+        })
+        .collect();
+    diffs.sort_by(|left, right| left.address.cmp(&right.address));
+    diffs
+}
+
+/// Overlay the replay's journal onto the seeded accounts. Self-destructed
+/// and empty accounts are dropped, as EIP-161 removes them from the trie.
+fn apply_state_changes(
+    mut accounts: BTreeMap<Address, StateAccount>,
+    changes: EvmState,
+) -> BTreeMap<Address, StateAccount> {
+    for (address, account) in changes {
+        if !account.is_touched() {
+            continue;
+        }
+        if account.is_selfdestructed() {
+            accounts.remove(&address);
+            continue;
+        }
+        let code_hash = match account.info.code.as_ref() {
+            Some(code) => bytecode_hash(code),
+            None => account.info.code_hash,
+        };
+        let entry = accounts.entry(address).or_insert_with(|| StateAccount {
+            nonce: 0,
+            balance: U256::ZERO,
+            code_hash,
+            storage: BTreeMap::new(),
+        });
+        entry.nonce = account.info.nonce;
+        entry.balance = account.info.balance;
+        entry.code_hash = code_hash;
+        for (slot, value) in account.storage {
+            if value.present_value.is_zero() {
+                entry.storage.remove(&slot);
+            } else {
+                entry.storage.insert(slot, value.present_value);
+            }
+        }
+    }
+    accounts.retain(|_, account| {
+        account.nonce != 0
+            || !account.balance.is_zero()
+            || account.code_hash != KECCAK_EMPTY
+            || !account.storage.is_empty()
+    });
+    accounts
+}
+
+fn bytecode_hash(code: &Bytecode) -> B256 {
+    if code.is_empty() {
+        KECCAK_EMPTY
+    } else {
+        code.hash_slow()
+    }
+}
+
+/// Shrink the witness to the accounts and storage slots the replay actually
+/// touches, plus the caller. The pruned witness is replayed again and must
+/// reproduce the full witness's outcome.
+pub fn prune_replay_witness(
+    input: &SimulationReplayInput,
+) -> Result<Vec<ReplayWitnessAccount>, String> {
+    let accounts = resolve_witness_accounts(&input.simulation_witness)?.ok_or_else(|| {
+        "simulationWitness has neither replayAccounts nor replayPrestateDiff; nothing to prune."
+            .to_string()
+    })?;
+    let full = execute_replay(input, &accounts, true, None)?;
+    let access = full
+        .access
+        .as_ref()
+        .ok_or("replay did not record account access")?;
+    let caller = resolve_replay_caller(input)?;
+
+    let mut pruned = Vec::new();
+    for account in accounts.iter() {
+        let address = parse_address(&account.address, "replay account address")?;
+        let slots = access.slots.get(&address);
+        if address != caller && slots.is_none() && !access.accounts.contains(&address) {
+            continue;
+        }
+        let field = format!("simulationWitness.replayAccounts[{address:#x}]");
+        let mut storage = BTreeMap::new();
+        for (key, value) in &account.storage {
+            let slot = parse_hex_quantity(key, &format!("{field}.storage key"))?;
+            if slots.is_some_and(|slots| slots.contains(&slot)) {
+                storage.insert(key.clone(), value.clone());
+            }
+        }
+        pruned.push(ReplayWitnessAccount {
+            storage,
+            ..account.clone()
+        });
+    }
+
+    let reproduced = execute_replay(input, &pruned, false, None)?;
+    if (
+        reproduced.success,
+        &reproduced.return_data,
+        reproduced.gas_used,
+        &reproduced.logs,
+    ) != (full.success, &full.return_data, full.gas_used, &full.logs)
+    {
+        return Err(format!(
+            "Pruned witness ({} of {} accounts) does not reproduce the full replay.",
+            pruned.len(),
+            accounts.len()
+        ));
+    }
+    Ok(pruned)
+}
+
+/// Runs `transaction` as a plain call against the witness block and accounts,
+/// ignoring the packaged simulation. Returns the call's success and return
+/// data; used for view calls such as EIP-1271 `isValidSignature`. Witness
+/// code must match `provenCodeHashes`, as for a full replay.
+pub(crate) fn replay_witness_call(input: &SimulationReplayInput) -> Result<(bool, Bytes), String> {
+    let accounts = resolve_witness_accounts(&input.simulation_witness)?.unwrap_or_default();
+    ensure_witness_code_proven(input, &accounts)?;
+    let execution = execute_replay(input, &accounts, false, None)?;
+    let return_data = parse_hex_bytes(&execution.return_data, "replay return data")?;
+    Ok((execution.success, return_data))
+}
+
+/// Resolve the replay accounts from either the full `replayAccounts` list or
+/// the compact `replayPrestateDiff` encoding. `Ok(None)` means neither is set.
+fn resolve_witness_accounts(
+    witness: &ReplayWitness,
+) -> Result<Option<Cow<'_, [ReplayWitnessAccount]>>, String> {
+    match (
+        witness.replay_accounts.as_deref(),
+        witness.replay_prestate_diff.as_ref(),
+    ) {
+        (Some(_), Some(_)) => Err(
+            "simulationWitness carries both replayAccounts and replayPrestateDiff; provide exactly one."
+                .to_string(),
+        ),
+        (Some(accounts), None) => Ok(Some(Cow::Borrowed(accounts))),
+        (None, Some(diff)) => expand_prestate_diff(diff).map(|accounts| Some(Cow::Owned(accounts))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Expand a prestate diff into full replay accounts. Output is sorted by
+/// address and storage keys are canonicalized to 32-byte hex, so equivalent
+/// encodings always expand identically.
+fn expand_prestate_diff(diff: &ReplayPrestateDiff) -> Result<Vec<ReplayWitnessAccount>, String> {
+    let mut expanded = BTreeMap::new();
+    for (raw_address, account) in &diff.base {
+        let address = parse_address(raw_address, "simulationWitness.replayPrestateDiff.base")?;
+        let storage = canonical_prestate_storage(address, &account.storage)?;
+        let entry = ReplayWitnessAccount {
+            address: format!("{address:#x}"),
+            balance: account.balance.clone().unwrap_or_else(|| "0".to_string()),
+            nonce: account.nonce.unwrap_or(0),
+            code: account.code.clone().unwrap_or_else(|| "0x".to_string()),
+            storage,
+        };
+        if expanded.insert(address, entry).is_some() {
+            return Err(format!(
+                "simulationWitness.replayPrestateDiff.base has conflicting entries for {address:#x}."
+            ));
+        }
+    }
+
+    let mut overridden = BTreeSet::new();
+    for (raw_address, account) in &diff.overrides {
+        let address = parse_address(
+            raw_address,
+            "simulationWitness.replayPrestateDiff.overrides",
+        )?;
+        if !overridden.insert(address) {
+            return Err(format!(
+                "simulationWitness.replayPrestateDiff.overrides has conflicting entries for {address:#x}."
+            ));
+        }
+        let Some(entry) = expanded.get_mut(&address) else {
+            return Err(format!(
+                "simulationWitness.replayPrestateDiff.overrides targets {address:#x}, which is missing from base."
+            ));
+        };
+        if let Some(balance) = &account.balance {
+            entry.balance = balance.clone();
+        }
+        if let Some(nonce) = account.nonce {
+            entry.nonce = nonce;
+        }
+        if let Some(code) = &account.code {
+            entry.code = code.clone();
+        }
+        entry
+            .storage
+            .extend(canonical_prestate_storage(address, &account.storage)?);
+    }
+
+    Ok(expanded.into_values().collect())
+}
+
+/// Apply `stateOverrides` to the resolved witness accounts. Storage slots are
+/// merged; addresses absent from the witness are added as empty accounts.
+fn apply_state_overrides(
+    accounts: &mut Vec<ReplayWitnessAccount>,
+    overrides: &[ReplayStateOverride],
+) -> Result<(), String> {
+    for state_override in overrides {
+        let address = parse_address(&state_override.address, "stateOverrides.address")?;
+        let index = match accounts.iter().position(|account| {
+            parse_address(&account.address, "replay account address").ok() == Some(address)
+        }) {
+            Some(index) => index,
+            None => {
+                accounts.push(ReplayWitnessAccount {
+                    address: format!("{address:#x}"),
+                    balance: "0".to_string(),
+                    nonce: 0,
+                    code: "0x".to_string(),
+                    storage: BTreeMap::new(),
+                });
+                accounts.len() - 1
+            }
+        };
+        let account = &mut accounts[index];
+        if let Some(balance) = &state_override.balance {
+            account.balance = balance.clone();
+        }
+        if let Some(nonce) = state_override.nonce {
+            account.nonce = nonce;
+        }
+        if let Some(code) = &state_override.code {
+            account.code = code.clone();
+        }
+        for (slot, value) in &state_override.storage {
+            let slot_key =
+                parse_hex_quantity(slot, &format!("stateOverrides[{address:#x}].storage key"))?;
+            // Drop any witness entry for the same slot spelled differently.
+            account
+                .storage
+                .retain(|existing, _| parse_hex_quantity(existing, "").ok() != Some(slot_key));
+            account.storage.insert(slot.clone(), value.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Parse witness storage into numeric slots. Keys are free-form hex strings,
+/// so `0x01` and its 32-byte spelling are distinct map entries for the same
+/// slot; repeats are tolerated only when they agree on the value.
+fn canonical_seed_storage(
+    field: &str,
+    storage: &BTreeMap<String, String>,
+) -> Result<BTreeMap<U256, U256>, String> {
+    let mut canonical = BTreeMap::new();
+    for (slot, value) in storage {
+        let slot_key = parse_hex_quantity(slot, &format!("{field}.storage key"))?;
+        let slot_value = parse_hex_quantity(value, &format!("{field}.storage[{slot}]"))?;
+        match canonical.insert(slot_key, slot_value) {
+            Some(previous) if previous != slot_value => {
+                return Err(format!(
+                    "{field}.storage has conflicting entries for slot {:#x}: {previous:#x} and {slot_value:#x}.",
+                    B256::from(slot_key)
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(canonical)
+}
+
+fn canonical_prestate_storage(
+    address: Address,
+    storage: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut canonical = BTreeMap::new();
+    for (slot, value) in storage {
+        let slot_key = parse_hex_quantity(
+            slot,
+            &format!("simulationWitness.replayPrestateDiff[{address:#x}].storage key"),
+        )?;
+        let key = format!("{:#x}", B256::from(slot_key));
+        if canonical.insert(key, value.clone()).is_some() {
+            return Err(format!(
+                "simulationWitness.replayPrestateDiff has conflicting entries for slot {slot_key:#x} of {address:#x}."
+            ));
+        }
+    }
+    Ok(canonical)
+}
+
+fn validate_replay_chain_id(input: &SimulationReplayInput) -> Result<(), String> {
+    if input.chain_id == 0 || input.chain_id > MAX_CHAIN_ID {
+        return Err(format!(
+            "invalid chainId {}: expected a non-zero EIP-155 chain id up to {MAX_CHAIN_ID}.",
+            input.chain_id
+        ));
+    }
+    match input.package_chain_id {
+        Some(package_chain_id) if package_chain_id != input.chain_id => Err(format!(
+            "replay chainId {} does not match package chainId {package_chain_id}.",
+            input.chain_id
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Reject witnesses larger than the configured caps before any state is
+/// seeded, so an untrusted package cannot force unbounded replay work.
+fn ensure_witness_within_limits(
+    input: &SimulationReplayInput,
+    accounts: &[ReplayWitnessAccount],
+) -> Result<(), String> {
+    let max_accounts = input
+        .max_replay_accounts
+        .unwrap_or(DEFAULT_MAX_REPLAY_ACCOUNTS);
+    if accounts.len() > max_accounts {
+        return Err(format!(
+            "simulationWitness carries {} replay accounts, exceeding the limit of {max_accounts}.",
+            accounts.len()
+        ));
+    }
+
+    let max_storage = input
+        .max_storage_entries_per_account
+        .unwrap_or(DEFAULT_MAX_STORAGE_ENTRIES_PER_ACCOUNT);
+    if let Some(account) = accounts
+        .iter()
+        .find(|account| account.storage.len() > max_storage)
+    {
+        return Err(format!(
+            "replay account {} carries {} storage entries, exceeding the limit of {max_storage}.",
+            account.address,
+            account.storage.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Seeding lets a later entry for an address silently replace an earlier
+/// one, so two entries for the same address are ambiguous.
+fn ensure_unique_witness_accounts(accounts: &[ReplayWitnessAccount]) -> Result<(), String> {
+    let mut seen = BTreeSet::new();
+    for account in accounts {
+        // Malformed addresses are reported by the replay itself.
+        let Ok(address) = parse_address(&account.address, "replay account address") else {
+            continue;
+        };
+        if !seen.insert(address) {
+            return Err(format!(
+                "simulationWitness.replayAccounts lists {address:#x} more than once; each account must appear exactly once."
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn resolve_replay_caller(input: &SimulationReplayInput) -> Result<Address, String> {
+    match input.simulation_witness.replay_caller.as_deref() {
+        Some(raw) => parse_address(raw, "simulationWitness.replayCaller"),
+        None => parse_address(&input.safe_address, "safeAddress"),
+    }
+}
+
+/// An empty `transaction.to` replays as a top-level contract creation.
+fn parse_transaction_to(raw: &str) -> Result<Option<Address>, String> {
+    if raw.trim().is_empty() {
+        Ok(None)
+    } else {
+        parse_address(raw, "transaction.to").map(Some)
+    }
+}
+
+fn find_witness_account(
+    accounts: &[ReplayWitnessAccount],
+    address: Address,
+) -> Option<&ReplayWitnessAccount> {
+    accounts.iter().find(|account| {
+        parse_address(&account.address, "replay account address").ok() == Some(address)
+    })
+}
+
+/// Ties each witness account's bytecode to the code hash proven for its
+/// address, rather than to whatever the witness declares.
+fn ensure_witness_code_proven(
+    input: &SimulationReplayInput,
+    accounts: &[ReplayWitnessAccount],
+) -> Result<(), String> {
+    for (raw_address, raw_hash) in &input.proven_code_hashes {
+        let address = parse_address(raw_address, "provenCodeHashes address")?;
+        let proven = parse_b256(raw_hash, &format!("provenCodeHashes[{address:#x}]"))?;
+        let Some(account) = find_witness_account(accounts, address) else {
+            continue;
+        };
+        let code = parse_hex_bytes(
+            &account.code,
+            &format!("simulationWitness.replayAccounts[{address:#x}].code"),
+        )?;
+        let witness = keccak256(&code);
+        if witness != proven {
+            return Err(format!(
+                "simulationWitness.replayAccounts[{address:#x}].code hashes to {witness:#x}, but the code hash proven under the verified state root is {proven:#x}."
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn ensure_caller_in_witness(
+    input: &SimulationReplayInput,
+    accounts: &[ReplayWitnessAccount],
+) -> Result<(), String> {
+    // Malformed caller addresses are reported by the replay itself.
+    let Ok(caller) = resolve_replay_caller(input) else {
+        return Ok(());
+    };
+    if find_witness_account(accounts, caller).is_some() {
+        return Ok(());
+    }
+    Err(format!(
+        "replay caller {caller:#x} is missing from simulationWitness.replayAccounts; witness is incomplete for local replay."
+    ))
+}
+
+fn ensure_target_has_code(
+    input: &SimulationReplayInput,
+    accounts: &[ReplayWitnessAccount],
+) -> Result<(), String> {
+    // Malformed addresses and hex are reported by the replay itself.
+    let Ok(target) = parse_address(&input.transaction.to, "transaction.to") else {
+        return Ok(());
+    };
+    let calldata = match input.transaction.data.as_deref() {
+        Some(raw) => parse_hex_bytes(raw, "transaction.data").unwrap_or_default(),
+        None => Bytes::new(),
+    };
+    if calldata.is_empty() {
+        return Ok(());
+    }
+    let has_code = find_witness_account(accounts, target).is_some_and(|account| {
+        parse_hex_bytes(&account.code, "replay account code").map_or(true, |code| !code.is_empty())
+    });
+    if has_code {
+        return Ok(());
+    }
+    Err(format!(
+        "transaction.to {target:#x} has no code in simulationWitness.replayAccounts but transaction.data is non-empty; witness is missing the target bytecode."
+    ))
+}
+
+fn resolve_replay_block(
+    input: &SimulationReplayInput,
+    witness_only: bool,
+) -> Result<BlockEnv, String> {
+    let witness_block = match (
+        input.simulation_witness.replay_block.as_ref(),
+        input.verified_block_header.as_ref(),
+    ) {
+        // The proven header supplies the context a witness-only replay needs.
+        (None, Some(_)) => default_replay_block(input.simulation.block_number),
+        _ => resolve_witness_replay_block(input, witness_only)?,
+    };
+    match input.verified_block_header.as_ref() {
+        Some(header) => apply_verified_block_header(witness_block, header),
+        None => Ok(witness_block),
+    }
+}
+
+fn resolve_witness_replay_block(
+    input: &SimulationReplayInput,
     witness_only: bool,
 ) -> Result<BlockEnv, String> {
     match input.simulation_witness.replay_block.as_ref() {
-        Some(block) => build_replay_block_env(block, input.simulation.block_number),
+        Some(block) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default();
+            build_replay_block_env(block, input.simulation.block_number, input.chain_id, now)
+        }
         None if witness_only => Err(
             "simulationWitness.replayBlock is missing; witness-only replay requires full block context."
                 .to_string(),
@@ -504,21 +3029,209 @@ fn resolve_replay_block(
     }
 }
 
-fn build_replay_block_env(block: &ReplayBlock, block_number: u64) -> Result<BlockEnv, String> {
+fn apply_verified_block_header(
+    block: BlockEnv,
+    header: &VerifiedBlockHeader,
+) -> Result<BlockEnv, String> {
+    Ok(BlockEnv {
+        number: U256::from(header.block_number),
+        beneficiary: parse_address(&header.fee_recipient, "verifiedBlockHeader.feeRecipient")?,
+        timestamp: U256::from(header.timestamp),
+        gas_limit: header.gas_limit,
+        basefee: parse_u64_quantity(
+            &header.base_fee_per_gas,
+            "verifiedBlockHeader.baseFeePerGas",
+        )?,
+        prevrandao: Some(parse_b256(
+            &header.prev_randao,
+            "verifiedBlockHeader.prevRandao",
+        )?),
+        ..block
+    })
+}
+
+fn bound_by_proven_gas_limit(input: &SimulationReplayInput, gas_limit: u64) -> Result<u64, String> {
+    let (Some(policy), Some(header)) = (
+        input.proven_gas_limit_policy,
+        input.verified_block_header.as_ref(),
+    ) else {
+        return Ok(gas_limit);
+    };
+    if gas_limit <= header.gas_limit {
+        return Ok(gas_limit);
+    }
+    match policy {
+        ProvenGasLimitPolicy::Reject => Err(format!(
+            "Replay gas limit {gas_limit} exceeds the proven block gas limit {} of block {}.",
+            header.gas_limit, header.block_number
+        )),
+        ProvenGasLimitPolicy::Clamp => Ok(header.gas_limit),
+    }
+}
+
+/// The chain's typical block gas limit, kept within the replay block's own
+/// limit and, from Osaka, the EIP-7825 per-transaction cap.
+fn default_replay_gas_limit(chain_id: u64, block: &BlockEnv, spec: SpecId) -> u64 {
+    let limit = DEFAULT_REPLAY_GAS_LIMITS
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map_or(FALLBACK_REPLAY_GAS_LIMIT, |(_, limit)| *limit)
+        .min(block.gas_limit);
+    if spec.is_enabled_in(SpecId::OSAKA) {
+        limit.min(OSAKA_TX_GAS_LIMIT_CAP)
+    } else {
+        limit
+    }
+}
+
+/// `None` keeps revm's default (latest) spec.
+fn resolve_replay_spec(
+    input: &SimulationReplayInput,
+    block: &BlockEnv,
+) -> Result<Option<SpecId>, String> {
+    let block_number = block.number.saturating_to::<u64>();
+    let timestamp = block.timestamp.saturating_to::<u64>();
+    match input.fork_schedule.as_deref() {
+        Some(activations) => {
+            let schedule = parse_fork_schedule(activations)?;
+            Ok(Some(spec_at_block(&schedule, block_number, timestamp)))
+        }
+        None if input.pin_spec_to_block => Ok(Some(spec_at_block(
+            MAINNET_FORK_SCHEDULE,
+            block_number,
+            timestamp,
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn parse_fork_schedule(
+    activations: &[ReplayForkActivation],
+) -> Result<Vec<(SpecId, ForkCondition)>, String> {
+    activations
+        .iter()
+        .enumerate()
+        .map(|(index, activation)| {
+            let field = format!("forkSchedule[{index}]");
+            let spec = parse_spec_name(&activation.fork).ok_or_else(|| {
+                format!("invalid {field}.fork: unknown fork \"{}\"", activation.fork)
+            })?;
+            let condition = match (activation.block, activation.timestamp) {
+                (Some(block), None) => ForkCondition::Block(block),
+                (None, Some(timestamp)) => ForkCondition::Timestamp(timestamp),
+                _ => {
+                    return Err(format!(
+                        "invalid {field}: set exactly one of block or timestamp."
+                    ))
+                }
+            };
+            Ok((spec, condition))
+        })
+        .collect()
+}
+
+fn parse_spec_name(raw: &str) -> Option<SpecId> {
+    let normalized: String = raw
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric())
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect();
+    Some(match normalized.as_str() {
+        "frontier" => SpecId::FRONTIER,
+        "homestead" => SpecId::HOMESTEAD,
+        "daofork" => SpecId::DAO_FORK,
+        "tangerine" | "tangerinewhistle" => SpecId::TANGERINE,
+        "spuriousdragon" => SpecId::SPURIOUS_DRAGON,
+        "byzantium" => SpecId::BYZANTIUM,
+        "constantinople" => SpecId::CONSTANTINOPLE,
+        "petersburg" => SpecId::PETERSBURG,
+        "istanbul" => SpecId::ISTANBUL,
+        "muirglacier" => SpecId::MUIR_GLACIER,
+        "berlin" => SpecId::BERLIN,
+        "london" => SpecId::LONDON,
+        "arrowglacier" => SpecId::ARROW_GLACIER,
+        "grayglacier" => SpecId::GRAY_GLACIER,
+        "merge" | "paris" => SpecId::MERGE,
+        "shanghai" => SpecId::SHANGHAI,
+        "cancun" => SpecId::CANCUN,
+        "prague" => SpecId::PRAGUE,
+        "osaka" => SpecId::OSAKA,
+        _ => return None,
+    })
+}
+
+/// Lowercase fork name, the inverse of `parse_spec_name`, so `specUsed` does
+/// not track revm's display names.
+fn spec_name(spec: SpecId) -> &'static str {
+    match spec {
+        SpecId::FRONTIER => "frontier",
+        SpecId::HOMESTEAD => "homestead",
+        SpecId::DAO_FORK => "daofork",
+        SpecId::TANGERINE => "tangerine",
+        SpecId::SPURIOUS_DRAGON => "spuriousdragon",
+        SpecId::BYZANTIUM => "byzantium",
+        SpecId::CONSTANTINOPLE => "constantinople",
+        SpecId::PETERSBURG => "petersburg",
+        SpecId::ISTANBUL => "istanbul",
+        SpecId::MUIR_GLACIER => "muirglacier",
+        SpecId::BERLIN => "berlin",
+        SpecId::LONDON => "london",
+        SpecId::ARROW_GLACIER => "arrowglacier",
+        SpecId::GRAY_GLACIER => "grayglacier",
+        SpecId::MERGE => "merge",
+        SpecId::SHANGHAI => "shanghai",
+        SpecId::CANCUN => "cancun",
+        SpecId::PRAGUE => "prague",
+        SpecId::OSAKA => "osaka",
+        other => other.into(),
+    }
+}
+
+/// Latest fork whose activation the block has reached; Frontier when none has.
+fn spec_at_block(
+    schedule: &[(SpecId, ForkCondition)],
+    block_number: u64,
+    timestamp: u64,
+) -> SpecId {
+    schedule
+        .iter()
+        .filter(|(_, condition)| match *condition {
+            ForkCondition::Block(activation) => block_number >= activation,
+            ForkCondition::Timestamp(activation) => timestamp >= activation,
+        })
+        .map(|(spec, _)| *spec)
+        .max()
+        .unwrap_or(SpecId::FRONTIER)
+}
+
+fn build_replay_block_env(
+    block: &ReplayBlock,
+    block_number: u64,
+    chain_id: u64,
+    now: u64,
+) -> Result<BlockEnv, String> {
     let beneficiary = parse_address(
         &block.beneficiary,
         "simulationWitness.replayBlock.beneficiary",
     )?;
-    let timestamp = parse_u256(&block.timestamp)
-        .map_err(|err| format!("invalid simulationWitness.replayBlock.timestamp: {err}"))?;
-    let gas_limit_u256 = parse_u256(&block.gas_limit)
-        .map_err(|err| format!("invalid simulationWitness.replayBlock.gasLimit: {err}"))?;
+    let timestamp =
+        parse_hex_quantity(&block.timestamp, "simulationWitness.replayBlock.timestamp")?;
+    check_replay_block_timestamp(timestamp, chain_id, now)?;
+    let gas_limit_u256 =
+        parse_hex_quantity(&block.gas_limit, "simulationWitness.replayBlock.gasLimit")?;
     if gas_limit_u256 > U256::from(u64::MAX) {
         return Err("simulationWitness.replayBlock.gasLimit exceeds u64 range.".to_string());
     }
     let gas_limit = gas_limit_u256.to::<u64>();
-    let basefee_u256 = parse_u256(&block.base_fee_per_gas)
-        .map_err(|err| format!("invalid simulationWitness.replayBlock.baseFeePerGas: {err}"))?;
+    if !(MIN_REPLAY_BLOCK_GAS_LIMIT..=MAX_REPLAY_BLOCK_GAS_LIMIT).contains(&gas_limit) {
+        return Err(format!(
+            "simulationWitness.replayBlock.gasLimit {gas_limit} is outside the plausible range {MIN_REPLAY_BLOCK_GAS_LIMIT}..={MAX_REPLAY_BLOCK_GAS_LIMIT}."
+        ));
+    }
+    let basefee_u256 = parse_hex_quantity(
+        &block.base_fee_per_gas,
+        "simulationWitness.replayBlock.baseFeePerGas",
+    )?;
     if basefee_u256 > U256::from(u64::MAX) {
         return Err("simulationWitness.replayBlock.baseFeePerGas exceeds u64 range.".to_string());
     }
@@ -528,10 +3241,36 @@ fn build_replay_block_env(block: &ReplayBlock, block_number: u64) -> Result<Bloc
         None => None,
     };
     let difficulty = match block.difficulty.as_deref() {
-        Some(raw) => parse_u256(raw)
-            .map_err(|err| format!("invalid simulationWitness.replayBlock.difficulty: {err}"))?,
+        Some(raw) => parse_hex_quantity(raw, "simulationWitness.replayBlock.difficulty")?,
         None => U256::ZERO,
     };
+    let excess_blob_gas = match block.excess_blob_gas.as_deref() {
+        Some(raw) => Some(parse_u64_quantity(
+            raw,
+            "simulationWitness.replayBlock.excessBlobGas",
+        )?),
+        None => None,
+    };
+    // Absent blob fields keep revm's Cancun-safe default (zero excess gas).
+    let blob_excess_gas_and_price = match (block.blob_base_fee.as_deref(), excess_blob_gas) {
+        (Some(raw), excess) => {
+            let price = parse_hex_quantity(raw, "simulationWitness.replayBlock.blobBaseFee")?;
+            if price > U256::from(u128::MAX) {
+                return Err(
+                    "simulationWitness.replayBlock.blobBaseFee exceeds u128 range.".to_string(),
+                );
+            }
+            Some(BlobExcessGasAndPrice {
+                excess_blob_gas: excess.unwrap_or(0),
+                blob_gasprice: price.to::<u128>(),
+            })
+        }
+        (None, Some(excess)) => Some(BlobExcessGasAndPrice::new(
+            excess,
+            BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE,
+        )),
+        (None, None) => None,
+    };
 
     Ok(BlockEnv {
         number: U256::from(block_number),
@@ -541,26 +3280,47 @@ fn build_replay_block_env(block: &ReplayBlock, block_number: u64) -> Result<Bloc
         basefee,
         difficulty,
         prevrandao,
+        blob_excess_gas_and_price: blob_excess_gas_and_price
+            .or_else(|| BlockEnv::default().blob_excess_gas_and_price),
         ..Default::default()
     })
 }
 
-fn resolve_replay_gas_price(input: &SimulationReplayInput) -> Result<u128, String> {
-    let Some(block) = input.simulation_witness.replay_block.as_ref() else {
-        return Ok(0);
-    };
+/// The replay pays exactly the block base fee, with no priority fee.
+fn replay_gas_price(input: &SimulationReplayInput, block: &BlockEnv) -> u128 {
+    if input.simulation_witness.zero_gas_price {
+        0
+    } else {
+        u128::from(block.basefee)
+    }
+}
 
-    let basefee = parse_u256(&block.base_fee_per_gas)
-        .map_err(|err| format!("invalid simulationWitness.replayBlock.baseFeePerGas: {err}"))?;
-    if basefee > U256::from(u128::MAX) {
-        return Err("simulationWitness.replayBlock.baseFeePerGas exceeds u128 range.".to_string());
+/// Reject timestamps at or before genesis (including zero) and ones beyond
+/// `now` plus `MAX_REPLAY_BLOCK_FUTURE_SECS`.
+fn check_replay_block_timestamp(timestamp: U256, chain_id: u64, now: u64) -> Result<(), String> {
+    let genesis = GENESIS_TIMESTAMPS
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map_or(0, |(_, genesis)| *genesis);
+    if timestamp <= U256::from(genesis) {
+        return Err(format!(
+            "simulationWitness.replayBlock.timestamp {timestamp} is not after the chain {chain_id} genesis timestamp {genesis}."
+        ));
+    }
+    let latest = now.saturating_add(MAX_REPLAY_BLOCK_FUTURE_SECS);
+    if timestamp > U256::from(latest) {
+        return Err(format!(
+            "simulationWitness.replayBlock.timestamp {timestamp} is more than {MAX_REPLAY_BLOCK_FUTURE_SECS}s past the verifier clock ({now})."
+        ));
     }
-    Ok(basefee.to::<u128>())
+    Ok(())
 }
 
+/// Leaves `prevrandao` unset: nothing supplied it.
 fn default_replay_block(block_number: u64) -> BlockEnv {
     BlockEnv {
         number: U256::from(block_number),
+        prevrandao: None,
         ..Default::default()
     }
 }
@@ -575,30 +3335,74 @@ fn extract_execution(
             output,
             logs,
             ..
-        } => ReplayExecution {
-            success: true,
-            return_data: to_hex_prefixed(output.into_data().as_ref()),
-            gas_used,
-            logs: logs.into_iter().map(into_simulation_log).collect(),
-            native_transfers,
-        },
+        } => {
+            let mut logs_bloom = Bloom::default();
+            for log in &logs {
+                logs_bloom.accrue_log(log);
+            }
+            ReplayExecution {
+                success: true,
+                created_address: output.address().copied(),
+                return_data: to_hex_prefixed(output.into_data().as_ref()),
+                gas_used,
+                logs: logs.into_iter().map(into_simulation_log).collect(),
+                logs_bloom,
+                native_transfers,
+                storage_ops: None,
+                halt_reason: None,
+                create2_deployments: Vec::new(),
+                unseeded_accounts: Vec::new(),
+                access: None,
+                spec: SpecId::default(),
+                post_state_root: None,
+                nonce_diffs: Vec::new(),
+                gas_profile: None,
+                reentrancy_detected: false,
+                tx_envelope: None,
+                pre_revert_logs: None,
+            }
+        }
         ExecutionResult::Revert { gas_used, output } => ReplayExecution {
             success: false,
             return_data: to_hex_prefixed(output.as_ref()),
             gas_used,
             logs: Vec::new(),
+            logs_bloom: Bloom::default(),
+            created_address: None,
             native_transfers: Vec::new(),
+            storage_ops: None,
+            halt_reason: None,
+            create2_deployments: Vec::new(),
+            unseeded_accounts: Vec::new(),
+            access: None,
+            spec: SpecId::default(),
+            post_state_root: None,
+            nonce_diffs: Vec::new(),
+            gas_profile: None,
+            reentrancy_detected: false,
+            tx_envelope: None,
+            pre_revert_logs: None,
         },
         ExecutionResult::Halt { reason, gas_used } => ReplayExecution {
             success: false,
             return_data: "0x".to_string(),
             gas_used,
-            logs: vec![ReplaySimulationLog {
-                address: "0x0000000000000000000000000000000000000000".to_string(),
-                topics: vec![format!("halt:{reason:?}")],
-                data: "0x".to_string(),
-            }],
+            logs: Vec::new(),
+            logs_bloom: Bloom::default(),
+            created_address: None,
             native_transfers: Vec::new(),
+            storage_ops: None,
+            halt_reason: Some(ReplayHaltReason::from(&reason)),
+            create2_deployments: Vec::new(),
+            unseeded_accounts: Vec::new(),
+            access: None,
+            spec: SpecId::default(),
+            post_state_root: None,
+            nonce_diffs: Vec::new(),
+            gas_profile: None,
+            reentrancy_detected: false,
+            tx_envelope: None,
+            pre_revert_logs: None,
         },
     }
 }
@@ -630,33 +3434,102 @@ fn normalize_simulation_logs(logs: &[ReplaySimulationLog]) -> Vec<ReplaySimulati
         .collect()
 }
 
+fn logs_match_semantically(left: &[ReplaySimulationLog], right: &[ReplaySimulationLog]) -> bool {
+    left.len() == right.len()
+        && left.iter().zip(right).all(|(left, right)| {
+            left.address == right.address
+                && left.topics == right.topics
+                && abi_words(&left.data).is_some_and(|words| abi_words(&right.data) == Some(words))
+        })
+}
+
+/// `data` right-padded to whole 32-byte words.
+fn abi_words(data: &str) -> Option<Vec<u8>> {
+    let mut bytes = parse_hex_bytes(data, "log data").ok()?.to_vec();
+    bytes.resize(bytes.len().next_multiple_of(32), 0);
+    Some(bytes)
+}
+
 fn parse_address(raw: &str, field: &str) -> Result<Address, String> {
     Address::from_str(raw).map_err(|err| format!("invalid {field} ({raw}): {err}"))
 }
 
-fn parse_bytes(raw: &str) -> Result<Bytes, String> {
+/// Parse `0x`-prefixed (or bare) hex bytes. Errors name `field` and quote
+/// the start of the offending value.
+fn parse_hex_bytes(raw: &str, field: &str) -> Result<Bytes, String> {
     let normalized = raw.trim();
     let stripped = normalized.strip_prefix("0x").unwrap_or(normalized);
+    reject_non_digits(raw, stripped, 16, field)?;
     if !stripped.len().is_multiple_of(2) {
-        return Err("hex string has odd length".to_string());
+        return Err(invalid_hex_value(field, raw, "hex string has odd length"));
     }
-    let decoded = hex::decode(stripped).map_err(|err| err.to_string())?;
+    let decoded = hex::decode(stripped).map_err(|err| invalid_hex_value(field, raw, err))?;
     Ok(Bytes::from(decoded))
 }
 
-fn parse_u256(raw: &str) -> Result<U256, String> {
+/// Parse a quantity in `0x`-prefixed hex or decimal form, with the same
+/// field-aware errors as `parse_hex_bytes`.
+fn parse_hex_quantity(raw: &str, field: &str) -> Result<U256, String> {
     let trimmed = raw.trim();
-    if let Some(hex) = trimmed.strip_prefix("0x") {
-        U256::from_str_radix(hex, 16).map_err(|err| err.to_string())
+    let (digits, radix) = match trimmed.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (trimmed, 10),
+    };
+    reject_non_digits(raw, digits, radix, field)?;
+    U256::from_str_radix(digits, radix as u64).map_err(|err| invalid_hex_value(field, raw, err))
+}
+
+fn reject_non_digits(raw: &str, digits: &str, radix: u32, field: &str) -> Result<(), String> {
+    match digits.char_indices().find(|(_, ch)| !ch.is_digit(radix)) {
+        Some((offset, ch)) => Err(invalid_hex_value(
+            field,
+            raw,
+            format!("unexpected character {ch:?} at offset {offset}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn invalid_hex_value(field: &str, raw: &str, err: impl std::fmt::Display) -> String {
+    const MAX_SNIPPET_CHARS: usize = 24;
+    let snippet: String = raw.chars().take(MAX_SNIPPET_CHARS).collect();
+    let ellipsis = if raw.chars().count() > MAX_SNIPPET_CHARS {
+        "..."
     } else {
-        U256::from_str_radix(trimmed, 10).map_err(|err| err.to_string())
+        ""
+    };
+    format!("invalid {field} ({snippet}{ellipsis}): {err}")
+}
+
+fn parse_u64_quantity(raw: &str, field: &str) -> Result<u64, String> {
+    let value = parse_hex_quantity(raw, field)?;
+    if value > U256::from(u64::MAX) {
+        return Err(format!("{field} exceeds u64 range."));
     }
+    Ok(value.to::<u64>())
 }
 
 fn parse_b256(raw: &str, field: &str) -> Result<B256, String> {
     B256::from_str(raw).map_err(|err| format!("invalid {field} ({raw}): {err}"))
 }
 
+fn parse_access_list_entry(
+    entry: &ReplayAccessListEntry,
+    field: &str,
+) -> Result<AccessListItem, String> {
+    let address = parse_address(&entry.address, &format!("{field}.address"))?;
+    let storage_keys = entry
+        .storage_keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| parse_b256(key, &format!("{field}.storageKeys[{index}]")))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(AccessListItem {
+        address,
+        storage_keys,
+    })
+}
+
 fn normalize_address(value: &str) -> String {
     value.to_ascii_lowercase()
 }
@@ -680,7 +3553,12 @@ fn to_hex_prefixed(bytes: &[u8]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{env, fs, time::Instant};
+    use crate::trace;
+    use std::{
+        env, fs,
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
 
     fn target_account(address: &str, code: &str) -> ReplayWitnessAccount {
         ReplayWitnessAccount {
@@ -706,6 +3584,9 @@ mod tests {
         }
     }
 
+    /// Post-genesis on every chain the tests use.
+    const REPLAY_TIMESTAMP: &str = "1700000000";
+
     fn replay_block(timestamp: &str) -> ReplayBlock {
         ReplayBlock {
             timestamp: timestamp.to_string(),
@@ -716,6 +3597,8 @@ mod tests {
                 "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
             ),
             difficulty: Some("0".to_string()),
+            blob_base_fee: None,
+            excess_blob_gas: None,
         }
     }
 
@@ -830,6 +3713,8 @@ mod tests {
                 gas_used: "21000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
                 replay_block: None,
@@ -838,11 +3723,13 @@ mod tests {
                 replay_gas_limit: None,
                 replay_calldata: None,
                 witness_only: None,
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(!result.executed);
-        assert_eq!(result.reason, REASON_WITNESS_INCOMPLETE);
+        assert_eq!(result.reason, ReplayReason::WitnessIncomplete);
     }
 
     #[test]
@@ -868,29 +3755,34 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![caller_account(caller), target_account(target, code)]),
                 replay_caller: Some(caller.to_string()),
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: None,
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
         assert!(!result.success);
         assert_eq!(
-            result.reason, REASON_REPLAY_MISMATCH_RETURN_DATA,
+            result.reason,
+            ReplayReason::MismatchReturnData,
             "{result:?}"
         );
     }
 
     #[test]
-    fn returns_success_when_replay_matches_simulation() {
-        // Runtime: PUSH1 0x00 PUSH1 0x00 REVERT
-        let code = "0x60006000fd";
+    fn reports_every_mismatched_field_in_comparison() {
+        // Runtime: PUSH1 0x2a PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+        let code = "0x602a60005260206000f3";
         let caller = "0x1000000000000000000000000000000000000001";
         let target = "0x2000000000000000000000000000000000000002";
 
@@ -905,36 +3797,49 @@ mod tests {
                 safe_tx_gas: Some("500000".to_string()),
             },
             simulation: ReplaySimulation {
-                success: false,
+                success: true,
                 return_data: Some("0x".to_string()),
-                gas_used: "500000".to_string(),
+                gas_used: "21000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![caller_account(caller), target_account(target, code)]),
                 replay_caller: Some(caller.to_string()),
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: None,
+                ..Default::default()
             },
+            ..Default::default()
         });
 
-        assert!(result.executed);
-        assert!(result.success, "{result:?}");
-        assert_eq!(result.reason, REASON_REPLAY_MATCHED);
+        assert_eq!(result.reason, ReplayReason::MismatchReturnData);
+        let comparison = result.comparison.expect("comparison must be populated");
+        assert!(comparison.success.matched);
+        assert!(!comparison.return_data.matched);
+        assert_eq!(
+            comparison.return_data.replay,
+            "0x000000000000000000000000000000000000000000000000000000000000002a"
+        );
+        assert_eq!(comparison.return_data.expected, "0x");
+        assert!(comparison.logs.expect("logs are compared").matched);
+        assert!(!comparison.gas.matched, "{:?}", comparison.gas);
+        assert_eq!(comparison.gas.expected, "21000");
     }
 
     #[test]
-    fn returns_success_when_replay_matches_on_non_mainnet_chain_id() {
+    fn returns_success_when_replay_matches_simulation() {
         // Runtime: PUSH1 0x00 PUSH1 0x00 REVERT
         let code = "0x60006000fd";
         let caller = "0x1000000000000000000000000000000000000001";
         let target = "0x2000000000000000000000000000000000000002";
 
         let result = verify_simulation_replay(SimulationReplayInput {
-            chain_id: 100,
+            chain_id: 1,
             safe_address: caller.to_string(),
             transaction: ReplayTransaction {
                 to: target.to_string(),
@@ -949,35 +3854,147 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![caller_account(caller), target_account(target, code)]),
                 replay_caller: Some(caller.to_string()),
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: None,
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
         assert!(result.success, "{result:?}");
-        assert_eq!(result.reason, REASON_REPLAY_MATCHED);
+        assert_eq!(result.reason, ReplayReason::Matched);
     }
 
     #[test]
-    fn uses_caller_nonce_from_witness_account_snapshot() {
-        // Runtime: PUSH1 0x00 PUSH1 0x00 REVERT
-        let code = "0x60006000fd";
+    fn emits_receipt_summary_for_successful_and_reverting_replays() {
         let caller = "0x1000000000000000000000000000000000000001";
         let target = "0x2000000000000000000000000000000000000002";
+        let replay_with_code = |code: &str, success: bool, return_data: &str| {
+            verify_simulation_replay(SimulationReplayInput {
+                chain_id: 1,
+                safe_address: caller.to_string(),
+                transaction: ReplayTransaction {
+                    to: target.to_string(),
+                    value: "0".to_string(),
+                    data: Some("0x".to_string()),
+                    operation: 0,
+                    safe_tx_gas: Some("500000".to_string()),
+                },
+                simulation: ReplaySimulation {
+                    success,
+                    return_data: Some(return_data.to_string()),
+                    gas_used: "500000".to_string(),
+                    block_number: 1,
+                    logs: Vec::new(),
+                    expected_gas_range: None,
+                    expected_revert_selector: None,
+                },
+                simulation_witness: ReplayWitness {
+                    replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+                    replay_accounts: Some(vec![
+                        caller_account(caller),
+                        target_account(target, code),
+                    ]),
+                    replay_caller: Some(caller.to_string()),
+                    replay_gas_limit: Some(500000),
+                    replay_calldata: None,
+                    witness_only: None,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
 
-        let result = verify_simulation_replay(SimulationReplayInput {
-            chain_id: 100,
-            safe_address: caller.to_string(),
-            transaction: ReplayTransaction {
-                to: target.to_string(),
-                value: "0".to_string(),
+        // Runtime: PUSH1 0x2a PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+        let succeeded = replay_with_code(
+            "0x602a60005260206000f3",
+            true,
+            "0x000000000000000000000000000000000000000000000000000000000000002a",
+        );
+        assert!(succeeded.success, "{succeeded:?}");
+        let receipt = succeeded.replay_receipt.expect("receipt must be present");
+        assert_eq!(receipt.status, 1);
+        assert_eq!(
+            Some(receipt.gas_used),
+            succeeded.comparison.map(|comparison| comparison.gas.replay)
+        );
+        assert_eq!(receipt.logs_bloom, format!("{:#x}", Bloom::default()));
+        assert_eq!(receipt.contract_address, None);
+
+        // Runtime: PUSH1 0x00 PUSH1 0x00 REVERT
+        let reverted = replay_with_code("0x60006000fd", false, "0x");
+        assert!(reverted.success, "{reverted:?}");
+        let receipt = reverted.replay_receipt.expect("receipt must be present");
+        assert_eq!(receipt.status, 0);
+        assert_ne!(receipt.gas_used, "0");
+        assert!(receipt.logs.is_empty());
+    }
+
+    #[test]
+    fn returns_success_when_replay_matches_on_non_mainnet_chain_id() {
+        // Runtime: PUSH1 0x00 PUSH1 0x00 REVERT
+        let code = "0x60006000fd";
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+
+        let result = verify_simulation_replay(SimulationReplayInput {
+            chain_id: 100,
+            safe_address: caller.to_string(),
+            transaction: ReplayTransaction {
+                to: target.to_string(),
+                value: "0".to_string(),
+                data: Some("0x".to_string()),
+                operation: 0,
+                safe_tx_gas: Some("500000".to_string()),
+            },
+            simulation: ReplaySimulation {
+                success: false,
+                return_data: Some("0x".to_string()),
+                gas_used: "500000".to_string(),
+                block_number: 1,
+                logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
+            },
+            simulation_witness: ReplayWitness {
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+                replay_accounts: Some(vec![caller_account(caller), target_account(target, code)]),
+                replay_caller: Some(caller.to_string()),
+                replay_gas_limit: Some(500000),
+                replay_calldata: None,
+                witness_only: None,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        assert!(result.executed);
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.reason, ReplayReason::Matched);
+    }
+
+    #[test]
+    fn uses_caller_nonce_from_witness_account_snapshot() {
+        // Runtime: PUSH1 0x00 PUSH1 0x00 REVERT
+        let code = "0x60006000fd";
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+
+        let result = verify_simulation_replay(SimulationReplayInput {
+            chain_id: 100,
+            safe_address: caller.to_string(),
+            transaction: ReplayTransaction {
+                to: target.to_string(),
+                value: "0".to_string(),
                 data: Some("0x".to_string()),
                 operation: 0,
                 safe_tx_gas: Some("500000".to_string()),
@@ -988,9 +4005,11 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![
                     caller_account_with_nonce(caller, 340),
                     target_account(target, code),
@@ -999,12 +4018,76 @@ mod tests {
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: None,
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
         assert!(result.success, "{result:?}");
-        assert_eq!(result.reason, REASON_REPLAY_MATCHED);
+        assert_eq!(result.reason, ReplayReason::Matched);
+    }
+
+    fn missing_caller_input(require_caller_in_witness: bool) -> SimulationReplayInput {
+        // Runtime: PUSH1 0x00 PUSH1 0x00 REVERT
+        let code = "0x60006000fd";
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+
+        SimulationReplayInput {
+            chain_id: 1,
+            safe_address: caller.to_string(),
+            transaction: ReplayTransaction {
+                to: target.to_string(),
+                value: "0".to_string(),
+                data: Some("0x".to_string()),
+                operation: 0,
+                safe_tx_gas: Some("500000".to_string()),
+            },
+            simulation: ReplaySimulation {
+                success: false,
+                return_data: Some("0x".to_string()),
+                gas_used: "500000".to_string(),
+                block_number: 1,
+                logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
+            },
+            simulation_witness: ReplayWitness {
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+                replay_accounts: Some(vec![target_account(target, code)]),
+                replay_caller: Some(caller.to_string()),
+                replay_gas_limit: Some(500000),
+                replay_calldata: None,
+                witness_only: None,
+                ..Default::default()
+            },
+            require_caller_in_witness,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn synthesizes_missing_caller_when_witness_membership_is_not_required() {
+        let result = verify_simulation_replay(missing_caller_input(false));
+
+        assert!(result.executed);
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.reason, ReplayReason::Matched);
+    }
+
+    #[test]
+    fn rejects_missing_caller_when_witness_membership_is_required() {
+        let result = verify_simulation_replay(missing_caller_input(true));
+
+        assert!(!result.executed);
+        assert!(!result.success);
+        assert_eq!(result.reason, ReplayReason::WitnessIncomplete);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("0x1000000000000000000000000000000000000001"));
     }
 
     #[test]
@@ -1028,9 +4111,11 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![
                     ReplayWitnessAccount {
                         address: caller.to_string(),
@@ -1045,12 +4130,14 @@ mod tests {
                 replay_gas_limit: Some(3_000_000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
         assert!(result.success, "{result:?}");
-        assert_eq!(result.reason, REASON_REPLAY_MATCHED);
+        assert_eq!(result.reason, ReplayReason::Matched);
         assert_eq!(
             result.replay_native_transfers,
             Some(vec![ReplayNativeTransfer {
@@ -1061,6 +4148,103 @@ mod tests {
         );
     }
 
+    fn native_transfer_input(output_number_format: OutputNumberFormat) -> SimulationReplayInput {
+        let caller = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+        let target = "0x5bb21b30e912871d27182e7b7f9c37c888269cb2";
+
+        SimulationReplayInput {
+            chain_id: 100,
+            safe_address: "0xba260842b007fab4119c9747d709119de4257276".to_string(),
+            transaction: ReplayTransaction {
+                to: target.to_string(),
+                value: "1000000000000000000".to_string(),
+                data: Some("0x".to_string()),
+                operation: 0,
+                safe_tx_gas: Some("0".to_string()),
+            },
+            simulation: ReplaySimulation {
+                success: true,
+                return_data: Some("0x".to_string()),
+                gas_used: "500000".to_string(),
+                block_number: 1,
+                logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
+            },
+            simulation_witness: ReplayWitness {
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+                replay_accounts: Some(vec![caller_account(caller), target_account(target, "0x")]),
+                replay_caller: Some(caller.to_string()),
+                replay_gas_limit: Some(3_000_000),
+                replay_calldata: None,
+                witness_only: Some(true),
+                ..Default::default()
+            },
+            output_number_format,
+            include_tx_envelope: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn encodes_native_transfer_values_as_hex_quantities_when_requested() {
+        let result = verify_simulation_replay(native_transfer_input(OutputNumberFormat::Hex));
+
+        assert!(result.success, "{result:?}");
+        let transfers = result.replay_native_transfers.unwrap_or_default();
+        assert_eq!(transfers.len(), 1, "{transfers:?}");
+        assert_eq!(transfers[0].value, "0xde0b6b3a7640000");
+        let comparison = result.comparison.expect("comparison must be populated");
+        assert_eq!(comparison.gas.expected, "0x7a120");
+        assert!(comparison.gas.replay.starts_with("0x"));
+    }
+
+    #[test]
+    fn round_trips_every_numeric_field_between_hex_and_decimal() {
+        let decimal = verify_simulation_replay(native_transfer_input(OutputNumberFormat::Decimal));
+        let hex = verify_simulation_replay(native_transfer_input(OutputNumberFormat::Hex));
+        assert!(decimal.success && hex.success, "{decimal:?} {hex:?}");
+
+        let fields = |result: &SimulationReplayVerificationResult| {
+            let comparison = result.comparison.as_ref().expect("comparison");
+            let receipt = result.replay_receipt.as_ref().expect("receipt");
+            let envelope = result.replay_tx_envelope.as_ref().expect("envelope");
+            let mut fields = vec![
+                result.gas_headroom.clone().expect("gas headroom"),
+                result
+                    .total_native_value_moved
+                    .clone()
+                    .expect("total moved"),
+                comparison.gas.replay.clone(),
+                comparison.gas.expected.clone(),
+                receipt.gas_used.clone(),
+                envelope.value.clone(),
+                envelope.gas_price.clone(),
+            ];
+            fields.extend(
+                result
+                    .replay_native_transfers
+                    .iter()
+                    .flatten()
+                    .map(|transfer| transfer.value.clone()),
+            );
+            fields
+        };
+        let (decimal_fields, hex_fields) = (fields(&decimal), fields(&hex));
+
+        for (decimal_value, hex_value) in decimal_fields.iter().zip(&hex_fields) {
+            assert!(
+                hex_value.trim_start_matches('-').starts_with("0x"),
+                "{hex_value}"
+            );
+            assert_eq!(&OutputNumberFormat::Hex.reformat(decimal_value), hex_value);
+            assert_eq!(
+                &OutputNumberFormat::Decimal.reformat(hex_value),
+                decimal_value
+            );
+        }
+    }
+
     #[test]
     fn returns_mismatch_return_data_in_witness_only_mode() {
         // Runtime: PUSH1 0x2a PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
@@ -1084,20 +4268,24 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![caller_account(caller), target_account(target, code)]),
                 replay_caller: Some(caller.to_string()),
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
         assert!(!result.success);
-        assert_eq!(result.reason, REASON_REPLAY_MISMATCH_RETURN_DATA);
+        assert_eq!(result.reason, ReplayReason::MismatchReturnData);
     }
 
     #[test]
@@ -1123,9 +4311,11 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![
                     caller_account(caller),
                     ReplayWitnessAccount {
@@ -1140,7 +4330,9 @@ mod tests {
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
@@ -1175,9 +4367,11 @@ mod tests {
                 gas_used: "800000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![
                     caller_account(caller),
                     ReplayWitnessAccount {
@@ -1193,7 +4387,9 @@ mod tests {
                 replay_gas_limit: Some(800000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
@@ -1225,9 +4421,11 @@ mod tests {
                 gas_used: "800000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![
                     caller_account(caller),
                     ReplayWitnessAccount {
@@ -1243,7 +4441,9 @@ mod tests {
                 replay_gas_limit: Some(800000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
@@ -1255,6 +4455,7 @@ mod tests {
         assert_eq!(transfers[1].from, transfers[0].to);
         assert_eq!(transfers[1].to, receiver);
         assert_eq!(transfers[1].value, "1");
+        assert_eq!(result.total_native_value_moved.as_deref(), Some("3"));
     }
 
     #[test]
@@ -1278,20 +4479,24 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![caller_account(caller), target_account(target, "0x")]),
                 replay_caller: Some(caller.to_string()),
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: None,
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
         assert!(!result.success);
-        assert_eq!(result.reason, REASON_REPLAY_EXEC_ERROR);
+        assert_eq!(result.reason, ReplayReason::ExecError);
         assert!(result
             .error
             .as_deref()
@@ -1365,9 +4570,11 @@ mod tests {
                         gas_used: "500000".to_string(),
                         block_number: 1,
                         logs: expected_logs.clone(),
+                        expected_gas_range: None,
+                        expected_revert_selector: None,
                     },
                     simulation_witness: ReplayWitness {
-                        replay_block: Some(replay_block("1")),
+                        replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                         replay_accounts: Some(vec![
                             caller_account(caller),
                             target_account(target, code),
@@ -1376,7 +4583,9 @@ mod tests {
                         replay_gas_limit: Some(500000),
                         replay_calldata: None,
                         witness_only: None,
+                        ..Default::default()
                     },
+                    ..Default::default()
                 };
 
                 let started = Instant::now();
@@ -1418,6 +4627,8 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
                 replay_block: None,
@@ -1426,11 +4637,13 @@ mod tests {
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(!result.success);
-        assert_eq!(result.reason, REASON_REPLAY_EXEC_ERROR);
+        assert_eq!(result.reason, ReplayReason::ExecError);
         assert!(result
             .error
             .as_deref()
@@ -1438,6 +4651,90 @@ mod tests {
             .contains("simulationWitness.replayBlock is missing"));
     }
 
+    #[test]
+    fn rejects_zero_replay_block_timestamp() {
+        let now = 1_750_000_000;
+
+        let error =
+            build_replay_block_env(&replay_block("0"), 1, 1, now).expect_err("zero timestamp");
+
+        assert!(
+            error.contains("is not after the chain 1 genesis"),
+            "{error}"
+        );
+        let sepolia = build_replay_block_env(&replay_block("1"), 1, 11_155_111, now)
+            .expect_err("pre-genesis Sepolia timestamp");
+        assert!(sepolia.contains("1633267481"), "{sepolia}");
+        assert!(build_replay_block_env(&replay_block(REPLAY_TIMESTAMP), 1, 1, now).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_replay_block_before_mainnet_genesis() {
+        let now = 1_750_000_000;
+
+        let error = build_replay_block_env(&replay_block("1438269973"), 1, 1, now)
+            .expect_err("pre-genesis mainnet timestamp");
+
+        assert!(
+            error.contains("chain 1 genesis timestamp 1438269973"),
+            "{error}"
+        );
+        assert!(build_replay_block_env(&replay_block("1438269974"), 1, 1, now).is_ok());
+        assert!(build_replay_block_env(&replay_block("1"), 1, 100, now).is_err());
+    }
+
+    #[test]
+    fn rejects_an_implausible_replay_block_gas_limit() {
+        let now = 1_750_000_000;
+        let with_gas_limit = |gas_limit: &str| ReplayBlock {
+            gas_limit: gas_limit.to_string(),
+            ..replay_block(REPLAY_TIMESTAMP)
+        };
+
+        let zero =
+            build_replay_block_env(&with_gas_limit("0"), 1, 1, now).expect_err("zero gas limit");
+        assert!(
+            zero.contains("gasLimit 0 is outside the plausible range"),
+            "{zero}"
+        );
+        assert!(build_replay_block_env(&with_gas_limit("0x4000000000001"), 1, 1, now).is_err());
+        assert!(build_replay_block_env(&with_gas_limit("0x4000000000000"), 1, 1, now).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_replay_block_gas_limit_below_the_tx_gas_limit() {
+        let mut input = push0_replay_input();
+        // Below the 500000 safeTxGas the replay runs with.
+        input
+            .simulation_witness
+            .replay_block
+            .as_mut()
+            .expect("replay block")
+            .gas_limit = "100000".to_string();
+
+        let result = verify_simulation_replay(input);
+
+        assert!(!result.success);
+        assert_eq!(result.reason, ReplayReason::ExecError);
+        assert!(result
+            .error
+            .expect("error")
+            .contains("Replay gas limit 500000 exceeds the replay block gas limit 100000"));
+    }
+
+    #[test]
+    fn rejects_far_future_replay_block_timestamp() {
+        let now = 1_750_000_000;
+        let far_future = (now + 365 * 24 * 60 * 60).to_string();
+
+        let error = build_replay_block_env(&replay_block(&far_future), 1, 1, now)
+            .expect_err("far-future timestamp");
+
+        assert!(error.contains("past the verifier clock"), "{error}");
+        let pending = (now + 12).to_string();
+        assert!(build_replay_block_env(&replay_block(&pending), 1, 1, now).is_ok());
+    }
+
     #[test]
     fn uses_replay_block_timestamp_for_timestamp_opcode_paths() {
         // Runtime: TIMESTAMP PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
@@ -1445,7 +4742,7 @@ mod tests {
         let caller = "0x1000000000000000000000000000000000000001";
         let target = "0x2000000000000000000000000000000000000002";
         let expected_timestamp =
-            "0x000000000000000000000000000000000000000000000000000000000000002a";
+            "0x000000000000000000000000000000000000000000000000000000006553f100";
 
         let result = verify_simulation_replay(SimulationReplayInput {
             chain_id: 1,
@@ -1463,20 +4760,24 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 42,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("42")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![caller_account(caller), target_account(target, code)]),
                 replay_caller: Some(caller.to_string()),
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
 
         assert!(result.executed);
         assert!(result.success, "{result:?}");
-        assert_eq!(result.reason, REASON_REPLAY_MATCHED);
+        assert_eq!(result.reason, ReplayReason::Matched);
     }
 
     #[test]
@@ -1509,9 +4810,11 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![
                     caller_account(caller),
                     target_account(proxy_addr, proxy_code),
@@ -1520,11 +4823,13 @@ mod tests {
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
         assert!(result_without.executed);
         assert!(!result_without.success);
-        assert_eq!(result_without.reason, REASON_REPLAY_MISMATCH_RETURN_DATA);
+        assert_eq!(result_without.reason, ReplayReason::MismatchReturnData);
 
         // With slot 0 + singleton → correct delegatecall → match
         let mut proxy_storage = BTreeMap::new();
@@ -1549,9 +4854,11 @@ mod tests {
                 gas_used: "500000".to_string(),
                 block_number: 1,
                 logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
             },
             simulation_witness: ReplayWitness {
-                replay_block: Some(replay_block("1")),
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
                 replay_accounts: Some(vec![
                     caller_account(caller),
                     ReplayWitnessAccount {
@@ -1567,11 +4874,2194 @@ mod tests {
                 replay_gas_limit: Some(500000),
                 replay_calldata: None,
                 witness_only: Some(true),
+                ..Default::default()
             },
+            ..Default::default()
         });
         assert!(result_with.executed);
         assert!(result_with.success, "{result_with:?}");
-        assert_eq!(result_with.reason, REASON_REPLAY_MATCHED);
+        assert_eq!(result_with.reason, ReplayReason::Matched);
+    }
+
+    #[test]
+    fn rejects_witness_exceeding_replay_account_cap() {
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account(caller),
+                target_account(target, "0x600160005260206000f3"),
+            ]),
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.max_replay_accounts = Some(1);
+
+        let result = verify_simulation_replay(input);
+
+        assert!(!result.executed);
+        assert_eq!(result.reason, ReplayReason::WitnessIncomplete);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("exceeding the limit of 1"));
+    }
+
+    #[test]
+    fn emits_replay_execution_span_with_gas_used() {
+        let output = TraceBuffer::default();
+        let subscriber = trace::subscriber("1", output.clone());
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+        let input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account(caller),
+                target_account(target, "0x600160005260206000f3"),
+            ]),
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            ..Default::default()
+        });
+
+        let result =
+            tracing::subscriber::with_default(subscriber, || verify_simulation_replay(input));
+
+        assert!(result.executed);
+        let output = output.contents();
+        assert!(output.lines().any(|line| {
+            line.contains("replay_execution{accounts=2}")
+                && line.contains("replay executed")
+                && line.contains("gas_used=")
+        }));
+    }
+
+    /// `MakeWriter` collecting the formatted trace output in memory.
+    #[derive(Clone, Default)]
+    struct TraceBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl TraceBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().expect("trace buffer").clone()).expect("utf-8 trace")
+        }
+    }
+
+    impl std::io::Write for TraceBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("trace buffer").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'writer> tracing_subscriber::fmt::MakeWriter<'writer> for TraceBuffer {
+        type Writer = Self;
+
+        fn make_writer(&'writer self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn exposes_witness_blob_base_fee_to_blobbasefee_opcode() {
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+        // BLOBBASEFEE, MSTORE at 0, RETURN 32 bytes
+        let runtime = "0x4a60005260206000f3";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(ReplayBlock {
+                blob_base_fee: Some("7".to_string()),
+                ..replay_block(REPLAY_TIMESTAMP)
+            }),
+            replay_accounts: Some(vec![
+                caller_account(caller),
+                target_account(target, runtime),
+            ]),
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.simulation.return_data =
+            Some("0x0000000000000000000000000000000000000000000000000000000000000007".to_string());
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.reason, ReplayReason::Matched);
+    }
+
+    #[test]
+    fn replays_against_storage_override_and_flags_result() {
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+        // SLOAD slot 0, MSTORE at 0, RETURN 32 bytes
+        let runtime = "0x60005460005260206000f3";
+        let witness = || ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account(caller),
+                ReplayWitnessAccount {
+                    address: target.to_string(),
+                    balance: "0".to_string(),
+                    nonce: 0,
+                    code: runtime.to_string(),
+                    storage: BTreeMap::from([("0x0".to_string(), "0x1".to_string())]),
+                },
+            ]),
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            ..Default::default()
+        };
+
+        let baseline = verify_simulation_replay(proxy_replay_input(witness()));
+        assert!(baseline.success, "{baseline:?}");
+        assert!(!baseline.overridden);
+
+        let mut input = proxy_replay_input(witness());
+        input.state_overrides = vec![ReplayStateOverride {
+            address: target.to_string(),
+            storage: BTreeMap::from([(
+                "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+                "0x2a".to_string(),
+            )]),
+            ..Default::default()
+        }];
+        let overridden = verify_simulation_replay(input);
+
+        assert!(overridden.executed);
+        assert!(overridden.overridden);
+        assert_eq!(overridden.reason, ReplayReason::MismatchReturnData);
+        let comparison = overridden.comparison.expect("comparison");
+        assert_eq!(
+            comparison.return_data.replay,
+            "0x000000000000000000000000000000000000000000000000000000000000002a"
+        );
+    }
+
+    #[test]
+    fn hex_parse_errors_name_the_field_and_quote_the_value() {
+        let bytes_err = parse_hex_bytes("0xZZ", "transaction.data").expect_err("non-hex bytes");
+        assert!(bytes_err.contains("transaction.data"), "{bytes_err}");
+        assert!(bytes_err.contains("(0xZZ)"), "{bytes_err}");
+        assert!(bytes_err.contains("'Z' at offset 0"), "{bytes_err}");
+
+        let quantity_err =
+            parse_hex_quantity("0xZZ", "transaction.value").expect_err("non-hex quantity");
+        assert!(quantity_err.contains("transaction.value"), "{quantity_err}");
+
+        let long = format!("0x{}ZZ", "ab".repeat(40));
+        let long_err = parse_hex_bytes(&long, "simulationWitness.replayCalldata")
+            .expect_err("non-hex long value");
+        assert!(
+            long_err.contains("0xababababababababababab..."),
+            "{long_err}"
+        );
+    }
+
+    #[test]
+    fn reports_witness_account_field_for_malformed_storage_value() {
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+        let result = verify_simulation_replay(proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account(caller),
+                ReplayWitnessAccount {
+                    address: target.to_string(),
+                    balance: "0".to_string(),
+                    nonce: 0,
+                    code: "0x600160005260206000f3".to_string(),
+                    storage: BTreeMap::from([("0x0".to_string(), "0xZZ".to_string())]),
+                },
+            ]),
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            ..Default::default()
+        }));
+
+        assert_eq!(result.reason, ReplayReason::ExecError);
+        let error = result.error.expect("error");
+        assert!(
+            error.contains(&format!(
+                "simulationWitness.replayAccounts[{target}].storage[0x0]"
+            )),
+            "{error}"
+        );
+        assert!(error.contains("0xZZ"), "{error}");
+    }
+
+    fn chain_id_replay_input(chain_id: u64) -> SimulationReplayInput {
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+        // CHAINID, MSTORE at 0, RETURN 32 bytes
+        let runtime = "0x4660005260206000f3";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account(caller),
+                target_account(target, runtime),
+            ]),
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.chain_id = chain_id;
+        input.package_chain_id = Some(chain_id);
+        input.simulation.return_data = Some(format!("{:#066x}", chain_id));
+        input
+    }
+
+    #[test]
+    fn chainid_opcode_returns_replay_chain_id() {
+        for chain_id in [1, 100] {
+            let result = verify_simulation_replay(chain_id_replay_input(chain_id));
+            assert!(result.success, "chain {chain_id}: {result:?}");
+            assert_eq!(result.reason, ReplayReason::Matched);
+        }
+    }
+
+    #[test]
+    fn rejects_replay_chain_id_that_differs_from_package() {
+        let mut input = chain_id_replay_input(100);
+        input.package_chain_id = Some(1);
+
+        let result = verify_simulation_replay(input);
+
+        assert!(!result.executed);
+        assert_eq!(result.reason, ReplayReason::ExecError);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("does not match package chainId 1"));
+    }
+
+    #[test]
+    fn rejects_zero_replay_chain_id() {
+        let result = verify_simulation_replay(chain_id_replay_input(0));
+
+        assert!(!result.executed);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("invalid chainId 0"));
+    }
+
+    #[test]
+    fn diffs_logs_by_index_when_middle_log_data_differs() {
+        let log = |data: &str| ReplaySimulationLog {
+            address: "0x2000000000000000000000000000000000000002".to_string(),
+            topics: vec![
+                "0xdddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd".to_string(),
+            ],
+            data: data.to_string(),
+        };
+        let expected = vec![log("0x01"), log("0x02"), log("0x03")];
+        let replay = vec![log("0x01"), log("0xff"), log("0x03")];
+
+        let diff = diff_logs(&replay, &expected);
+
+        assert_eq!(
+            diff,
+            vec![ReplayLogDiff {
+                index: 1,
+                kind: ReplayLogDiffKind::Changed,
+                replay: Some(log("0xff")),
+                expected: Some(log("0x02")),
+            }]
+        );
+
+        let extra = diff_logs(&expected, &expected[..2]);
+        assert_eq!(extra.len(), 1);
+        assert_eq!(extra[0].index, 2);
+        assert_eq!(extra[0].kind, ReplayLogDiffKind::Added);
+        let missing = diff_logs(&expected[..2], &expected);
+        assert_eq!(missing[0].kind, ReplayLogDiffKind::Removed);
+    }
+
+    #[test]
+    fn mainnet_schedule_selects_pre_push0_spec_before_shanghai() {
+        // Block 16_000_000 (Nov 2022): post-merge, pre-Shanghai.
+        let spec = spec_at_block(MAINNET_FORK_SCHEDULE, 16_000_000, 1_668_000_000);
+
+        assert_eq!(spec, SpecId::MERGE);
+        assert!(!spec.is_enabled_in(SpecId::SHANGHAI));
+    }
+
+    #[test]
+    fn mainnet_schedule_selects_cancun_after_dencun() {
+        // Block 19_500_000 (Mar 2024): after Dencun, before Pectra.
+        let spec = spec_at_block(MAINNET_FORK_SCHEDULE, 19_500_000, 1_711_000_000);
+
+        assert_eq!(spec, SpecId::CANCUN);
+    }
+
+    fn push0_replay_input() -> SimulationReplayInput {
+        // Runtime: PUSH0 PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        let code = "0x5f5f5260205ff3";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]),
+            replay_caller: Some("0x1000000000000000000000000000000000000001".to_string()),
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.simulation.return_data = Some(format!("0x{}", "00".repeat(32)));
+        input
+    }
+
+    #[test]
+    fn pinned_pre_shanghai_replay_rejects_push0() {
+        let latest = verify_simulation_replay(push0_replay_input());
+        assert!(latest.success, "{latest:?}");
+
+        let mut input = push0_replay_input();
+        input.fork_schedule = Some(vec![
+            ReplayForkActivation {
+                fork: "london".to_string(),
+                block: Some(0),
+                timestamp: None,
+            },
+            ReplayForkActivation {
+                fork: "shanghai".to_string(),
+                block: None,
+                timestamp: Some(2_000_000_000),
+            },
+        ]);
+        let pinned = verify_simulation_replay(input);
+
+        assert!(pinned.executed, "{pinned:?}");
+        assert_eq!(pinned.reason, ReplayReason::MismatchSuccess);
+    }
+
+    #[test]
+    fn reports_spec_used_for_pinned_and_default_replays() {
+        let default = verify_simulation_replay(push0_replay_input());
+        assert_eq!(
+            default.spec_used.as_deref(),
+            Some(spec_name(SpecId::default()))
+        );
+
+        let mut input = push0_replay_input();
+        input.fork_schedule = Some(vec![ReplayForkActivation {
+            fork: "shanghai".to_string(),
+            block: Some(0),
+            timestamp: None,
+        }]);
+        let pinned = verify_simulation_replay(input);
+
+        assert!(pinned.success, "{pinned:?}");
+        assert_eq!(pinned.spec_used.as_deref(), Some("shanghai"));
+        for name in ["london", "cancun", "prague"] {
+            let spec = parse_spec_name(name).expect("known fork");
+            assert_eq!(spec_name(spec), name);
+        }
+    }
+
+    #[test]
+    fn rejects_fork_activation_with_both_block_and_timestamp() {
+        let error = parse_fork_schedule(&[ReplayForkActivation {
+            fork: "cancun".to_string(),
+            block: Some(1),
+            timestamp: Some(1),
+        }])
+        .unwrap_err();
+
+        assert!(error.contains("forkSchedule[0]"), "{error}");
+    }
+
+    #[test]
+    fn rejects_witness_only_package_carrying_simulation_logs() {
+        let mut input = push0_replay_input();
+        input.simulation.logs = vec![ReplaySimulationLog {
+            address: "0x2000000000000000000000000000000000000002".to_string(),
+            topics: Vec::new(),
+            data: "0x".to_string(),
+        }];
+
+        let result = verify_simulation_replay(input);
+
+        assert!(!result.executed);
+        assert_eq!(result.reason, ReplayReason::WitnessOnlyLogsPresent);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("witnessOnly"));
+    }
+
+    fn codeless_target_input(require_target_code: bool) -> SimulationReplayInput {
+        let caller = "0x1000000000000000000000000000000000000001";
+        SimulationReplayInput {
+            require_target_code,
+            ..proxy_replay_input(ReplayWitness {
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+                replay_accounts: Some(vec![
+                    caller_account(caller),
+                    target_account("0x2000000000000000000000000000000000000002", "0x"),
+                ]),
+                replay_caller: Some(caller.to_string()),
+                witness_only: Some(true),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn rejects_calldata_to_codeless_target_when_target_code_is_required() {
+        let result = verify_simulation_replay(codeless_target_input(true));
+
+        assert!(!result.executed);
+        assert_eq!(result.reason, ReplayReason::WitnessIncomplete);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("has no code"));
+    }
+
+    #[test]
+    fn replays_calldata_to_codeless_target_as_transfer_by_default() {
+        let result = verify_simulation_replay(codeless_target_input(false));
+
+        assert!(result.executed, "{result:?}");
+        assert_eq!(result.reason, ReplayReason::MismatchReturnData);
+    }
+
+    #[test]
+    fn traces_storage_ops_in_execution_order() {
+        // SSTORE(1, 0x2a) SSTORE(2, 7) SLOAD(1), then return the loaded word.
+        let code = "0x602a600155600760025560015460005260206000f3";
+        let caller = "0x1000000000000000000000000000000000000001";
+        let target = "0x2000000000000000000000000000000000000002";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![caller_account(caller), target_account(target, code)]),
+            replay_caller: Some(caller.to_string()),
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.simulation.return_data = Some(format!("0x{}2a", "00".repeat(31)));
+        input.trace_storage_ops = true;
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+        let op = |pc: usize, op: &str, slot: &str, value: &str| ReplayStorageOp {
+            pc,
+            op: op.to_string(),
+            address: target.to_string(),
+            slot: slot.to_string(),
+            value: value.to_string(),
+        };
+        assert_eq!(
+            result.storage_ops,
+            Some(vec![
+                op(4, "SSTORE", "0x1", "0x2a"),
+                op(9, "SSTORE", "0x2", "0x7"),
+                op(12, "SLOAD", "0x1", "0x2a"),
+            ])
+        );
+    }
+
+    #[test]
+    fn omits_storage_ops_unless_requested() {
+        let result = verify_simulation_replay(push0_replay_input());
+
+        assert!(result.executed);
+        assert_eq!(result.storage_ops, None);
+    }
+
+    fn proxy_replay_input(simulation_witness: ReplayWitness) -> SimulationReplayInput {
+        SimulationReplayInput {
+            chain_id: 100,
+            safe_address: "0x1000000000000000000000000000000000000001".to_string(),
+            transaction: ReplayTransaction {
+                to: "0x2000000000000000000000000000000000000002".to_string(),
+                value: "0".to_string(),
+                data: Some("0xdeadbeef".to_string()),
+                operation: 0,
+                safe_tx_gas: Some("500000".to_string()),
+            },
+            simulation: ReplaySimulation {
+                success: true,
+                return_data: Some(
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
+                        .to_string(),
+                ),
+                gas_used: "500000".to_string(),
+                block_number: 1,
+                logs: Vec::new(),
+                expected_gas_range: None,
+                expected_revert_selector: None,
+            },
+            simulation_witness,
+            ..Default::default()
+        }
+    }
+
+    const PROXY_CODE: &str = "0x608060405273ffffffffffffffffffffffffffffffffffffffff600054167fa619486e0000000000000000000000000000000000000000000000000000000060003514156050578060005260206000f35b3660008037600080366000845af43d6000803e60008114156070573d6000fd5b3d6000f3fe";
+
+    #[test]
+    fn prestate_diff_replay_matches_equivalent_full_account_replay() {
+        let caller = "0x1000000000000000000000000000000000000001";
+        let proxy_addr = "0x2000000000000000000000000000000000000002";
+        let singleton_addr = "0x3000000000000000000000000000000000000003";
+        // Minimal singleton: returns abi.encode(true) for any call
+        let singleton_code = "0x600160005260206000f3";
+        let singleton_slot_value = format!("0x000000000000000000000000{}", &singleton_addr[2..]);
+
+        let mut proxy_storage = BTreeMap::new();
+        proxy_storage.insert(
+            "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+            singleton_slot_value.clone(),
+        );
+        let full = verify_simulation_replay(proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account(caller),
+                ReplayWitnessAccount {
+                    address: proxy_addr.to_string(),
+                    balance: "0".to_string(),
+                    nonce: 0,
+                    code: PROXY_CODE.to_string(),
+                    storage: proxy_storage,
+                },
+                target_account(singleton_addr, singleton_code),
+            ]),
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            ..Default::default()
+        }));
+
+        // The base carries a stale singleton pointer; the override fixes it
+        // with a non-canonical slot key.
+        let mut base = BTreeMap::new();
+        base.insert(
+            caller.to_string(),
+            ReplayPrestateAccount {
+                balance: Some("1000000000000000000".to_string()),
+                ..Default::default()
+            },
+        );
+        base.insert(
+            proxy_addr.to_string(),
+            ReplayPrestateAccount {
+                code: Some(PROXY_CODE.to_string()),
+                storage: BTreeMap::from([(
+                    "0x0".to_string(),
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        .to_string(),
+                )]),
+                ..Default::default()
+            },
+        );
+        base.insert(
+            singleton_addr.to_string(),
+            ReplayPrestateAccount {
+                code: Some(singleton_code.to_string()),
+                ..Default::default()
+            },
+        );
+        let overrides = BTreeMap::from([(
+            proxy_addr.to_string(),
+            ReplayPrestateAccount {
+                storage: BTreeMap::from([("0x00".to_string(), singleton_slot_value)]),
+                ..Default::default()
+            },
+        )]);
+        let diff = verify_simulation_replay(proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: None,
+            replay_caller: Some(caller.to_string()),
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            replay_prestate_diff: Some(ReplayPrestateDiff { base, overrides }),
+            ..Default::default()
+        }));
+
+        assert!(full.success, "{full:?}");
+        assert_eq!(diff, full);
+    }
+
+    #[test]
+    fn replay_output_is_independent_of_witness_account_order() {
+        let caller = "0x1000000000000000000000000000000000000001";
+        let proxy_addr = "0x2000000000000000000000000000000000000002";
+        let singleton_addr = "0x3000000000000000000000000000000000000003";
+        let accounts = || {
+            vec![
+                caller_account(caller),
+                ReplayWitnessAccount {
+                    address: proxy_addr.to_string(),
+                    balance: "0".to_string(),
+                    nonce: 0,
+                    code: PROXY_CODE.to_string(),
+                    storage: BTreeMap::from([(
+                        "0x0".to_string(),
+                        format!("0x{}", &singleton_addr[2..]),
+                    )]),
+                },
+                target_account(singleton_addr, "0x600160005260206000f3"),
+            ]
+        };
+        let serialized_replay = |order: &[usize]| {
+            let mut pool: Vec<Option<ReplayWitnessAccount>> =
+                accounts().into_iter().map(Some).collect();
+            let shuffled = order
+                .iter()
+                .map(|&index| pool[index].take().expect("permutation index"))
+                .collect();
+            let result = verify_simulation_replay(proxy_replay_input(ReplayWitness {
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+                replay_accounts: Some(shuffled),
+                replay_caller: Some(caller.to_string()),
+                replay_gas_limit: Some(500000),
+                replay_calldata: None,
+                witness_only: Some(true),
+                ..Default::default()
+            }));
+            assert!(result.success, "{result:?}");
+            serde_json::to_string(&result).expect("serialize replay result")
+        };
+
+        let baseline = serialized_replay(&[0, 1, 2]);
+        for order in [[0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]] {
+            assert_eq!(serialized_replay(&order), baseline, "order {order:?}");
+        }
+    }
+
+    fn push0_replay_with_gas_range(
+        offset_min: i64,
+        offset_max: i64,
+    ) -> SimulationReplayVerificationResult {
+        let baseline = verify_simulation_replay(push0_replay_input());
+        let gas_used: i64 = baseline
+            .replay_receipt
+            .expect("replay receipt")
+            .gas_used
+            .parse()
+            .expect("decimal gas");
+        let mut input = push0_replay_input();
+        input.simulation.expected_gas_range = Some(ReplayGasRange {
+            min: (gas_used + offset_min).to_string(),
+            max: (gas_used + offset_max).to_string(),
+        });
+        verify_simulation_replay(input)
+    }
+
+    #[test]
+    fn accepts_replay_gas_inside_expected_range() {
+        let result = push0_replay_with_gas_range(-10, 10);
+
+        assert!(result.success, "{result:?}");
+        assert!(result.comparison.expect("comparison").gas.matched);
+        assert!(push0_replay_with_gas_range(0, 0).success);
+    }
+
+    #[test]
+    fn rejects_conflicting_storage_keys_for_the_same_slot() {
+        let field = "simulationWitness.replayAccounts[0x01]";
+        let storage = BTreeMap::from([
+            ("0x01".to_string(), "0x05".to_string()),
+            (format!("0x{}01", "00".repeat(31)), "0x06".to_string()),
+        ]);
+
+        let error = canonical_seed_storage(field, &storage).expect_err("conflicting slot");
+
+        assert!(error.contains("conflicting entries for slot"), "{error}");
+        assert!(
+            error.contains(&format!("0x{}01", "00".repeat(31))),
+            "{error}"
+        );
+
+        let agreeing = BTreeMap::from([
+            ("0x1".to_string(), "0x05".to_string()),
+            (format!("0x{}01", "00".repeat(31)), "0x5".to_string()),
+        ]);
+        let seeded = canonical_seed_storage(field, &agreeing).expect("agreeing duplicates");
+        assert_eq!(seeded, BTreeMap::from([(U256::from(1), U256::from(5))]));
+    }
+
+    /// Target runs `CREATE2(value 0, init code 0x00, salt 0x2a)` and stops.
+    fn create2_replay_input(expected: Vec<String>) -> SimulationReplayInput {
+        let code = "0x602a600160006000f500";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]),
+            replay_caller: Some("0x1000000000000000000000000000000000000001".to_string()),
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.simulation.return_data = None;
+        input.expected_create2_addresses = Some(expected);
+        input
+    }
+
+    fn expected_create2_address() -> Address {
+        compute_create2_address(
+            Address::from_str("0x2000000000000000000000000000000000000002").unwrap(),
+            B256::from(U256::from(0x2a)),
+            keccak256([0x00]),
+        )
+    }
+
+    #[test]
+    fn matches_create2_deployment_against_expected_address() {
+        let expected = format!("{:#x}", expected_create2_address());
+
+        let result = verify_simulation_replay(create2_replay_input(vec![expected.clone()]));
+
+        assert!(result.success, "{result:?}");
+        let deployments = result.create2_deployments.expect("deployments");
+        assert_eq!(deployments.len(), 1);
+        assert_eq!(deployments[0].address, expected);
+        assert_eq!(
+            deployments[0].deployer,
+            "0x2000000000000000000000000000000000000002"
+        );
+    }
+
+    #[test]
+    fn flags_create2_deployment_at_unexpected_address() {
+        let result = verify_simulation_replay(create2_replay_input(vec![
+            "0x000000000000000000000000000000000000dead".to_string(),
+        ]));
+
+        assert!(result.executed);
+        assert_eq!(result.reason, ReplayReason::MismatchCreate2);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("not in expectedCreate2Addresses"));
+    }
+
+    #[test]
+    fn echoes_native_currency_metadata_into_the_result() {
+        let mut input = push0_replay_input();
+        input.native_decimals = Some(18);
+        input.native_symbol = Some("xDAI".to_string());
+
+        let result = verify_simulation_replay(input);
+        let json: serde_json::Value =
+            serde_json::to_value(&result).expect("serialize replay result");
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.native_decimals, Some(18));
+        assert_eq!(result.native_symbol.as_deref(), Some("xDAI"));
+        assert_eq!(json["nativeDecimals"], 18);
+        assert_eq!(json["nativeSymbol"], "xDAI");
+
+        let failed = verify_simulation_replay(SimulationReplayInput {
+            native_symbol: Some("ETH".to_string()),
+            ..Default::default()
+        });
+        assert!(!failed.executed);
+        assert_eq!(failed.native_symbol.as_deref(), Some("ETH"));
+    }
+
+    /// Target CALLs `0x..dead`, which the witness omits, then stops.
+    fn call_unseeded_replay_input(require_complete_witness: bool) -> SimulationReplayInput {
+        let code = format!(
+            "0x5f5f5f5f5f73{}5af100",
+            "000000000000000000000000000000000000dead"
+        );
+        let mut input = push0_replay_input();
+        input.simulation_witness.replay_accounts = Some(vec![
+            caller_account("0x1000000000000000000000000000000000000001"),
+            target_account("0x2000000000000000000000000000000000000002", &code),
+        ]);
+        input.simulation.return_data = None;
+        input.require_complete_witness = require_complete_witness;
+        input
+    }
+
+    #[test]
+    fn strict_witness_rejects_call_to_account_missing_from_witness() {
+        let lenient = verify_simulation_replay(call_unseeded_replay_input(false));
+        assert!(lenient.success, "{lenient:?}");
+
+        let strict = verify_simulation_replay(call_unseeded_replay_input(true));
+
+        assert!(strict.executed);
+        assert_eq!(strict.reason, ReplayReason::WitnessIncomplete);
+        let error = strict.error.expect("error");
+        assert!(
+            error.contains("0x000000000000000000000000000000000000dead"),
+            "{error}"
+        );
+        assert!(!error.contains("0x2000000000000000000000000000000000000002"));
+    }
+
+    #[test]
+    fn every_executed_mismatch_carries_replay_effects() {
+        let mut success = push0_replay_input();
+        success.simulation.success = false;
+        let mut return_data = push0_replay_input();
+        return_data.simulation.return_data = Some("0x01".to_string());
+        let mut logs = push0_replay_input();
+        logs.simulation_witness.witness_only = Some(false);
+        logs.simulation.logs = vec![ReplaySimulationLog {
+            address: "0x2000000000000000000000000000000000000002".to_string(),
+            topics: Vec::new(),
+            data: "0x".to_string(),
+        }];
+        let mut gas = push0_replay_input();
+        gas.simulation.gas_used = "1".to_string();
+        let create2 = create2_replay_input(vec![
+            "0x000000000000000000000000000000000000dead".to_string()
+        ]);
+
+        for (input, reason) in [
+            (success, ReplayReason::MismatchSuccess),
+            (return_data, ReplayReason::MismatchReturnData),
+            (logs, ReplayReason::MismatchLogs),
+            (gas, ReplayReason::MismatchGas),
+            (create2, ReplayReason::MismatchCreate2),
+            (
+                call_unseeded_replay_input(true),
+                ReplayReason::WitnessIncomplete,
+            ),
+        ] {
+            let result = verify_simulation_replay(input);
+
+            assert_eq!(result.reason, reason, "{result:?}");
+            assert!(result.executed, "{reason}");
+            assert!(result.replay_logs.is_some(), "{reason}");
+            assert!(result.replay_native_transfers.is_some(), "{reason}");
+            assert!(result.replay_receipt.is_some(), "{reason}");
+        }
+    }
+
+    #[test]
+    fn prunes_witness_accounts_the_replay_never_touches() {
+        let mut input = push0_replay_input();
+        let mut target = target_account(
+            "0x2000000000000000000000000000000000000002",
+            "0x5f545f5260205ff3",
+        );
+        target.storage = BTreeMap::from([
+            ("0x0".to_string(), "0x0".to_string()),
+            ("0x1".to_string(), "0x7".to_string()),
+        ]);
+        input.simulation_witness.replay_accounts = Some(vec![
+            caller_account("0x1000000000000000000000000000000000000001"),
+            target,
+            target_account("0x3000000000000000000000000000000000000003", "0x00"),
+        ]);
+
+        let pruned = prune_replay_witness(&input).expect("pruned witness");
+
+        let addresses: Vec<&str> = pruned
+            .iter()
+            .map(|account| account.address.as_str())
+            .collect();
+        assert_eq!(
+            addresses,
+            [
+                "0x1000000000000000000000000000000000000001",
+                "0x2000000000000000000000000000000000000002"
+            ]
+        );
+        assert_eq!(
+            pruned[1].storage,
+            BTreeMap::from([("0x0".to_string(), "0x0".to_string())])
+        );
+
+        input.simulation_witness.replay_accounts = Some(pruned);
+        assert!(verify_simulation_replay(input).success);
+    }
+
+    #[test]
+    fn reports_every_malformed_input_field_at_once() {
+        let mut input = push0_replay_input();
+        input.safe_address = "0x1234".to_string();
+        input.transaction.value = "0xZZ".to_string();
+        input.transaction.data = Some("0xZZ".to_string());
+        input.simulation_witness.replay_caller = Some("caller".to_string());
+
+        let result = verify_simulation_replay(input);
+
+        assert!(!result.executed);
+        assert_eq!(result.reason, ReplayReason::InvalidInput);
+        let input_errors = result.input_errors.expect("input errors");
+        assert_eq!(input_errors.len(), 4, "{input_errors:?}");
+        for field in [
+            "safeAddress",
+            "transaction.value",
+            "transaction.data",
+            "simulationWitness.replayCaller",
+        ] {
+            assert!(
+                input_errors.iter().any(|error| error.contains(field)),
+                "{field} missing from {input_errors:?}"
+            );
+        }
+        assert!(verify_simulation_replay(push0_replay_input())
+            .input_errors
+            .is_none());
+    }
+
+    #[test]
+    fn groups_replay_logs_by_emitting_contract_in_emission_order() {
+        // Target: LOG0(1), CALL 0x3000…03, LOG0(2), STOP. Callee: LOG0(3), STOP.
+        let target = "0x60015f5260205fa05f5f5f5f5f733000000000000000000000000000000000000003\
+                      5af15060025f5260205fa000";
+        let callee = "0x60035f5260205fa000";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", target),
+                target_account("0x3000000000000000000000000000000000000003", callee),
+            ]),
+            replay_caller: Some("0x1000000000000000000000000000000000000001".to_string()),
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.simulation.return_data = None;
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+        let word = |value: u8| format!("0x{}{value:02x}", "00".repeat(31));
+        let groups = result.logs_by_address.expect("grouped logs");
+        let data = |address: &str| -> Vec<String> {
+            groups[address].iter().map(|log| log.data.clone()).collect()
+        };
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            data("0x2000000000000000000000000000000000000002"),
+            vec![word(1), word(2)]
+        );
+        assert_eq!(
+            data("0x3000000000000000000000000000000000000003"),
+            vec![word(3)]
+        );
+        assert_eq!(result.replay_logs.expect("flat logs").len(), 3);
+    }
+
+    #[test]
+    fn caller_nonce_override_pins_top_level_create_address() {
+        let caller =
+            Address::from_str("0x1000000000000000000000000000000000000001").expect("caller");
+        let created_with = |nonce: Option<u64>| {
+            let mut input = proxy_replay_input(ReplayWitness {
+                replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+                replay_accounts: Some(vec![caller_account(
+                    "0x1000000000000000000000000000000000000001",
+                )]),
+                replay_caller: Some("0x1000000000000000000000000000000000000001".to_string()),
+                replay_caller_nonce: nonce,
+                ..Default::default()
+            });
+            // Init code: STOP, deploying empty runtime code.
+            input.transaction.to = String::new();
+            input.transaction.data = Some("0x00".to_string());
+            input.simulation.return_data = None;
+            let result = verify_simulation_replay(input);
+            assert!(result.success, "{result:?}");
+            result
+                .replay_receipt
+                .and_then(|receipt| receipt.contract_address)
+                .expect("created address")
+        };
+
+        assert_eq!(created_with(None), format!("{:#x}", caller.create(0)));
+        assert_eq!(created_with(Some(7)), format!("{:#x}", caller.create(7)));
+    }
+
+    #[test]
+    fn reports_effects_source_for_witness_only_and_full_replays() {
+        let witness_only = verify_simulation_replay(push0_replay_input());
+        assert!(witness_only.success, "{witness_only:?}");
+        assert_eq!(
+            witness_only.effects_source,
+            Some(ReplayEffectsSource::Replay)
+        );
+
+        let mut full = push0_replay_input();
+        full.simulation_witness.witness_only = Some(false);
+        let full = verify_simulation_replay(full);
+        assert!(full.success, "{full:?}");
+        assert_eq!(full.effects_source, Some(ReplayEffectsSource::Packaged));
+        assert_eq!(
+            serde_json::to_value(full.effects_source).expect("serialize"),
+            "packaged"
+        );
+    }
+
+    /// State root after `push0_replay_input` with the caller left holding
+    /// `caller_balance`. The caller pays gas at the 1 wei base fee and bumps
+    /// its nonce; the PUSH0 target is untouched and the coinbase stays empty.
+    fn push0_post_state_root(caller_balance: u64) -> B256 {
+        state_root(&BTreeMap::from([
+            (
+                Address::from_str("0x1000000000000000000000000000000000000001").expect("caller"),
+                StateAccount {
+                    nonce: 1,
+                    balance: U256::from(caller_balance),
+                    code_hash: KECCAK_EMPTY,
+                    storage: BTreeMap::new(),
+                },
+            ),
+            (
+                Address::from_str("0x2000000000000000000000000000000000000002").expect("target"),
+                StateAccount {
+                    nonce: 0,
+                    balance: U256::ZERO,
+                    code_hash: keccak256([0x5f, 0x5f, 0x52, 0x60, 0x20, 0x5f, 0xf3]),
+                    storage: BTreeMap::new(),
+                },
+            ),
+        ]))
+    }
+
+    fn push0_gas_used() -> u64 {
+        verify_simulation_replay(push0_replay_input())
+            .replay_receipt
+            .expect("receipt")
+            .gas_used
+            .parse()
+            .expect("decimal gas")
+    }
+
+    #[test]
+    fn verifies_post_state_root_over_a_complete_witness() {
+        let expected_root =
+            |gas_used: u64| push0_post_state_root(1_000_000_000_000_000_000 - gas_used);
+        let gas_used = push0_gas_used();
+
+        let mut matching = push0_replay_input();
+        matching.verify_post_state_root = true;
+        matching.expected_post_state_root = Some(format!("{:#x}", expected_root(gas_used)));
+        let result = verify_simulation_replay(matching);
+        assert!(result.success, "{result:?}");
+        assert_eq!(
+            result.post_state_root,
+            Some(format!("{:#x}", expected_root(gas_used)))
+        );
+
+        let mut mismatching = push0_replay_input();
+        mismatching.verify_post_state_root = true;
+        mismatching.expected_post_state_root = Some(format!("{:#x}", expected_root(0)));
+        let result = verify_simulation_replay(mismatching);
+        assert_eq!(result.reason, ReplayReason::MismatchPostStateRoot);
+        assert!(result
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("does not match expectedPostStateRoot")));
+    }
+
+    #[test]
+    fn post_state_root_uses_the_witness_caller_balance() {
+        // 500000 gas at 1 wei needs more than the caller holds, so the replay
+        // tops it up; the root must still reflect the witness balance.
+        let gas_used = push0_gas_used();
+        let with_caller_balance = |balance: u64| {
+            let mut input = push0_replay_input();
+            input
+                .simulation_witness
+                .replay_accounts
+                .as_mut()
+                .expect("accounts")[0]
+                .balance = balance.to_string();
+            input.verify_post_state_root = true;
+            input.expected_post_state_root =
+                Some(format!("{:#x}", push0_post_state_root(100_000 - gas_used)));
+            input
+        };
+
+        let result = verify_simulation_replay(with_caller_balance(100_000));
+        assert!(result.success, "{result:?}");
+
+        let result = verify_simulation_replay(with_caller_balance(gas_used - 1));
+        assert_eq!(result.reason, ReplayReason::ExecError);
+        assert!(result
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("spent more than its witness balance")));
+    }
+
+    fn custom_error_revert_input(expected_selector: &str) -> SimulationReplayInput {
+        // Runtime: PUSH4 0x12345678 PUSH1 0xe0 SHL PUSH0 MSTORE PUSH1 0x04 PUSH0 REVERT
+        let code = "0x631234567860e01b5f5260045ffd";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]),
+            replay_caller: Some("0x1000000000000000000000000000000000000001".to_string()),
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.simulation.success = false;
+        input.simulation.return_data = Some("0x12345678".to_string());
+        input.simulation.expected_revert_selector = Some(expected_selector.to_string());
+        input
+    }
+
+    #[test]
+    fn accepts_revert_with_the_expected_custom_error_selector() {
+        let result = verify_simulation_replay(custom_error_revert_input("0x12345678"));
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.reason, ReplayReason::Matched);
+    }
+
+    #[test]
+    fn rejects_revert_with_a_different_custom_error_selector() {
+        let result = verify_simulation_replay(custom_error_revert_input("0xdeadbeef"));
+
+        assert!(!result.success);
+        assert_eq!(result.reason, ReplayReason::MismatchReturnData);
+        assert!(result
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("selector 0x12345678")));
+    }
+
+    #[test]
+    fn canonical_json_is_identical_for_logically_equal_results() {
+        let log = |address: &str, data: &str| ReplaySimulationLog {
+            address: address.into(),
+            topics: vec![],
+            data: data.into(),
+        };
+        let mut left = failed_result(true, ReplayReason::MismatchLogs, "logs differ".into());
+        left.replay_logs = Some(vec![log(
+            "0xAAAA000000000000000000000000000000000001",
+            "0xABCD",
+        )]);
+        left.logs_by_address = left.replay_logs.as_deref().map(group_logs_by_address);
+        let mut right = failed_result(true, ReplayReason::MismatchLogs, "logs differ".into());
+        right.replay_logs = Some(vec![log(
+            "0xaaaa000000000000000000000000000000000001",
+            "0xabcd",
+        )]);
+        right.logs_by_address = right.replay_logs.as_deref().map(group_logs_by_address);
+
+        let canonical = canonical_json(&left).expect("canonical json");
+
+        assert_eq!(canonical, canonical_json(&right).expect("canonical json"));
+        assert!(
+            canonical.starts_with("{\"comparison\":null,"),
+            "{canonical}"
+        );
+        assert!(canonical.contains("\"0xaaaa000000000000000000000000000000000001\":["));
+    }
+
+    #[test]
+    fn gas_only_mismatch_reports_effects_matched_overall_status() {
+        let mut input = push0_replay_input();
+        input.simulation.gas_used = "1".into();
+
+        let result = verify_simulation_replay(input);
+
+        assert!(!result.success);
+        assert_eq!(result.reason, ReplayReason::MismatchGas);
+        assert_eq!(
+            result.overall_status,
+            ReplayOverallStatus::EffectsMatchedGasDiffers
+        );
+        assert_eq!(
+            serde_json::to_value(result.overall_status).expect("status"),
+            serde_json::json!("effects-matched-gas-differs")
+        );
+        assert_eq!(
+            verify_simulation_replay(push0_replay_input()).overall_status,
+            ReplayOverallStatus::Matched
+        );
+    }
+
+    #[test]
+    fn gas_heavy_call_uses_the_chain_default_gas_limit() {
+        // PUSH3 160000; loop { n -= 1 } while n != 0 (~26 gas per iteration,
+        // ~4.2M in total); then PUSH0 PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN.
+        let code = "0x620271005b60019003806004575f5f5260205ff3";
+        let heavy_input = |replay_gas_limit: Option<u64>| {
+            let mut input = push0_replay_input();
+            input.transaction.safe_tx_gas = None;
+            input.simulation.gas_used = "5000000".into();
+            input.simulation_witness.replay_gas_limit = replay_gas_limit;
+            input.simulation_witness.replay_accounts = Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]);
+            input
+        };
+
+        let chain_default = verify_simulation_replay(heavy_input(None));
+        assert!(chain_default.success, "{chain_default:?}");
+        let gas_used: u64 = chain_default
+            .replay_receipt
+            .expect("replay receipt")
+            .gas_used
+            .parse()
+            .expect("decimal gas");
+        assert!(gas_used > 3_000_000, "{gas_used}");
+
+        let legacy_default = verify_simulation_replay(heavy_input(Some(3_000_000)));
+        assert!(legacy_default.executed, "{legacy_default:?}");
+        assert_eq!(legacy_default.reason, ReplayReason::MismatchSuccess);
+    }
+
+    #[test]
+    fn reports_nonce_diffs_for_a_create_deployer() {
+        let factory = "0x2000000000000000000000000000000000000002";
+        let init_code = hex::decode("60006000f3").expect("valid init code");
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account(factory, &build_create_runtime(&init_code, 0)),
+            ]),
+            replay_caller: Some("0x1000000000000000000000000000000000000001".to_string()),
+            witness_only: Some(true),
+            ..Default::default()
+        });
+        input.simulation.return_data = Some("0x".to_string());
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+        let created = format!(
+            "{:#x}",
+            Address::from_str(factory).expect("address").create(0)
+        );
+        let diffs = result.nonce_diffs.expect("nonce diffs");
+        let diff_for = |address: &str| diffs.iter().find(|diff| diff.address == address);
+        assert_eq!(
+            diff_for(factory),
+            Some(&ReplayNonceDiff {
+                address: factory.to_string(),
+                before: 0,
+                after: 1,
+            })
+        );
+        assert_eq!(
+            diff_for(&created).map(|diff| (diff.before, diff.after)),
+            Some((0, 1))
+        );
+        assert_eq!(
+            diff_for("0x1000000000000000000000000000000000000001")
+                .map(|diff| (diff.before, diff.after)),
+            Some((0, 1))
+        );
+
+        // PUSH0 PUSH0 REVERT
+        let mut reverting = push0_replay_input();
+        reverting.simulation.success = false;
+        reverting.simulation.return_data = Some("0x".to_string());
+        reverting.simulation_witness.replay_accounts = Some(vec![
+            caller_account("0x1000000000000000000000000000000000000001"),
+            target_account("0x2000000000000000000000000000000000000002", "0x5f5ffd"),
+        ]);
+        let reverted = verify_simulation_replay(reverting);
+        assert!(reverted.executed, "{reverted:?}");
+        assert_eq!(reverted.nonce_diffs, Some(Vec::new()));
+    }
+
+    #[test]
+    fn profiles_sstore_heavy_replays_as_storage_dominated() {
+        // Three SSTOREs to fresh slots, then PUSH0 PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN.
+        let code = "0x60015f55600160015560016002555f5f5260205ff3";
+        let mut input = push0_replay_input();
+        input.profile_gas = true;
+        input.simulation_witness.replay_accounts = Some(vec![
+            caller_account("0x1000000000000000000000000000000000000001"),
+            target_account("0x2000000000000000000000000000000000000002", code),
+        ]);
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+        let profile = result.gas_profile.expect("gas profile");
+        let storage = profile["storage"];
+        let rest: u64 = profile
+            .iter()
+            .filter(|(category, _)| category.as_str() != "storage")
+            .map(|(_, gas)| gas)
+            .sum();
+        assert!(storage >= 3 * 22_100, "{profile:?}");
+        assert!(storage > rest, "{profile:?}");
+        assert_eq!(
+            verify_simulation_replay(push0_replay_input()).gas_profile,
+            None
+        );
+    }
+
+    /// Runtime that calls itself with one more calldata byte until calldata
+    /// reaches `limit` bytes, then returns 32 zero bytes.
+    fn self_calling_code(limit: u8) -> String {
+        // PUSH1 limit CALLDATASIZE LT PUSH1 0x0e JUMPI
+        // PUSH0 PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        // JUMPDEST PUSH0 PUSH0 CALLDATASIZE PUSH1 1 ADD PUSH0 PUSH0 ADDRESS GAS CALL POP
+        // PUSH0 PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        format!(
+            "0x60{limit:02x}3610600e57\
+             5f5f5260205ff3\
+             5b5f5f366001015f5f305af150\
+             5f5f5260205ff3"
+        )
+    }
+
+    #[test]
+    fn flags_recursive_self_calls_as_reentrancy() {
+        let replay_with_limit = |limit: u8| {
+            let mut input = push0_replay_input();
+            input.simulation_witness.replay_accounts = Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account(
+                    "0x2000000000000000000000000000000000000002",
+                    &self_calling_code(limit),
+                ),
+            ]);
+            verify_simulation_replay(input)
+        };
+
+        // 0xdeadbeef is 4 bytes: one direct self-call, then A -> A -> A.
+        let single = replay_with_limit(5);
+        let recursive = replay_with_limit(6);
+
+        assert!(single.executed, "{single:?}");
+        assert!(!single.reentrancy_detected);
+        assert!(recursive.executed, "{recursive:?}");
+        assert!(recursive.reentrancy_detected);
+        assert!(!verify_simulation_replay(push0_replay_input()).reentrancy_detected);
+    }
+
+    #[test]
+    fn zero_gas_price_leaves_the_caller_balance_untouched_by_fees() {
+        // Runtime: ORIGIN BALANCE PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        let code = "0x32315f5260205ff3";
+        let witness_balance = format!("0x{:064x}", 1_000_000_000_000_000_000u64);
+        let replay = |zero_gas_price: bool| {
+            let mut input = push0_replay_input();
+            input.simulation_witness.zero_gas_price = zero_gas_price;
+            input.simulation_witness.replay_accounts = Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]);
+            input.simulation.return_data = Some(witness_balance.clone());
+            verify_simulation_replay(input)
+        };
+
+        let zero = replay(true);
+        assert!(zero.success, "{zero:?}");
+
+        // Base fee 1 wei: the upfront gas charge shows in the caller balance.
+        let charged = replay(false);
+        assert!(charged.executed, "{charged:?}");
+        assert_eq!(charged.reason, ReplayReason::MismatchReturnData);
+    }
+
+    #[test]
+    fn prefers_the_verified_header_base_fee_over_the_witness_block() {
+        // Runtime: BASEFEE PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        let code = "0x485f5260205ff3";
+        let mut input = push0_replay_input();
+        input.simulation_witness.replay_accounts = Some(vec![
+            caller_account("0x1000000000000000000000000000000000000001"),
+            target_account("0x2000000000000000000000000000000000000002", code),
+        ]);
+        // The witness block claims a base fee of 1 wei.
+        input.verified_block_header = Some(VerifiedBlockHeader {
+            block_hash: format!("{:#x}", B256::repeat_byte(0xbb)),
+            parent_hash: format!("{:#x}", B256::repeat_byte(0xaa)),
+            fee_recipient: "0x3000000000000000000000000000000000000003".to_string(),
+            state_root: format!("{:#x}", B256::ZERO),
+            receipts_root: format!("{:#x}", B256::ZERO),
+            prev_randao: format!("{:#x}", B256::repeat_byte(0x01)),
+            block_number: input.simulation.block_number,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            timestamp: 1_700_000_000,
+            base_fee_per_gas: "7".to_string(),
+        });
+        input.simulation.return_data = Some(format!("0x{:064x}", 7));
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+    }
+
+    #[test]
+    fn attaches_remediation_hints_to_failed_replays() {
+        let mut incomplete = push0_replay_input();
+        incomplete.simulation_witness.replay_accounts = None;
+        let mut delegatecall = push0_replay_input();
+        delegatecall.transaction.operation = 1;
+
+        let incomplete = verify_simulation_replay(incomplete);
+        let delegatecall = verify_simulation_replay(delegatecall);
+
+        assert_eq!(incomplete.reason, ReplayReason::WitnessIncomplete);
+        assert!(incomplete
+            .hint
+            .expect("hint")
+            .contains("prestate tracer enabled"));
+        assert_eq!(delegatecall.reason, ReplayReason::ExecError);
+        assert!(delegatecall
+            .hint
+            .expect("hint")
+            .starts_with("DELEGATECALL transactions cannot be replayed locally"));
+        assert_eq!(verify_simulation_replay(push0_replay_input()).hint, None);
+    }
+
+    #[test]
+    fn sequence_replays_each_transaction_on_the_previous_state() {
+        // Runtime: PUSH0 SLOAD PUSH1 1 ADD DUP1 PUSH0 SSTORE
+        //          PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        // Increments slot 0 and returns the new value.
+        let code = "0x5f54600101805f555f5260205ff3";
+        let transaction = |returned: u64| {
+            let mut input = push0_replay_input();
+            input.simulation_witness.replay_accounts = Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]);
+            input.simulation.return_data = Some(format!("0x{returned:064x}"));
+            input
+        };
+
+        // Both witnesses show slot 0 unset; only the carried state makes the
+        // second transaction return 2.
+        let result = verify_simulation_replay_sequence(SimulationReplaySequenceInput {
+            transactions: vec![transaction(1), transaction(2)],
+        });
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.stopped_at, None);
+        let target = result
+            .state_diff
+            .iter()
+            .find(|diff| diff.address == "0x2000000000000000000000000000000000000002")
+            .expect("target diff");
+        assert_eq!(
+            target.storage,
+            vec![ReplayStorageSlotDiff {
+                slot: "0x0".to_string(),
+                before: "0x0".to_string(),
+                after: "0x2".to_string(),
+            }]
+        );
+        let caller = result
+            .state_diff
+            .iter()
+            .find(|diff| diff.address == "0x1000000000000000000000000000000000000001")
+            .expect("caller diff");
+        assert_eq!((caller.nonce_before, caller.nonce_after), (0, 2));
+
+        let isolated = verify_simulation_replay(transaction(2));
+        assert_eq!(isolated.reason, ReplayReason::MismatchReturnData);
+    }
+
+    #[test]
+    fn sequence_stops_at_a_transaction_that_did_not_execute() {
+        // Fails validation before executing, so it leaves no state behind.
+        let mut invalid = push0_replay_input();
+        invalid.package_chain_id = Some(5);
+
+        let result = verify_simulation_replay_sequence(SimulationReplaySequenceInput {
+            transactions: vec![invalid, push0_replay_input()],
+        });
+
+        assert!(!result.success);
+        assert_eq!(result.stopped_at, Some(0));
+        assert_eq!(result.results.len(), 1);
+        assert!(!result.results[0].executed);
+        assert!(result.state_diff.is_empty());
+    }
+
+    #[test]
+    fn sequence_tops_up_a_carried_caller_missing_from_the_witness() {
+        let transaction = || {
+            let mut input = push0_replay_input();
+            input.simulation_witness.replay_accounts = Some(vec![target_account(
+                "0x2000000000000000000000000000000000000002",
+                "0x5f5f5260205ff3",
+            )]);
+            if let Some(block) = input.simulation_witness.replay_block.as_mut() {
+                block.base_fee_per_gas = "30000000000".to_string();
+            }
+            input
+        };
+
+        // The first transaction's top-up covers its own gas limit only; the
+        // second needs a fresh one on the carried caller.
+        let result = verify_simulation_replay_sequence(SimulationReplaySequenceInput {
+            transactions: vec![transaction(), transaction()],
+        });
+
+        assert!(result.success, "{result:?}");
+        let caller = result
+            .state_diff
+            .iter()
+            .find(|diff| diff.address == "0x1000000000000000000000000000000000000001")
+            .expect("caller diff");
+        assert_eq!((caller.nonce_before, caller.nonce_after), (0, 2));
+        assert_eq!(
+            (
+                caller.balance_before.as_str(),
+                caller.balance_after.as_str()
+            ),
+            ("0", "0")
+        );
+    }
+
+    #[test]
+    fn success_mismatch_reports_the_decoded_revert_reason() {
+        // Runtime: CODECOPY the 100-byte Error("nope") payload appended after
+        // the 10-byte prefix to memory 0, then REVERT with it.
+        let payload = format!(
+            "08c379a0{:064x}{:064x}{:0<64}",
+            0x20,
+            4,
+            hex::encode("nope")
+        );
+        let code = format!("0x6064600a5f3960645ffd{payload}");
+        let mut input = push0_replay_input();
+        input.simulation_witness.replay_accounts = Some(vec![
+            caller_account("0x1000000000000000000000000000000000000001"),
+            target_account("0x2000000000000000000000000000000000000002", &code),
+        ]);
+
+        let result = verify_simulation_replay(input);
+
+        assert_eq!(result.reason, ReplayReason::MismatchSuccess);
+        let error = result.error.expect("error");
+        assert!(
+            error.contains(&format!("replay returnData=0x{payload}")),
+            "{error}"
+        );
+        assert!(
+            error.ends_with(r#"revert reason: Error("nope")"#),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn semantic_log_comparison_ignores_partial_word_padding() {
+        // Runtime: PUSH1 1 PUSH0 MSTORE8 PUSH1 0x20 PUSH0 LOG0
+        //          PUSH0 PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        let code = "0x60015f5360205fa05f5f5260205ff3";
+        let replay_with = |mode: LogComparisonMode, data: String| {
+            let mut input = push0_replay_input();
+            input.simulation_witness.witness_only = Some(false);
+            input.simulation_witness.replay_accounts = Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]);
+            input.simulation.logs = vec![ReplaySimulationLog {
+                address: "0x2000000000000000000000000000000000000002".to_string(),
+                topics: Vec::new(),
+                data,
+            }];
+            input.log_comparison_mode = mode;
+            verify_simulation_replay(input)
+        };
+
+        // The packaged data stops at the last non-zero byte of the word.
+        let strict = replay_with(LogComparisonMode::Strict, "0x01".to_string());
+        let semantic = replay_with(LogComparisonMode::Semantic, "0x01".to_string());
+        // An extra all-zero word is an extra ABI value, e.g. a trailing 0.
+        let extra_word = replay_with(
+            LogComparisonMode::Semantic,
+            format!("0x01{}", "00".repeat(63)),
+        );
+
+        assert_eq!(strict.reason, ReplayReason::MismatchLogs);
+        assert!(semantic.success, "{semantic:?}");
+        assert_eq!(extra_word.reason, ReplayReason::MismatchLogs);
+    }
+
+    #[test]
+    fn abi_words_pads_only_the_last_partial_word() {
+        assert_eq!(
+            abi_words("0x01"),
+            abi_words(&format!("0x01{}", "00".repeat(31)))
+        );
+        assert_ne!(
+            abi_words("0x01"),
+            abi_words(&format!("0x01{}", "00".repeat(63)))
+        );
+        assert_ne!(abi_words("0x01"), abi_words("0x0001"));
+        assert_eq!(abi_words("0x"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn warns_when_the_replay_uses_a_small_fraction_of_the_expected_gas() {
+        let replay_gas: u64 = verify_simulation_replay(push0_replay_input())
+            .replay_receipt
+            .expect("receipt")
+            .gas_used
+            .parse()
+            .expect("gas used");
+        let with_threshold = |threshold: Option<u8>| {
+            let mut input = push0_replay_input();
+            input.simulation.gas_used = (replay_gas * 10).to_string();
+            input.gas_underrun_threshold_percent = threshold;
+            verify_simulation_replay(input)
+        };
+
+        let warned = with_threshold(Some(50));
+        assert!(warned.success, "{warned:?}");
+        assert!(warned
+            .gas_underrun_warning
+            .expect("warning")
+            .contains("10% of the expected"));
+        assert_eq!(with_threshold(Some(5)).gas_underrun_warning, None);
+        assert_eq!(with_threshold(None).gas_underrun_warning, None);
+    }
+
+    #[test]
+    fn rejects_a_witness_listing_an_account_twice() {
+        let mut input = push0_replay_input();
+        input
+            .simulation_witness
+            .replay_accounts
+            .as_mut()
+            .expect("accounts")
+            .push(target_account(
+                "2000000000000000000000000000000000000002",
+                "0x00",
+            ));
+
+        let result = verify_simulation_replay(input);
+
+        assert!(!result.executed);
+        assert_eq!(result.reason, ReplayReason::ExecError);
+        assert!(result
+            .error
+            .expect("error")
+            .contains("lists 0x2000000000000000000000000000000000000002 more than once"));
+    }
+
+    #[test]
+    fn tx_envelope_reflects_the_resolved_gas_limit_fallback() {
+        let envelope = |safe_tx_gas: Option<&str>| {
+            let mut input = push0_replay_input();
+            input.include_tx_envelope = true;
+            input.transaction.safe_tx_gas = safe_tx_gas.map(str::to_string);
+            verify_simulation_replay(input)
+                .replay_tx_envelope
+                .expect("envelope")
+        };
+
+        assert_eq!(
+            envelope(Some("500000")),
+            ReplayTxEnvelope {
+                caller: "0x1000000000000000000000000000000000000001".into(),
+                to: Some("0x2000000000000000000000000000000000000002".into()),
+                value: "0".into(),
+                data: "0xdeadbeef".into(),
+                gas_limit: 500_000,
+                gas_price: "1".into(),
+                nonce: 0,
+                chain_id: 100,
+            }
+        );
+        let block = BlockEnv {
+            gas_limit: 30_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            envelope(None).gas_limit,
+            default_replay_gas_limit(100, &block, SpecId::default())
+        );
+        assert_eq!(
+            verify_simulation_replay(push0_replay_input()).replay_tx_envelope,
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_prevrandao_reads_without_a_witness_value() {
+        // PREVRANDAO PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        let prevrandao_input = |prev_randao: Option<&str>| {
+            let mut input = push0_replay_input();
+            let witness = &mut input.simulation_witness;
+            witness.replay_accounts.as_mut().expect("accounts")[1].code = "0x445f5260205ff3".into();
+            witness.replay_block.as_mut().expect("block").prev_randao =
+                prev_randao.map(str::to_string);
+            input
+        };
+
+        let missing = verify_simulation_replay(prevrandao_input(None));
+
+        assert_eq!(missing.reason, ReplayReason::ExecError);
+        assert!(missing.error.expect("error").contains("PREVRANDAO (0x44)"));
+
+        let randomness = format!("0x{}", "ab".repeat(32));
+        let mut supplied = prevrandao_input(Some(&randomness));
+        supplied.simulation.return_data = Some(randomness);
+        let supplied = verify_simulation_replay(supplied);
+        assert!(supplied.success, "{supplied:?}");
+
+        let mut unused = push0_replay_input();
+        unused
+            .simulation_witness
+            .replay_block
+            .as_mut()
+            .expect("block")
+            .prev_randao = None;
+        let unused = verify_simulation_replay(unused);
+        assert!(unused.success, "{unused:?}");
+    }
+
+    #[test]
+    fn pre_merge_difficulty_reads_need_no_witness_randomness() {
+        // DIFFICULTY PUSH1 0 MSTORE PUSH1 0x20 PUSH1 0 RETURN
+        let mut input = push0_replay_input();
+        input.fork_schedule = Some(vec![ReplayForkActivation {
+            fork: "london".to_string(),
+            block: Some(0),
+            timestamp: None,
+        }]);
+        let witness = &mut input.simulation_witness;
+        witness.replay_accounts.as_mut().expect("accounts")[1].code = "0x4460005260206000f3".into();
+        witness.replay_block.as_mut().expect("block").prev_randao = None;
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.spec_used.as_deref(), Some("london"));
+    }
+
+    #[test]
+    fn captures_logs_emitted_before_a_revert() {
+        // PUSH1 0x2a PUSH0 PUSH0 LOG1 PUSH0 PUSH0 REVERT
+        let logging_revert = |capture: bool| {
+            let mut input = push0_replay_input();
+            input.capture_pre_revert_logs = capture;
+            input.simulation.success = false;
+            input.simulation.return_data = Some("0x".into());
+            input
+                .simulation_witness
+                .replay_accounts
+                .as_mut()
+                .expect("accounts")[1]
+                .code = "0x602a5f5fa15f5ffd".into();
+            verify_simulation_replay(input)
+        };
+
+        let result = logging_revert(true);
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.replay_logs, Some(Vec::new()));
+        assert_eq!(
+            result.pre_revert_logs,
+            Some(vec![ReplaySimulationLog {
+                address: "0x2000000000000000000000000000000000000002".into(),
+                topics: vec![format!("{:#x}", B256::with_last_byte(0x2a))],
+                data: "0x".into(),
+            }])
+        );
+        assert_eq!(logging_revert(false).pre_revert_logs, None);
+    }
+
+    #[test]
+    fn totals_native_value_across_transfers() {
+        let transfer = |from: &str, to: &str, value: &str| ReplayNativeTransfer {
+            from: from.into(),
+            to: to.into(),
+            value: value.into(),
+        };
+
+        let total = total_native_value(&[
+            transfer("0x01", "0x02", "1000000000000000000"),
+            transfer("0x02", "0x03", "250"),
+            transfer("0x03", "0x03", "7"),
+        ]);
+
+        assert_eq!(total, U256::from(1_000_000_000_000_000_250u128));
+        assert_eq!(total_native_value(&[]), U256::ZERO);
+    }
+
+    #[test]
+    fn rejects_witness_code_that_differs_from_the_proven_code_hash() {
+        let target = "0x2000000000000000000000000000000000000002";
+        let with_proven_hash = |code: &str| {
+            let mut input = push0_replay_input();
+            let hash = keccak256(hex::decode(code).expect("hex"));
+            input
+                .proven_code_hashes
+                .insert(target.to_string(), format!("{hash:#x}"));
+            verify_simulation_replay(input)
+        };
+
+        let proven = with_proven_hash("5f5f5260205ff3");
+        assert!(proven.success, "{proven:?}");
+
+        let mismatched = with_proven_hash("5f5ffd");
+        assert!(!mismatched.executed);
+        assert_eq!(mismatched.reason, ReplayReason::ExecError);
+        assert!(mismatched
+            .error
+            .expect("error")
+            .contains("proven under the verified state root"));
+    }
+
+    #[test]
+    fn bounds_a_gas_limit_above_the_proven_block_gas_limit() {
+        let replay = |policy: ProvenGasLimitPolicy| {
+            let mut input = push0_replay_input();
+            input.include_tx_envelope = true;
+            input.verified_block_header = Some(VerifiedBlockHeader {
+                block_hash: format!("{:#x}", B256::repeat_byte(0xbb)),
+                parent_hash: format!("{:#x}", B256::repeat_byte(0xaa)),
+                fee_recipient: "0x3000000000000000000000000000000000000003".to_string(),
+                state_root: format!("{:#x}", B256::ZERO),
+                receipts_root: format!("{:#x}", B256::ZERO),
+                prev_randao: format!("{:#x}", B256::repeat_byte(0x01)),
+                block_number: input.simulation.block_number,
+                // Below the 500000 safeTxGas the witness asks for.
+                gas_limit: 100_000,
+                gas_used: 0,
+                timestamp: 1_700_000_000,
+                base_fee_per_gas: "1".to_string(),
+            });
+            input.proven_gas_limit_policy = Some(policy);
+            verify_simulation_replay(input)
+        };
+
+        let rejected = replay(ProvenGasLimitPolicy::Reject);
+        assert!(!rejected.success);
+        assert_eq!(rejected.reason, ReplayReason::ExecError);
+        assert!(rejected
+            .error
+            .expect("error")
+            .contains("Replay gas limit 500000 exceeds the proven block gas limit 100000"));
+
+        let clamped = replay(ProvenGasLimitPolicy::Clamp);
+        assert!(clamped.success, "{clamped:?}");
+        assert_eq!(
+            clamped.replay_tx_envelope.expect("envelope").gas_limit,
+            100_000
+        );
+    }
+
+    #[test]
+    fn flags_value_recipients_missing_from_the_expected_list() {
+        let target = "0x2000000000000000000000000000000000000002";
+        let replay = |expected: Option<Vec<&str>>| {
+            let mut input = push0_replay_input();
+            input.transaction.value = "0x5".to_string();
+            input.expected_recipients =
+                expected.map(|expected| expected.into_iter().map(str::to_string).collect());
+            verify_simulation_replay(input)
+        };
+
+        let unexpected = replay(Some(vec!["0x3000000000000000000000000000000000000003"]));
+        assert!(unexpected.executed, "{unexpected:?}");
+        assert_eq!(
+            unexpected.unexpected_recipients,
+            Some(vec![target.to_string()])
+        );
+
+        let expected = replay(Some(vec![target]));
+        assert_eq!(expected.unexpected_recipients, Some(Vec::new()));
+        assert_eq!(replay(None).unexpected_recipients, None);
+    }
+
+    #[test]
+    fn summary_keeps_the_verdict_and_drops_the_effects() {
+        let result = verify_simulation_replay(push0_replay_input());
+        assert!(result.success, "{result:?}");
+
+        let summary =
+            serde_json::to_value(SimulationReplaySummary::from(&result)).expect("summary json");
+
+        assert_eq!(summary["success"], true);
+        assert_eq!(summary["reason"], "simulation-replay-matched");
+        // Witness-only: packaged logs are not compared.
+        assert_eq!(summary["matchedEffects"], 3);
+        assert_eq!(summary["comparedEffects"], 3);
+        for heavy in [
+            "replayLogs",
+            "replayNativeTransfers",
+            "comparison",
+            "replayReceipt",
+        ] {
+            assert!(summary.get(heavy).is_none(), "{heavy} in summary");
+        }
+    }
+
+    #[test]
+    fn replay_reasons_serialize_to_legacy_strings() {
+        for (reason, legacy) in [
+            (ReplayReason::Matched, "simulation-replay-matched"),
+            (ReplayReason::InvalidInput, "invalid-input"),
+            (ReplayReason::ExecError, "simulation-replay-exec-error"),
+            (
+                ReplayReason::MismatchSuccess,
+                "simulation-replay-mismatch-success",
+            ),
+            (
+                ReplayReason::MismatchReturnData,
+                "simulation-replay-mismatch-return-data",
+            ),
+            (
+                ReplayReason::MismatchLogs,
+                "simulation-replay-mismatch-logs",
+            ),
+            (ReplayReason::MismatchGas, "simulation-replay-mismatch-gas"),
+            (
+                ReplayReason::MismatchCreate2,
+                "simulation-replay-mismatch-create2",
+            ),
+            (
+                ReplayReason::MismatchPostStateRoot,
+                "simulation-replay-mismatch-post-state-root",
+            ),
+            (
+                ReplayReason::WitnessIncomplete,
+                "simulation-witness-incomplete",
+            ),
+            (
+                ReplayReason::WitnessOnlyLogsPresent,
+                "simulation-witness-only-logs-present",
+            ),
+        ] {
+            assert_eq!(serde_json::to_value(reason).expect("serialize"), legacy);
+            assert_eq!(reason.to_string(), legacy);
+            assert_eq!(
+                serde_json::from_value::<ReplayReason>(legacy.into()).expect("deserialize"),
+                reason
+            );
+        }
+    }
+
+    fn ecrecover_call_gas(access_list: Vec<ReplayAccessListEntry>) -> u64 {
+        // Runtime: PUSH0 x4 PUSH1 0x01 GAS STATICCALL POP STOP
+        let code = "0x5f5f5f5f60015afa5000";
+        let mut input = proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: Some(vec![
+                caller_account("0x1000000000000000000000000000000000000001"),
+                target_account("0x2000000000000000000000000000000000000002", code),
+            ]),
+            replay_caller: Some("0x1000000000000000000000000000000000000001".to_string()),
+            witness_only: Some(true),
+            replay_access_list: access_list,
+            ..Default::default()
+        });
+        input.simulation.return_data = None;
+        let result = verify_simulation_replay(input);
+        assert!(result.executed, "{result:?}");
+        let receipt = result.replay_receipt.expect("replay receipt");
+        assert_eq!(receipt.status, 1);
+        receipt.gas_used.parse().expect("decimal gas")
+    }
+
+    #[test]
+    fn access_listed_precompile_only_adds_intrinsic_access_list_cost() {
+        let unlisted = ecrecover_call_gas(Vec::new());
+        let listed = ecrecover_call_gas(vec![ReplayAccessListEntry {
+            address: "0x0000000000000000000000000000000000000001".to_string(),
+            storage_keys: Vec::new(),
+        }]);
+
+        // Precompiles are warm from the start of every transaction (EIP-2929),
+        // so the STATICCALL costs the same 100 warm-access gas either way and
+        // the listed replay pays only the 2400 per-address intrinsic cost.
+        assert_eq!(listed - unlisted, 2_400);
+    }
+
+    #[test]
+    fn reports_typed_halt_reason_without_synthetic_logs() {
+        let mut input = push0_replay_input();
+        input.simulation_witness.replay_gas_limit = Some(21_001);
+        input.simulation.success = false;
+        input.simulation.return_data = None;
+
+        let result = verify_simulation_replay(input);
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.halt_reason, Some(ReplayHaltReason::OutOfGas));
+        assert_eq!(result.replay_logs, Some(Vec::new()));
+        assert_eq!(result.replay_receipt.expect("replay receipt").status, 0);
+        assert!(!result.comparison.expect("comparison").success.replay);
+    }
+
+    #[test]
+    fn reports_gas_headroom_against_the_simulation_budget() {
+        assert_eq!(
+            GasPolicy::AtMost(U256::from(500_000)).headroom(200_000),
+            "300000"
+        );
+        assert_eq!(
+            GasPolicy::AtMost(U256::from(500_000)).headroom(500_001),
+            "-1"
+        );
+
+        let mut input = push0_replay_input();
+        input.simulation.gas_used = "500000".to_string();
+        let result = verify_simulation_replay(input);
+        let gas_used: u64 = result
+            .replay_receipt
+            .as_ref()
+            .expect("replay receipt")
+            .gas_used
+            .parse()
+            .expect("decimal gas");
+
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.gas_headroom, Some((500_000 - gas_used).to_string()));
+    }
+
+    #[test]
+    fn rejects_replay_gas_below_expected_range() {
+        let result = push0_replay_with_gas_range(1, 100);
+
+        assert_eq!(result.reason, ReplayReason::MismatchGas);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("outside expectedGasRange"));
+    }
+
+    #[test]
+    fn rejects_replay_gas_above_expected_range() {
+        let result = push0_replay_with_gas_range(-100, -1);
+
+        assert_eq!(result.reason, ReplayReason::MismatchGas);
+        let gas = result.comparison.expect("comparison").gas;
+        assert!(!gas.matched);
+        assert!(gas.expected.contains("..="), "{}", gas.expected);
+    }
+
+    #[test]
+    fn serializes_schema_version_as_first_field() {
+        let executed = verify_simulation_replay(push0_replay_input());
+        let failed = failed_result(
+            false,
+            ReplayReason::WitnessIncomplete,
+            "missing".to_string(),
+        );
+
+        assert_eq!(REPLAY_RESULT_SCHEMA_VERSION, 22);
+        for result in [executed, failed] {
+            let json = serde_json::to_string(&result).expect("serialize replay result");
+            assert!(
+                json.starts_with(r#"{"schemaVersion":22,"#),
+                "unexpected prefix: {json}"
+            );
+        }
+    }
+
+    #[test]
+    fn expands_prestate_diff_sorted_by_address_then_slot() {
+        let base = BTreeMap::from([
+            (
+                "0x00000000000000000000000000000000000000Bb".to_string(),
+                ReplayPrestateAccount {
+                    storage: BTreeMap::from([
+                        ("0x10".to_string(), "0x1".to_string()),
+                        ("0x2".to_string(), "0x2".to_string()),
+                    ]),
+                    ..Default::default()
+                },
+            ),
+            (
+                "0x00000000000000000000000000000000000000aa".to_string(),
+                ReplayPrestateAccount::default(),
+            ),
+        ]);
+
+        let expanded = expand_prestate_diff(&ReplayPrestateDiff {
+            base,
+            overrides: BTreeMap::new(),
+        })
+        .expect("valid diff");
+
+        let addresses: Vec<&str> = expanded.iter().map(|a| a.address.as_str()).collect();
+        assert_eq!(
+            addresses,
+            [
+                "0x00000000000000000000000000000000000000aa",
+                "0x00000000000000000000000000000000000000bb",
+            ]
+        );
+        let slots: Vec<&str> = expanded[1].storage.keys().map(String::as_str).collect();
+        assert_eq!(
+            slots,
+            [
+                "0x0000000000000000000000000000000000000000000000000000000000000002",
+                "0x0000000000000000000000000000000000000000000000000000000000000010",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_prestate_diff_with_conflicting_base_entries() {
+        let mut base = BTreeMap::new();
+        base.insert(
+            "0x00000000000000000000000000000000000000aa".to_string(),
+            ReplayPrestateAccount::default(),
+        );
+        base.insert(
+            "0x00000000000000000000000000000000000000AA".to_string(),
+            ReplayPrestateAccount {
+                nonce: Some(1),
+                ..Default::default()
+            },
+        );
+
+        let result = verify_simulation_replay(proxy_replay_input(ReplayWitness {
+            replay_block: Some(replay_block(REPLAY_TIMESTAMP)),
+            replay_accounts: None,
+            replay_caller: None,
+            replay_gas_limit: Some(500000),
+            replay_calldata: None,
+            witness_only: Some(true),
+            replay_prestate_diff: Some(ReplayPrestateDiff {
+                base,
+                overrides: BTreeMap::new(),
+            }),
+            ..Default::default()
+        }));
+
+        assert!(!result.executed);
+        assert_eq!(result.reason, ReplayReason::ExecError);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("conflicting entries"));
     }
 
     #[test]
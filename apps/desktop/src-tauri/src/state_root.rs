@@ -0,0 +1,102 @@
+//! Merkle-Patricia state root over a complete set of accounts.
+//!
+//! Only meaningful when the account set is the whole world state: a partial
+//! witness yields the root of a smaller trie, never the chain's state root.
+
+use alloy::{
+    primitives::{keccak256, Address, B256, U256},
+    trie::{HashBuilder, Nibbles, TrieAccount},
+};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateAccount {
+    pub nonce: u64,
+    pub balance: U256,
+    pub code_hash: B256,
+    /// Non-zero slots only; zero-valued slots are absent from the trie.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+pub fn state_root(accounts: &BTreeMap<Address, StateAccount>) -> B256 {
+    trie_root(accounts.iter().map(|(address, account)| {
+        (
+            keccak256(address),
+            encode_account(
+                account.nonce,
+                account.balance,
+                storage_root(&account.storage),
+                account.code_hash,
+            ),
+        )
+    }))
+}
+
+fn storage_root(storage: &BTreeMap<U256, U256>) -> B256 {
+    trie_root(
+        storage
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(slot, value)| (keccak256(B256::from(*slot)), alloy::rlp::encode(value))),
+    )
+}
+
+/// Leaves are inserted in hashed-key order, as `HashBuilder` requires.
+fn trie_root(leaves: impl Iterator<Item = (B256, Vec<u8>)>) -> B256 {
+    let leaves: BTreeMap<B256, Vec<u8>> = leaves.collect();
+    let mut builder = HashBuilder::default();
+    for (key, value) in &leaves {
+        builder.add_leaf(Nibbles::unpack(key), value);
+    }
+    builder.root()
+}
+
+/// State trie leaf: `rlp([nonce, balance, storageRoot, codeHash])`.
+pub(crate) fn encode_account(
+    nonce: u64,
+    balance: U256,
+    storage_root: B256,
+    code_hash: B256,
+) -> Vec<u8> {
+    alloy::rlp::encode(TrieAccount {
+        nonce,
+        balance,
+        storage_root,
+        code_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{primitives::b256, trie::EMPTY_ROOT_HASH};
+
+    #[test]
+    fn empty_state_has_the_empty_trie_root() {
+        assert_eq!(state_root(&BTreeMap::new()), EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn zero_storage_values_do_not_change_the_root() {
+        let account = |storage: BTreeMap<U256, U256>| {
+            BTreeMap::from([(
+                Address::repeat_byte(0x01),
+                StateAccount {
+                    nonce: 1,
+                    balance: U256::from(10),
+                    code_hash: b256!(
+                        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+                    ),
+                    storage,
+                },
+            )])
+        };
+
+        let without = state_root(&account(BTreeMap::new()));
+        let with_zero = state_root(&account(BTreeMap::from([(U256::from(1), U256::ZERO)])));
+        let with_value = state_root(&account(BTreeMap::from([(U256::from(1), U256::from(5))])));
+
+        assert_eq!(without, with_zero);
+        assert_ne!(without, with_value);
+    }
+}
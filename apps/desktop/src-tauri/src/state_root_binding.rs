@@ -0,0 +1,298 @@
+//! Binding-only recheck of account proofs against a verified state root.
+//!
+//! `verify_consensus_proof` is the expensive step (BLS over the sync
+//! committee); its `verified_state_root` does not change afterwards. Given
+//! that root, this re-proves the Safe and the replay witness accounts with
+//! plain trie lookups, so a UI can recheck the binding without re-running
+//! consensus verification.
+
+use alloy::primitives::{Address, B256, KECCAK_EMPTY};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::safe_account::{prove_account, prove_storage, ProvenAccount, StorageSlotProof};
+
+const ERR_STATE_ROOT_BINDING_FAILED: &str = "state-root-binding-failed";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateRootBindingInput {
+    /// `verified_state_root` from `verify_consensus_proof`.
+    pub verified_state_root: String,
+    pub safe: AccountProof,
+    #[serde(default)]
+    pub replay_accounts: Vec<AccountProof>,
+}
+
+/// One account as returned by `eth_getProof`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub address: String,
+    /// Absent when the proof is an exclusion proof.
+    pub account: Option<ProvenAccount>,
+    pub account_proof: Vec<String>,
+    #[serde(default)]
+    pub storage_proof: Vec<StorageSlotProof>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StateRootBindingResult {
+    pub valid: bool,
+    pub verified_state_root: String,
+    /// Per-account outcome, the Safe first and then `replayAccounts` in
+    /// input order.
+    pub accounts: Vec<AccountBinding>,
+    /// The first account's error, when any failed.
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBinding {
+    pub address: String,
+    pub proven: bool,
+    /// Storage root from the proven leaf that the account's `storageProof`
+    /// entries were checked against; `None` when it carries no storage
+    /// proofs, is proven absent, or its proof failed.
+    pub storage_root: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn verify_state_root_binding(input: &StateRootBindingInput) -> StateRootBindingResult {
+    let accounts: Vec<AccountBinding> = std::iter::once((&input.safe, true))
+        .chain(input.replay_accounts.iter().map(|proof| (proof, false)))
+        .map(|(proof, is_safe)| {
+            let (storage_root, error) =
+                match prove_binding(&input.verified_state_root, proof, is_safe) {
+                    Ok(storage_root) => (storage_root, None),
+                    Err(error) => (None, Some(error)),
+                };
+            AccountBinding {
+                address: proof.address.clone(),
+                proven: error.is_none(),
+                storage_root: storage_root.map(|root| format!("{root:#x}")),
+                error,
+            }
+        })
+        .collect();
+    let error = accounts.iter().find_map(|account| account.error.clone());
+
+    StateRootBindingResult {
+        valid: error.is_none(),
+        verified_state_root: input.verified_state_root.clone(),
+        accounts,
+        error_code: error
+            .as_ref()
+            .map(|_| ERR_STATE_ROOT_BINDING_FAILED.to_string()),
+        error,
+    }
+}
+
+/// The Safe must be a present leaf with code, as in
+/// `verify_safe_account_proof`; replay accounts may be proven absent.
+/// Returns the storage root the account's storage proofs verified against.
+fn prove_binding(
+    verified_state_root: &str,
+    proof: &AccountProof,
+    is_safe: bool,
+) -> Result<Option<B256>, String> {
+    let address = Address::from_str(proof.address.trim())
+        .map_err(|err| format!("invalid address {}: {err}", proof.address))?;
+    let state_root = B256::from_str(verified_state_root.trim())
+        .map_err(|err| format!("invalid verifiedStateRoot: {err}"))?;
+    let proven = prove_account(
+        address,
+        state_root,
+        proof.account.as_ref(),
+        &proof.account_proof,
+    )?;
+    if is_safe
+        && !proven
+            .as_ref()
+            .is_some_and(|leaf| leaf.code_hash != KECCAK_EMPTY)
+    {
+        return Err(format!(
+            "Safe {address:#x} has no code under the verified state root."
+        ));
+    }
+    match proven {
+        _ if proof.storage_proof.is_empty() => Ok(None),
+        Some(leaf) => {
+            prove_storage(leaf.storage_root, &proof.storage_proof)?;
+            Ok(Some(leaf.storage_root))
+        }
+        None => Err(format!(
+            "Account {address:#x} is proven absent but carries storage proofs."
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safe_account::proven_account_leaf;
+    use alloy::{
+        primitives::{keccak256, U256},
+        trie::{proof::ProofRetainer, HashBuilder, Nibbles, EMPTY_ROOT_HASH},
+    };
+
+    const SAFE: &str = "0x1000000000000000000000000000000000000001";
+    const OWNER: &str = "0x3000000000000000000000000000000000000003";
+
+    fn account(nonce: u64, code_hash: B256) -> ProvenAccount {
+        ProvenAccount {
+            nonce,
+            balance: "0".into(),
+            storage_hash: format!("{EMPTY_ROOT_HASH:#x}"),
+            code_hash: format!("{code_hash:#x}"),
+        }
+    }
+
+    /// Storage trie holding `value` at `slot` alone, with the slot's proof.
+    fn single_slot_storage(slot: U256, value: U256) -> (B256, StorageSlotProof) {
+        let key = Nibbles::unpack(keccak256(B256::from(slot)));
+        let mut builder = HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![key]));
+        builder.add_leaf(key, &alloy::rlp::encode(value));
+        let storage_root = builder.root();
+        let proof = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| format!("{node}"))
+            .collect();
+        (
+            storage_root,
+            StorageSlotProof {
+                key: format!("{slot:#x}"),
+                value: format!("{value:#x}"),
+                proof,
+            },
+        )
+    }
+
+    /// Input proving the Safe and one replay account as the only leaves of
+    /// the state trie.
+    fn binding_input() -> StateRootBindingInput {
+        binding_input_with_safe(account(1, B256::repeat_byte(0xcc)), Vec::new())
+    }
+
+    fn binding_input_with_safe(
+        safe_account: ProvenAccount,
+        safe_storage_proof: Vec<StorageSlotProof>,
+    ) -> StateRootBindingInput {
+        let mut leaves: Vec<(Nibbles, &str, ProvenAccount)> =
+            [(SAFE, safe_account), (OWNER, account(7, KECCAK_EMPTY))]
+                .into_iter()
+                .map(|(address, account)| {
+                    let address_key = keccak256(Address::from_str(address).expect("address"));
+                    (Nibbles::unpack(address_key), address, account)
+                })
+                .collect();
+        leaves.sort_by_key(|(key, _, _)| *key);
+        let targets = leaves.iter().map(|(key, _, _)| *key).collect();
+        let mut builder = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, _, account) in &leaves {
+            let code_hash = B256::from_str(&account.code_hash).expect("code hash");
+            builder.add_leaf(
+                *key,
+                &proven_account_leaf(account, code_hash).expect("account"),
+            );
+        }
+        let state_root = builder.root();
+        let nodes = builder.take_proof_nodes().into_nodes_sorted();
+        let mut proofs: Vec<AccountProof> = leaves
+            .into_iter()
+            .map(|(key, address, account)| AccountProof {
+                address: address.into(),
+                account: Some(account),
+                account_proof: nodes
+                    .iter()
+                    .filter(|(path, _)| key.starts_with(path))
+                    .map(|(_, node)| format!("{node}"))
+                    .collect(),
+                storage_proof: Vec::new(),
+            })
+            .collect();
+        let safe_index = proofs
+            .iter()
+            .position(|proof| proof.address == SAFE)
+            .expect("safe proof");
+        let mut safe = proofs.remove(safe_index);
+        safe.storage_proof = safe_storage_proof;
+
+        StateRootBindingInput {
+            verified_state_root: format!("{state_root:#x}"),
+            safe,
+            replay_accounts: proofs,
+        }
+    }
+
+    #[test]
+    fn accepts_the_safe_and_replay_accounts_proven_under_the_root() {
+        let result = verify_state_root_binding(&binding_input());
+
+        assert!(result.valid, "{result:?}");
+        assert_eq!(result.accounts.len(), 2);
+        assert!(result.accounts.iter().all(|account| account.proven));
+        assert!(result
+            .accounts
+            .iter()
+            .all(|account| account.storage_root.is_none()));
+        assert_eq!(result.error_code, None);
+    }
+
+    #[test]
+    fn rejects_a_tampered_replay_account_proof() {
+        let mut input = binding_input();
+        input.replay_accounts[0]
+            .account
+            .as_mut()
+            .expect("account")
+            .nonce = 8;
+
+        let result = verify_state_root_binding(&input);
+
+        assert!(!result.valid);
+        assert!(result.accounts[0].proven);
+        assert!(!result.accounts[1].proven);
+        assert_eq!(result.accounts[1].storage_root, None);
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some(ERR_STATE_ROOT_BINDING_FAILED)
+        );
+        assert!(result.error.expect("error").contains(OWNER));
+    }
+
+    #[test]
+    fn rejects_a_safe_without_code() {
+        let mut input = binding_input();
+        std::mem::swap(&mut input.safe, &mut input.replay_accounts[0]);
+
+        let result = verify_state_root_binding(&input);
+
+        assert!(!result.valid);
+        assert!(!result.accounts[0].proven);
+        assert!(result.accounts[1].proven);
+        assert!(result.error.expect("error").contains("has no code"));
+    }
+
+    #[test]
+    fn reports_the_storage_root_the_storage_proofs_verified_against() {
+        let (storage_root, slot) = single_slot_storage(U256::from(4), U256::from(2));
+        let mut safe = account(1, B256::repeat_byte(0xcc));
+        safe.storage_hash = format!("{storage_root:#x}");
+
+        let result = verify_state_root_binding(&binding_input_with_safe(safe, vec![slot]));
+
+        assert!(result.valid, "{result:?}");
+        assert_eq!(
+            result.accounts[0].storage_root,
+            Some(format!("{storage_root:#x}"))
+        );
+        assert_ne!(storage_root, EMPTY_ROOT_HASH);
+        assert_eq!(result.accounts[1].storage_root, None);
+    }
+}
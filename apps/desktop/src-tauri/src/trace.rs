@@ -0,0 +1,44 @@
+//! Opt-in debug tracing for the verification paths.
+//!
+//! Consensus and replay verification emit `tracing` spans and events at debug
+//! level. Nothing is printed unless `SAFELENS_TRACE` is set. Its value is an
+//! `EnvFilter` directive (`debug`, `safelens_desktop::consensus=trace`, ...);
+//! `1` is shorthand for debug output from this crate only.
+
+use tracing::Subscriber;
+use tracing_subscriber::{fmt::MakeWriter, EnvFilter};
+
+pub const TRACE_ENV_VAR: &str = "SAFELENS_TRACE";
+
+const DEFAULT_DIRECTIVE: &str = concat!(env!("CARGO_CRATE_NAME"), "=debug");
+
+/// Install the stderr subscriber when `SAFELENS_TRACE` is set to a value
+/// other than `0`. Production runs leave it unset and stay quiet.
+pub fn init_from_env() {
+    let Some(directives) = std::env::var(TRACE_ENV_VAR)
+        .ok()
+        .filter(|value| !value.is_empty() && value != "0")
+    else {
+        return;
+    };
+    let _ = tracing::subscriber::set_global_default(subscriber(&directives, std::io::stderr));
+}
+
+/// `fmt` subscriber filtered by `directives` and writing through `writer`.
+/// Directives that fail to parse fall back to this crate at debug level.
+pub fn subscriber<W>(directives: &str, writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let directives = if directives == "1" {
+        DEFAULT_DIRECTIVE
+    } else {
+        directives
+    };
+    let filter =
+        EnvFilter::try_new(directives).unwrap_or_else(|_| EnvFilter::new(DEFAULT_DIRECTIVE));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .finish()
+}
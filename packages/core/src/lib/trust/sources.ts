@@ -18,10 +18,14 @@ export type SimulationVerificationReason =
   | "simulation-replay-world-state-unproven"
   | "simulation-witness-proof-failed"
   | "simulation-witness-incomplete"
+  | "simulation-witness-only-logs-present"
   | "simulation-replay-mismatch-success"
   | "simulation-replay-mismatch-return-data"
   | "simulation-replay-mismatch-logs"
-  | "simulation-replay-mismatch-gas";
+  | "simulation-replay-mismatch-gas"
+  | "simulation-replay-mismatch-create2"
+  | "simulation-replay-mismatch-post-state-root"
+  | "invalid-input";
 
 interface ConsensusSourceMetadata {
   name: string;
@@ -318,6 +322,14 @@ export function buildVerificationSources(
               ? "No simulation witness was included; simulation remains RPC-sourced."
               : context.simulationVerificationReason === "simulation-witness-proof-failed"
                 ? "Simulation witness checks failed; simulation remains RPC-sourced."
+                : context.simulationVerificationReason === "simulation-witness-only-logs-present"
+                  ? "Package claims a witness-only simulation but still carries logs; simulation remains RPC-sourced."
+                : context.simulationVerificationReason === "simulation-replay-mismatch-create2"
+                  ? "Local replay CREATE2 deployments mismatched the expected addresses."
+                : context.simulationVerificationReason === "simulation-replay-mismatch-post-state-root"
+                  ? "Local replay post-state root mismatched the expected post-state root."
+                : context.simulationVerificationReason === "invalid-input"
+                  ? "Simulation replay input is malformed; simulation remains RPC-sourced."
                 : context.simulationVerificationReason === "simulation-witness-incomplete"
                   ? "Simulation witness is incomplete for local replay; simulation remains RPC-sourced."
                   : context.simulationVerificationReason === "simulation-replay-mismatch-success"
@@ -343,6 +355,14 @@ export function buildVerificationSources(
               ? "Simulation output was packaged without a witness artifact. Treat simulation outcome as RPC-trusted until witness generation and local replay verification are both available."
               : context.simulationVerificationReason === "simulation-witness-proof-failed"
                 ? "Simulation output was compared against witness metadata, but witness proof validation failed. Treat simulation outcome as RPC-trusted until witness and replay verification both pass."
+                : context.simulationVerificationReason === "simulation-witness-only-logs-present"
+                  ? "Witness-only packages strip simulation logs and recover them from local replay, yet this package carries populated logs. The combination is contradictory; treat the package as malformed or tampered and the simulation outcome as unverified."
+                : context.simulationVerificationReason === "simulation-replay-mismatch-create2"
+                  ? "Local replay deployed contracts via CREATE2 at addresses other than the expected deterministic ones, or skipped an expected deployment. Treat simulation outcome as unverified and investigate witness/package integrity."
+                : context.simulationVerificationReason === "simulation-replay-mismatch-post-state-root"
+                  ? "The state root computed from the witness after local replay differs from the package's expected post-state root. This check needs a witness covering the complete world state; treat the after-view as unverified and investigate witness/package integrity."
+                : context.simulationVerificationReason === "invalid-input"
+                  ? "One or more package fields needed for local replay are missing or malformed, so replay did not run. Fix the listed fields and re-run verification; until then treat the simulation outcome as RPC-trusted."
                 : context.simulationVerificationReason === "simulation-witness-incomplete"
                   ? "The witness did not include a complete replay world state (accounts/storage/code). Treat simulation outcome as RPC-trusted until complete replay inputs are provided and replay verification passes."
                   : context.simulationVerificationReason === "simulation-replay-mismatch-success"
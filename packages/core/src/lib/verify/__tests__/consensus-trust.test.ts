@@ -64,11 +64,21 @@ describe("consensus trust reason contract", () => {
       ["update-verification-failed", "invalid-proof-payload"],
       ["invalid-finality-update-json", "invalid-proof-payload"],
       ["finality-verification-failed", "invalid-proof-payload"],
+      ["signature-slot-mismatch", "invalid-proof-payload"],
+      ["malformed-sync-aggregate", "invalid-proof-payload"],
+      ["sync-period-mismatch", "invalid-proof-payload"],
       ["missing-execution-payload", "invalid-proof-payload"],
+      ["execution-payload-unproven", "invalid-proof-payload"],
+      ["finality-lineage-mismatch", "invalid-proof-payload"],
       ["invalid-expected-state-root", "invalid-expected-state-root"],
       ["state-root-mismatch", "state-root-mismatch-flag"],
+      ["state-root-block-mismatch", "state-root-mismatch-flag"],
+      ["state-root-value-mismatch", "state-root-mismatch-flag"],
       ["envelope-state-root-mismatch", "invalid-proof-payload"],
       ["envelope-block-number-mismatch", "invalid-proof-payload"],
+      ["l2-output-root-unproven", "invalid-proof-payload"],
+      ["invalid-input", "invalid-proof-payload"],
+      ["verification-panic", "invalid-proof-payload"],
       ["invalid-proof-payload", "invalid-proof-payload"],
       ["stale-consensus-envelope", "stale-consensus-envelope"],
       ["non-finalized-consensus-envelope", "non-finalized-consensus-envelope"],
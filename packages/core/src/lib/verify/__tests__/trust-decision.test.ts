@@ -285,4 +285,22 @@ describe("deriveSimulationVerificationReason", () => {
     });
     expect(result).toBe("simulation-replay-world-state-unproven");
   });
+
+  it("treats a replay over stateOverrides as not run", () => {
+    const result = deriveSimulationVerificationReason({
+      ...baseOptions,
+      evidence: makeEvidence({
+        simulation: { success: true, returnData: null, gasUsed: "0", logs: [], blockNumber: 1, blockTimestamp: "", trust: "rpc-sourced" },
+        simulationWitness: {} as EvidencePackage["simulationWitness"],
+      }),
+      simulationWitnessVerification: { valid: true, checks: [], errors: [] } as SimulationWitnessVerificationResult,
+      simulationReplayVerification: {
+        executed: true,
+        success: true,
+        reason: "simulation-replay-matched",
+        overridden: true,
+      } as SimulationReplayVerificationResult,
+    });
+    expect(result).toBe("simulation-replay-not-run");
+  });
 });
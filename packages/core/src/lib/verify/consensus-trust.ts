@@ -51,6 +51,8 @@ export const CONSENSUS_VERIFIER_ERROR_CODES = [
   "opstack-consensus-verifier-pending",
   "linea-consensus-verifier-pending",
   "state-root-mismatch",
+  "state-root-block-mismatch",
+  "state-root-value-mismatch",
   "stale-consensus-envelope",
   "non-finalized-consensus-envelope",
   "invalid-checkpoint-hash",
@@ -60,12 +62,20 @@ export const CONSENSUS_VERIFIER_ERROR_CODES = [
   "update-verification-failed",
   "invalid-finality-update-json",
   "finality-verification-failed",
+  "signature-slot-mismatch",
+  "malformed-sync-aggregate",
+  "sync-period-mismatch",
   "missing-execution-payload",
+  "execution-payload-unproven",
+  "finality-lineage-mismatch",
   "invalid-proof-payload",
   "envelope-state-root-mismatch",
   "envelope-block-number-mismatch",
+  "l2-output-root-unproven",
   "invalid-expected-state-root",
   "missing-policy-state-root",
+  "invalid-input",
+  "verification-panic",
 ] as const;
 
 export type ConsensusVerifierErrorCode =
@@ -80,6 +90,8 @@ const CONSENSUS_ERROR_CODE_TO_TRUST_REASON: Readonly<
   "opstack-consensus-verifier-pending": "opstack-consensus-verifier-pending",
   "linea-consensus-verifier-pending": "linea-consensus-verifier-pending",
   "state-root-mismatch": "state-root-mismatch-flag",
+  "state-root-block-mismatch": "state-root-mismatch-flag",
+  "state-root-value-mismatch": "state-root-mismatch-flag",
   "stale-consensus-envelope": "stale-consensus-envelope",
   "non-finalized-consensus-envelope": "non-finalized-consensus-envelope",
   // Beacon parsing / verification failures are deterministic payload integrity
@@ -91,12 +103,22 @@ const CONSENSUS_ERROR_CODE_TO_TRUST_REASON: Readonly<
   "update-verification-failed": "invalid-proof-payload",
   "invalid-finality-update-json": "invalid-proof-payload",
   "finality-verification-failed": "invalid-proof-payload",
+  "signature-slot-mismatch": "invalid-proof-payload",
+  "malformed-sync-aggregate": "invalid-proof-payload",
+  "sync-period-mismatch": "invalid-proof-payload",
   "missing-execution-payload": "invalid-proof-payload",
+  "execution-payload-unproven": "invalid-proof-payload",
+  "finality-lineage-mismatch": "invalid-proof-payload",
   "invalid-proof-payload": "invalid-proof-payload",
   "envelope-state-root-mismatch": "invalid-proof-payload",
   "envelope-block-number-mismatch": "invalid-proof-payload",
+  "l2-output-root-unproven": "invalid-proof-payload",
   "invalid-expected-state-root": "invalid-expected-state-root",
   "missing-policy-state-root": "missing-consensus-or-policy-proof",
+  // Malformed package fields caught before any verification ran.
+  "invalid-input": "invalid-proof-payload",
+  // Helios panicked on adversarial input; the payload is not trustworthy.
+  "verification-panic": "invalid-proof-payload",
 };
 
 const CONSENSUS_VERIFIER_ERROR_CODE_SET: ReadonlySet<string> = new Set(
@@ -65,11 +65,53 @@ export type SignatureCheckBundle = {
 };
 
 export type ConsensusVerificationResult = {
+  schema_version?: number;
   valid: boolean;
   verified_state_root: string | null;
   verified_block_number: number | null;
+  verified_receipts_root?: string | null;
+  /** Blob gas fields of the finalized execution payload; null before Cancun. */
+  verified_blob_gas_used?: number | null;
+  verified_excess_blob_gas?: number | null;
+  /** Finalized execution payload header fields (beacon mode only). */
+  verified_block_header?: {
+    block_hash: string;
+    parent_hash: string;
+    fee_recipient: string;
+    state_root: string;
+    receipts_root: string;
+    prev_randao: string;
+    block_number: number;
+    gas_limit: number;
+    gas_used: number;
+    timestamp: number;
+    /** Decimal wei. */
+    base_fee_per_gas: string;
+  } | null;
   state_root_matches: boolean;
+  /** Expected or acceptable root the verified root matched; null on a mismatch. */
+  matched_state_root?: string | null;
   sync_committee_participants: number;
+  sync_committee_size?: number | null;
+  participation_fraction?: number | null;
+  finalized_fork?: string | null;
+  next_sync_committee_root?: string | null;
+  /** Store finalized slot; set only for bootstrapOnly pre-checks. */
+  bootstrap_finalized_slot?: number | null;
+  update_results?: Array<{
+    index: number;
+    applied: boolean;
+    error: string | null;
+    /** Equal before/after means the update only rotated the sync committee. */
+    finalized_slot_before?: number | null;
+    finalized_slot_after?: number | null;
+  }> | null;
+  /** OP Stack output root proving the envelope header against the L1 root. */
+  l2_output_root?: string | null;
+  /** Every malformed input field; set only with error_code "invalid-input". */
+  input_errors?: string[] | null;
+  /** Verifier was built with the offline-only feature. */
+  offline?: boolean;
   error: string | null;
   error_code?: string | null;
   checks: Array<{
@@ -78,8 +120,24 @@ export type ConsensusVerificationResult = {
     passed: boolean;
     detail: string | null;
   }>;
+  /** Passed and total entries of `checks`, for "N/M checks passed" summaries. */
+  checks_passed?: number;
+  checks_total?: number;
 };
 
+/** Verdict-only view returned by `verify_consensus_proof_summary`. */
+export type ConsensusVerificationSummary = Pick<
+  ConsensusVerificationResult,
+  | "schema_version"
+  | "valid"
+  | "error_code"
+  | "verified_state_root"
+  | "verified_block_number"
+  | "state_root_matches"
+  | "checks_passed"
+  | "checks_total"
+>;
+
 export type EvidenceVerificationReport = {
   proposer: string | null;
   targetWarnings: TransactionWarning[];
@@ -107,6 +165,7 @@ export interface VerifyEvidenceOptions {
 }
 
 export type SimulationReplayVerificationResult = {
+  schemaVersion?: number;
   executed: boolean;
   success: boolean;
   reason:
@@ -116,10 +175,14 @@ export type SimulationReplayVerificationResult = {
         | "simulation-replay-not-run"
         | "simulation-replay-exec-error"
         | "simulation-witness-incomplete"
+        | "simulation-witness-only-logs-present"
         | "simulation-replay-mismatch-success"
         | "simulation-replay-mismatch-return-data"
         | "simulation-replay-mismatch-logs"
         | "simulation-replay-mismatch-gas"
+        | "simulation-replay-mismatch-create2"
+        | "simulation-replay-mismatch-post-state-root"
+        | "invalid-input"
       >;
   error?: string | null;
   replayLogs?: Array<{
@@ -127,7 +190,98 @@ export type SimulationReplayVerificationResult = {
     topics: string[];
     data: string;
   }>;
+  /** `replayLogs` grouped by lowercase emitting address, in emission order. */
+  logsByAddress?: Record<
+    string,
+    Array<{
+      address: string;
+      topics: string[];
+      data: string;
+    }>
+  > | null;
   replayNativeTransfers?: NativeTransfer[];
+  /** Sum of replayNativeTransfers values in wei, excluding self-transfers. */
+  totalNativeValueMoved?: string | null;
+  /** Native value recipients missing from expectedRecipients; advisory only. */
+  unexpectedRecipients?: string[] | null;
+  /** Replay ran against debug stateOverrides; never evidence for the package. */
+  overridden?: boolean;
+  /** Ordered SLOAD/SSTORE trace, present only when traceStorageOps was requested. */
+  storageOps?: Array<{
+    pc: number;
+    op: "SLOAD" | "SSTORE";
+    address: string;
+    slot: string;
+    value: string;
+  }> | null;
+  /** Logs a failed replay emitted before reverting; discarded by the revert, never compared. */
+  preRevertLogs?: Array<{
+    address: string;
+    topics: string[];
+    data: string;
+  }> | null;
+  /** Expected gas ceiling minus replay gas, signed decimal; negative on a gas mismatch. */
+  gasHeadroom?: string | null;
+  /** Advisory: replay gas fell below gasUnderrunThresholdPercent of the expected ceiling. */
+  gasUnderrunWarning?: string | null;
+  /** Why the replay halted (e.g. "OutOfGas", "InvalidOpcode"); absent unless it halted. */
+  haltReason?: string | null;
+  /** Gas per opcode category (storage, calls, memory, ...); present only when profileGas was requested. */
+  gasProfile?: Record<string, number> | null;
+  /** Advisory: a contract was re-entered while an outer frame of it was running. */
+  reentrancyDetected?: boolean;
+  /** Operator-facing next step for a failed replay; `reason` stays the machine-readable code. */
+  hint?: string | null;
+  /** Verifier was built with the offline-only feature. */
+  offline?: boolean;
+  create2Deployments?: Array<{
+    deployer: string;
+    salt: string;
+    initCodeHash: string;
+    address: string;
+  }> | null;
+  /** Echo of the replay input's native currency metadata; values stay in wei. */
+  nativeDecimals?: number | null;
+  nativeSymbol?: string | null;
+  /** Hardfork the replay ran under (e.g. "cancun"); set whenever it executed. */
+  specUsed?: string | null;
+  /** Transaction fields the replay ran with; present only when includeTxEnvelope was requested. */
+  replayTxEnvelope?: {
+    caller: string;
+    to: string | null;
+    value: string;
+    data: string;
+    gasLimit: number;
+    gasPrice: string;
+    nonce: number;
+    chainId: number;
+  } | null;
+  /** Every malformed input field; set only with reason "invalid-input". */
+  inputErrors?: string[] | null;
+  /** "replay" when logs/transfers were derived locally (witness-only), not packaged. */
+  effectsSource?: "packaged" | "replay" | null;
+  /** State root after replay; set only when verifyPostStateRoot was requested. */
+  postStateRoot?: string | null;
+  /** Accounts whose nonce the replay changed; empty when it reverted or halted. */
+  nonceDiffs?: Array<{ address: string; before: number; after: number }> | null;
+  /** Like `success`, but a gas-only difference reads "effects-matched-gas-differs". */
+  overallStatus?: "matched" | "effects-matched-gas-differs" | "mismatched";
+};
+
+/** Verdict-only view returned by `verify_simulation_replay_summary`. */
+export type SimulationReplaySummary = Pick<
+  SimulationReplayVerificationResult,
+  | "schemaVersion"
+  | "executed"
+  | "success"
+  | "reason"
+  | "error"
+  | "overallStatus"
+  | "overridden"
+> & {
+  /** Matched fields of `comparison`, out of `comparedEffects`; null when not executed. */
+  matchedEffects: number | null;
+  comparedEffects: number | null;
 };
 
 interface BuildReportSourcesOptions {
@@ -263,6 +417,8 @@ function deriveSimulationVerificationReason(
   if (!options.evidence.simulationWitness) return "missing-simulation-witness";
   if (!options.simulationWitnessVerification?.valid) return "simulation-witness-proof-failed";
   if (!options.simulationReplayVerification) return "simulation-replay-not-run";
+  // A what-if replay over overridden state does not verify the package.
+  if (options.simulationReplayVerification.overridden) return "simulation-replay-not-run";
   if (options.simulationReplayVerification.success === false) {
     // When replay failed, propagate the specific failure reason.
     // TypeScript can't narrow the reason based on success === false, but
@@ -0,0 +1,46 @@
+#!/usr/bin/env bash
+# Checks the offline-only guarantee against the resolved Cargo graph rather
+# than the manifest text `build.rs` scans: no verification-path crate may
+# resolve a network-capable feature or reach a network client crate.
+#
+# Tauri itself pulls `reqwest`/`hyper` (updater, HTTP plugin plumbing); those
+# paths are outside the guarantee and are reported only when they run
+# through a verification-path crate.
+set -euo pipefail
+
+ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/../.." && pwd)"
+MANIFEST="$ROOT_DIR/apps/desktop/src-tauri/Cargo.toml"
+
+VERIFICATION_CRATES='^(alloy|alloy-[a-z0-9-]+|revm|revm-[a-z0-9-]+|helios-consensus-core|tree_hash|tracing|tracing-subscriber) '
+NETWORK_FEATURES='^(alloy|revm) feature "(default|full|network|providers|provider-http|provider-ws|provider-ipc|pubsub|reqwest|rpc-client|transports|alloydb)"'
+NETWORK_CRATES=(reqwest hyper tokio-tungstenite alloy-provider alloy-transport-http alloy-pubsub revm-database-alloydb)
+
+cargo_tree() {
+  cargo tree --locked --manifest-path "$MANIFEST" --features offline-only --prefix none "$@"
+}
+
+status=0
+
+features="$(cargo_tree -e features | grep -E "$NETWORK_FEATURES" | sort -u || true)"
+if [[ -n "$features" ]]; then
+  echo "offline-only: network-capable features resolved on verification-path crates:"
+  echo "$features"
+  status=1
+fi
+
+for crate in "${NETWORK_CRATES[@]}"; do
+  # `cargo tree -i` fails when the crate is not in the graph at all; the
+  # first line is the crate itself.
+  dependents="$(cargo_tree -e normal -i "$crate" 2>/dev/null | tail -n +2 || true)"
+  reached_via="$(echo "$dependents" | grep -E "$VERIFICATION_CRATES" | sort -u || true)"
+  if [[ -n "$reached_via" ]]; then
+    echo "offline-only: \`$crate\` is reachable from verification-path crates:"
+    echo "$reached_via"
+    status=1
+  fi
+done
+
+if [[ "$status" -eq 0 ]]; then
+  echo "offline-only: resolved graph is clean"
+fi
+exit "$status"